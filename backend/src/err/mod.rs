@@ -1,8 +0,0 @@
-pub mod add_player;
-pub mod ai;
-pub mod draw_cards;
-pub mod game_start;
-pub mod play_card;
-pub mod player_exist;
-pub mod player_turn;
-pub mod status;
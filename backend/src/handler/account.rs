@@ -0,0 +1,117 @@
+use crate::handler::util::response::{ErrMsg, ErrorResponse};
+use crate::repo::account_repo::{Account, AccountError, AccountRepo};
+use crate::AuthService;
+use actix_web::error::BlockingError;
+use actix_web::{get, http::StatusCode, post, web, HttpRequest, HttpResponse};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Debug)]
+pub struct Credentials {
+    username: String,
+    password: String,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SuccessResponse {
+    account_token: String,
+    account: Account,
+}
+
+/// Registers a new account and logs the player in.
+#[post("/account")]
+pub async fn register(
+    credentials: web::Json<Credentials>,
+    auth_service: web::Data<AuthService>,
+    accounts: web::Data<AccountRepo>,
+) -> HttpResponse {
+    let Credentials { username, password } = credentials.into_inner();
+
+    // hashing the password takes a while, it must not block the other requests
+    match web::block(move || accounts.register(&username, &password)).await {
+        Err(error) => blocking_error_response(error),
+        Ok(account) => HttpResponse::Created().json(SuccessResponse {
+            account_token: auth_service.issue_account_token(&account),
+            account,
+        }),
+    }
+}
+
+#[post("/account/login")]
+pub async fn login(
+    credentials: web::Json<Credentials>,
+    auth_service: web::Data<AuthService>,
+    accounts: web::Data<AccountRepo>,
+) -> HttpResponse {
+    let Credentials { username, password } = credentials.into_inner();
+
+    match web::block(move || accounts.login(&username, &password)).await {
+        Err(error) => blocking_error_response(error),
+        Ok(account) => HttpResponse::Ok().json(SuccessResponse {
+            account_token: auth_service.issue_account_token(&account),
+            account,
+        }),
+    }
+}
+
+/// The account the account token belongs to.
+#[get("/account")]
+pub async fn get_account(
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    accounts: web::Data<AccountRepo>,
+) -> HttpResponse {
+    match get_account_response(request, auth_service, accounts) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn get_account_response(
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    accounts: web::Data<AccountRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let account = auth_service.extract_account(&request)?.ok_or_else(|| {
+        HttpResponse::Unauthorized().json(ErrMsg::new_from_scratch("Log in to see your account"))
+    })?;
+
+    let account = accounts
+        .find(account.id)
+        .map_err(ErrorResponse::error_response)?;
+
+    Ok(HttpResponse::Ok().json(account))
+}
+
+fn blocking_error_response(error: BlockingError<AccountError>) -> HttpResponse {
+    match error {
+        BlockingError::Error(error) => error.error_response(),
+        BlockingError::Canceled => HttpResponse::InternalServerError()
+            .json(ErrMsg::new_from_scratch("Account request was canceled")),
+    }
+}
+
+impl ErrorResponse for AccountError {
+    fn status_code(&self) -> StatusCode {
+        use AccountError::*;
+        match self {
+            InvalidUsername(_) | WeakPassword => StatusCode::BAD_REQUEST,
+            UsernameTaken(_) => StatusCode::CONFLICT,
+            InvalidCredentials => StatusCode::UNAUTHORIZED,
+            NoSuchAccount(_) => StatusCode::NOT_FOUND,
+            Storage(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        use AccountError::*;
+        match self {
+            InvalidUsername(_) => Some("INVALID_USERNAME"),
+            WeakPassword => Some("WEAK_PASSWORD"),
+            UsernameTaken(_) => Some("USERNAME_TAKEN"),
+            InvalidCredentials => Some("INVALID_CREDENTIALS"),
+            NoSuchAccount(_) => Some("NO_SUCH_ACCOUNT"),
+            Storage(_) => None,
+        }
+    }
+}
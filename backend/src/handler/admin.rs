@@ -0,0 +1,414 @@
+use crate::handler::service::admin::{AdminError, AdminService};
+use crate::handler::util::response::ErrorResponse;
+use crate::handler::util::safe_lock::safe_lock;
+use crate::session::ai_driver::drive_ai;
+use crate::session::game_session::GameSession;
+use crate::{AuthService, InMemoryGameRepo};
+use actix_web::{delete, get, http::StatusCode, post, web, HttpRequest, HttpResponse};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uno_core::cards::card::Card;
+use uno_core::err::force_finish::ForceFinishError;
+use uno_core::gamestate::command::Command;
+use uno_core::gamestate::game::GameStatus;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminGameListing {
+    #[serde(rename = "gameID")]
+    game_id: String,
+    status: GameStatus,
+    author: String,
+    humans: usize,
+    ais: usize,
+    /// Players connected over a WebSocket.
+    connected: usize,
+    spectators: usize,
+    public: bool,
+    fair: bool,
+    turn: u64,
+}
+
+/// Every game on the server, private ones included.
+#[get("/admin/games")]
+pub async fn admin_list_games(
+    request: HttpRequest,
+    admin: web::Data<AdminService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    match admin_list_games_response(request, admin, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn admin_list_games_response(
+    request: HttpRequest,
+    admin: web::Data<AdminService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    admin
+        .check(&request)
+        .map_err(ErrorResponse::error_response)?;
+
+    let mut listings = vec![];
+    for session in game_repo.games() {
+        let session = safe_lock(&session)?;
+        let game = session.game();
+
+        let humans = game.players().iter().filter(|p| p.is_human()).count();
+        listings.push(AdminGameListing {
+            game_id: game.id.clone(),
+            status: game.status(),
+            author: game
+                .find_author()
+                .map_or_else(String::new, |author| author.name()),
+            humans,
+            ais: game.players().len() - humans,
+            connected: session.connected_players().len(),
+            spectators: session.spectators().count(),
+            public: session.is_public(),
+            fair: game.fairness().is_some(),
+            turn: game.turn(),
+        });
+    }
+    listings.sort_by(|a, b| a.game_id.cmp(&b.game_id));
+
+    Ok(HttpResponse::Ok().json(listings))
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminPlayer {
+    name: String,
+    is_ai: bool,
+    is_author: bool,
+    cards: Vec<Card>,
+    position: Option<usize>,
+    forfeited: bool,
+    connected: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_id: Option<i64>,
+}
+
+/// Everything about a game, the hands of all players included.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminGameView {
+    #[serde(rename = "gameID")]
+    game_id: String,
+    status: GameStatus,
+    turn: u64,
+    players: Vec<AdminPlayer>,
+    current_player: Option<String>,
+    top_card: Option<Card>,
+    draw_pile: usize,
+    discard_pile: usize,
+    is_clockwise: bool,
+    wins: BTreeMap<String, usize>,
+    rematch: Option<Vec<String>>,
+    public: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed_commitment: Option<String>,
+    spectators: usize,
+}
+
+impl AdminGameView {
+    fn new(session: &GameSession) -> Self {
+        let game = session.game();
+        let running = game.status() == GameStatus::Running;
+        let connected = session.connected_players();
+
+        Self {
+            game_id: game.id.clone(),
+            status: game.status(),
+            turn: game.turn(),
+            players: game
+                .players()
+                .iter()
+                .map(|player| AdminPlayer {
+                    name: player.name(),
+                    is_ai: !player.is_human(),
+                    is_author: player.is_author(),
+                    cards: player.cards(),
+                    position: player.position(),
+                    forfeited: game.forfeited_players().contains(&player.name()),
+                    connected: connected.contains(&player.name()),
+                    account_id: session.account_of(&player.name()),
+                })
+                .collect(),
+            current_player: match running {
+                true => game.get_current_player().map(|player| player.name()),
+                false => None,
+            },
+            top_card: match running {
+                true => Some(game.deck().top_discard_card().clone()),
+                false => None,
+            },
+            draw_pile: game.deck().draw_pile_size(),
+            discard_pile: game.deck().discard_pile_size(),
+            is_clockwise: game.is_clockwise,
+            wins: game.wins().clone(),
+            rematch: game.rematch_confirmed().cloned(),
+            public: session.is_public(),
+            seed_commitment: game.fairness().map(|fairness| fairness.commitment()),
+            spectators: session.spectators().count(),
+        }
+    }
+}
+
+#[get("/admin/game/{gameID}")]
+pub async fn admin_get_game(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    admin: web::Data<AdminService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    match admin_get_game_response(route_params.into_inner(), request, admin, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn admin_get_game_response(
+    game_id: String,
+    request: HttpRequest,
+    admin: web::Data<AdminService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    admin
+        .check(&request)
+        .map_err(ErrorResponse::error_response)?;
+
+    let game = game_repo.get_game_by_id(game_id)?;
+    let view = AdminGameView::new(&*safe_lock(&game)?);
+
+    Ok(HttpResponse::Ok().json(view))
+}
+
+/// Ends the running game right away, the players still in it are placed by how few cards they hold.
+#[post("/admin/game/{gameID}/finish")]
+pub async fn admin_finish_game(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    admin: web::Data<AdminService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    match admin_finish_game_response(route_params.into_inner(), request, admin, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn admin_finish_game_response(
+    game_id: String,
+    request: HttpRequest,
+    admin: web::Data<AdminService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    admin
+        .check(&request)
+        .map_err(ErrorResponse::error_response)?;
+
+    let game = game_repo.get_game_by_id(game_id)?;
+
+    safe_lock(&game)?
+        .apply(Command::ForceFinish)
+        .map_err(ErrorResponse::error_response)?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Deletes the game, closing every connection to it and invalidating every token for it.
+#[delete("/admin/game/{gameID}")]
+pub async fn admin_delete_game(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    admin: web::Data<AdminService>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    match admin_delete_game_response(
+        route_params.into_inner(),
+        request,
+        admin,
+        auth_service,
+        game_repo,
+    ) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn admin_delete_game_response(
+    game_id: String,
+    request: HttpRequest,
+    admin: web::Data<AdminService>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    admin
+        .check(&request)
+        .map_err(ErrorResponse::error_response)?;
+
+    let game = game_repo.get_game_by_id(game_id.clone())?;
+
+    game_repo.remove_game(&game_id);
+    auth_service.revoke_game(&game_id);
+    safe_lock(&game)?.disconnect_all();
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Removes the player from the lobby, or makes them give up the running game.
+/// Their token stops working either way.
+#[delete("/admin/game/{gameID}/player/{name}")]
+pub async fn admin_kick_player(
+    route_params: web::Path<(String, String)>,
+    request: HttpRequest,
+    admin: web::Data<AdminService>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let (game_id, kicked_name) = route_params.into_inner();
+    match admin_kick_player_response(
+        game_id,
+        kicked_name,
+        request,
+        admin,
+        auth_service,
+        game_repo,
+    ) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn admin_kick_player_response(
+    game_id: String,
+    kicked_name: String,
+    request: HttpRequest,
+    admin: web::Data<AdminService>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    admin
+        .check(&request)
+        .map_err(ErrorResponse::error_response)?;
+
+    let game = game_repo.get_game_by_id(game_id.clone())?;
+    {
+        let mut session = safe_lock(&game)?;
+
+        let author = session
+            .game()
+            .find_author()
+            .map_or_else(String::new, |author| author.name());
+        let is_human = session
+            .game()
+            .find_player(kicked_name.clone())
+            .is_some_and(|player| player.is_human());
+        // the lobby commands are the author's, the author themselves can only leave
+        let command = match session.game().status() {
+            GameStatus::Lobby if !is_human => Command::RemoveAi {
+                author,
+                player: kicked_name.clone(),
+            },
+            GameStatus::Lobby if kicked_name != author => Command::Kick {
+                author,
+                player: kicked_name.clone(),
+            },
+            GameStatus::Lobby => Command::Leave {
+                player: kicked_name.clone(),
+            },
+            _ => Command::Forfeit {
+                player: kicked_name.clone(),
+            },
+        };
+
+        session
+            .apply(command)
+            .map_err(ErrorResponse::error_response)?;
+        session.disconnect(&kicked_name);
+
+        if !session.game().players().iter().any(|p| p.is_human()) {
+            game_repo.remove_game(&game_id);
+            auth_service.revoke_game(&game_id);
+        }
+    }
+    auth_service.revoke(&game_id, &kicked_name);
+
+    // an AI may be at turn after the player who was made to give up
+    drive_ai(game);
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Deserialize, Debug)]
+pub struct NoticeBody {
+    text: String,
+}
+
+/// Sends a notice to every player and spectator of every game, e.g. that the server restarts soon.
+#[post("/admin/notice")]
+pub async fn admin_notice(
+    request: HttpRequest,
+    request_body: web::Json<NoticeBody>,
+    admin: web::Data<AdminService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    match admin_notice_response(request, request_body.into_inner(), admin, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn admin_notice_response(
+    request: HttpRequest,
+    request_body: NoticeBody,
+    admin: web::Data<AdminService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    admin
+        .check(&request)
+        .map_err(ErrorResponse::error_response)?;
+
+    let text = request_body.text.trim();
+    if text.is_empty() {
+        return Err(AdminError::EmptyNotice.error_response());
+    }
+
+    for session in game_repo.games() {
+        safe_lock(&session)?.notify(text);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+impl ErrorResponse for AdminError {
+    fn status_code(&self) -> StatusCode {
+        use AdminError::*;
+        match self {
+            MissingKey | InvalidKey => StatusCode::UNAUTHORIZED,
+            EmptyNotice => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        use AdminError::*;
+        match self {
+            MissingKey | InvalidKey => Some("INVALID_ADMIN_KEY"),
+            EmptyNotice => None,
+        }
+    }
+}
+
+impl ErrorResponse for ForceFinishError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::CONFLICT
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        Some("GAME_NOT_RUNNING")
+    }
+}
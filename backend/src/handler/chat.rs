@@ -0,0 +1,100 @@
+use crate::handler::util::response::ErrorResponse;
+use crate::handler::util::safe_lock::safe_lock;
+use crate::{AuthService, InMemoryGameRepo};
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct ChatRequestBody {
+    text: String,
+}
+
+/// Same as the CHAT command over the WebSocket, for clients that are not connected.
+#[post("/game/{gameID}/chat")]
+pub async fn chat(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    request_body: web::Json<ChatRequestBody>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let game_id = route_params.into_inner();
+    match chat_response(game_id, request, request_body, auth_service, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn chat_response(
+    game_id: String,
+    request: HttpRequest,
+    request_body: web::Json<ChatRequestBody>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let (game_id_from_token, player_name) = auth_service.extract_data(&request)?;
+
+    let game_id = game_id_from_token.check(game_id)?;
+
+    let game = game_repo.get_game_by_id(game_id)?;
+
+    safe_lock(&game)?
+        .chat(&player_name.into_inner(), request_body.into_inner().text)
+        .map_err(ErrorResponse::error_response)?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MuteRequestBody {
+    name: String,
+    #[serde(default = "default_muted")]
+    muted: bool,
+    /// Whether the name belongs to a spectator rather than a player
+    #[serde(default)]
+    spectator: bool,
+}
+
+fn default_muted() -> bool {
+    true
+}
+
+#[post("/game/{gameID}/mute")]
+pub async fn mute(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    request_body: web::Json<MuteRequestBody>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let game_id = route_params.into_inner();
+    match mute_response(game_id, request, request_body, auth_service, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn mute_response(
+    game_id: String,
+    request: HttpRequest,
+    request_body: web::Json<MuteRequestBody>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let (game_id_from_token, player_name) = auth_service.extract_data(&request)?;
+
+    let game_id = game_id_from_token.check(game_id)?;
+
+    let game = game_repo.get_game_by_id(game_id)?;
+
+    safe_lock(&game)?
+        .set_muted(
+            &player_name.into_inner(),
+            &request_body.name,
+            request_body.muted,
+            request_body.spectator,
+        )
+        .map_err(ErrorResponse::error_response)?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
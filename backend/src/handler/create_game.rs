@@ -1,63 +1,123 @@
-use crate::gamestate::game::Game;
-use crate::handler::service::auth::AuthService;
-use crate::handler::util::response::ErrMsg;
+use crate::handler::service::auth::{AuthService, TokenPair};
+use crate::handler::util::response::ErrorResponse;
+use crate::handler::util::safe_lock::safe_lock;
 use crate::InMemoryGameRepo;
-use actix_web::{post, web, HttpResponse, Responder};
+use actix_web::{http::StatusCode, post, web, HttpRequest, HttpResponse, Responder};
 use serde::Deserialize;
 use serde::Serialize;
-use std::sync::Mutex;
-
-use super::util::safe_lock::safe_lock;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use uno_core::gamestate::game::Game;
+use uno_core::gamestate::MAX_PLAYERS;
 
 #[derive(Deserialize, Debug)]
 pub struct RequestBody {
+    /// Ignored for logged in players, who play under their username
+    #[serde(default)]
     name: String,
     ais: String,
+    /// Whether the game should be listed in the lobby browser
+    #[serde(default)]
+    public: bool,
+    /// Whether the shuffles are provably fair, see `handler::fairness`
+    #[serde(default)]
+    fair: bool,
 }
 
 #[derive(Serialize, Debug)]
 pub struct SuccessResponse {
     #[serde(rename(serialize = "gameID", deserialize = "gameID"))]
     game_id: String,
-    token: String,
+    #[serde(flatten)]
+    tokens: TokenPair,
 }
 
 #[post("/game")]
 pub async fn create_game(
+    request: HttpRequest,
     request_body: web::Json<RequestBody>,
     auth_service: web::Data<AuthService>,
-    game_repo: web::Data<Mutex<InMemoryGameRepo>>,
+    game_repo: web::Data<InMemoryGameRepo>,
 ) -> impl Responder {
-    let author_name = &request_body.name;
+    let account = match auth_service.extract_account(&request) {
+        Err(response) => return response,
+        Ok(account) => account,
+    };
+    let author_name = match &account {
+        Some(account) => &account.username,
+        None => &request_body.name,
+    };
 
     if author_name.is_empty() {
-        return HttpResponse::BadRequest().json(ErrMsg::new_from_scratch(
-            "Name of the player cannot be empty",
-        ));
+        return CreateGameError::EmptyName.error_response();
     }
 
     let ais = match request_body.ais.parse::<usize>() {
+        Ok(ais) if ais >= MAX_PLAYERS => return CreateGameError::TooManyAis.error_response(),
         Ok(ais) => ais,
-        Err(_) => {
-            return HttpResponse::BadRequest().json(ErrMsg::new_from_scratch(
-                "Number of AIS must be positive number",
-            ))
-        }
+        Err(_) => return CreateGameError::InvalidAiCount.error_response(),
     };
 
-    let game = Game::new_with_ai(author_name.clone(), ais);
+    let mut game = Game::new_with_ai(author_name.clone(), ais);
+    if request_body.fair {
+        game.enable_fairness();
+    }
     let game_id = game.id.clone();
-    let jwt = auth_service.generate_jwt(author_name, &game_id);
+    let tokens = match &account {
+        Some(account) => auth_service.issue_account_tokens(account, &game_id),
+        None => auth_service.issue_tokens(author_name, &game_id),
+    };
 
-    let mut game_repo = match safe_lock(&game_repo) {
+    let session = game_repo.add_game(game);
+    match safe_lock(&session) {
         Err(response) => return response,
-        Ok(repo) => repo,
-    };
+        Ok(mut session) => {
+            session.set_public(request_body.public);
+            if let Some(account) = &account {
+                session.set_account(&account.username, account.id);
+            }
+        }
+    }
 
-    game_repo.add_game(game);
+    HttpResponse::Created().json(SuccessResponse { game_id, tokens })
+}
 
-    HttpResponse::Created().json(SuccessResponse {
-        game_id,
-        token: jwt,
-    })
+#[derive(Debug)]
+pub enum CreateGameError {
+    EmptyName,
+    InvalidAiCount,
+    TooManyAis,
+}
+
+impl Error for CreateGameError {}
+
+impl Display for CreateGameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use CreateGameError::*;
+
+        match self {
+            EmptyName => write!(f, "Name of the player cannot be empty"),
+            InvalidAiCount => write!(f, "Number of AIS must be positive number"),
+            TooManyAis => write!(
+                f,
+                "Number of AIS must be less than {}, the most players a game can have",
+                MAX_PLAYERS
+            ),
+        }
+    }
+}
+
+impl ErrorResponse for CreateGameError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        use CreateGameError::*;
+        match self {
+            EmptyName => Some("EMPTY_NAME"),
+            InvalidAiCount => Some("INVALID_AI_COUNT"),
+            TooManyAis => Some("TOO_MANY_AIS"),
+        }
+    }
 }
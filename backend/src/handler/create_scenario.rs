@@ -0,0 +1,56 @@
+use crate::handler::service::auth::{AuthService, TokenPair};
+use crate::handler::util::response::ErrorResponse;
+use crate::session::ai_driver::drive_ai;
+use crate::InMemoryGameRepo;
+use actix_web::{http::StatusCode, post, web, HttpResponse};
+use serde::Serialize;
+use std::collections::HashMap;
+use uno_core::err::game_build::GameBuildError;
+use uno_core::gamestate::builder::GameBuilder;
+
+#[derive(Serialize, Debug)]
+pub struct SuccessResponse {
+    #[serde(rename(serialize = "gameID", deserialize = "gameID"))]
+    game_id: String,
+    /// Tokens of all human players, keyed by their names.
+    tokens: HashMap<String, TokenPair>,
+}
+
+/// Development only: creates a running game in the exact state described by the body.
+#[post("/dev/game")]
+pub async fn create_scenario(
+    request_body: web::Json<GameBuilder>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let game = match request_body.into_inner().build() {
+        Err(error) => return error.error_response(),
+        Ok(game) => game,
+    };
+    let game_id = game.id.clone();
+
+    let tokens = game
+        .players()
+        .iter()
+        .filter(|player| player.is_human())
+        .map(|player| {
+            let tokens = auth_service.issue_tokens(&player.name(), &game_id);
+            (player.name(), tokens)
+        })
+        .collect();
+
+    // the scenario may start with an AI at turn
+    drive_ai(game_repo.add_game(game));
+
+    HttpResponse::Created().json(SuccessResponse { game_id, tokens })
+}
+
+impl ErrorResponse for GameBuildError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        None
+    }
+}
@@ -1,54 +1,102 @@
-use crate::err::draw_cards::PlayerDrawError;
-use crate::handler::util::response::{ErrMsg, TypedErrMsg};
+use crate::handler::util::idempotency::apply_once;
+use crate::handler::util::response::{ErrMsg, ErrorResponse};
 use crate::handler::util::safe_lock::safe_lock;
+use crate::session::ai_driver::drive_ai;
 use crate::{AuthService, InMemoryGameRepo};
-use actix_web::{post, web, HttpRequest, HttpResponse};
-use std::sync::Mutex;
+use actix_web::error::JsonPayloadError;
+use actix_web::{http::StatusCode, post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use uno_core::err::draw_cards::PlayerDrawError;
+use uno_core::gamestate::command::Command;
 
+#[derive(Deserialize, Debug)]
+pub struct RequestBody {
+    /// Turn of the game the cards are meant to be drawn in, nothing is drawn if the game moved on
+    #[serde(rename(serialize = "expectedTurn", deserialize = "expectedTurn"))]
+    expected_turn: Option<u64>,
+}
+
+/// The body is optional, drawing needs nothing but the token.
 #[post("/game/{gameID}/drawnCards")]
 pub async fn draw_card(
     route_params: web::Path<String>,
     request: HttpRequest,
+    request_body: web::Bytes,
     auth_service: web::Data<AuthService>,
-    game_repo: web::Data<Mutex<InMemoryGameRepo>>,
+    game_repo: web::Data<InMemoryGameRepo>,
 ) -> HttpResponse {
     let game_id = route_params.into_inner();
-    match draw_card_response(game_id, game_repo, auth_service, request) {
+    let expected_turn = match parse_expected_turn(&request_body) {
+        Ok(expected_turn) => expected_turn,
+        Err(r) => return r,
+    };
+    match draw_card_response(game_id, expected_turn, game_repo, auth_service, request) {
         Ok(r) => r,
         Err(r) => r,
     }
 }
 
+/// Only a missing body means no expected turn, a body that cannot be read is rejected
+/// rather than drawing without the check the client asked for.
+fn parse_expected_turn(request_body: &[u8]) -> Result<Option<u64>, HttpResponse> {
+    if request_body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(None);
+    }
+
+    match serde_json::from_slice::<RequestBody>(request_body) {
+        Ok(body) => Ok(body.expected_turn),
+        Err(error) => {
+            Err(HttpResponse::BadRequest().json(ErrMsg::new(JsonPayloadError::Deserialize(error))))
+        }
+    }
+}
+
 fn draw_card_response(
     game_id: String,
-    game_repo: web::Data<Mutex<InMemoryGameRepo>>,
+    expected_turn: Option<u64>,
+    game_repo: web::Data<InMemoryGameRepo>,
     auth_service: web::Data<AuthService>,
     request: HttpRequest,
 ) -> Result<HttpResponse, HttpResponse> {
-    let mut game_repo = safe_lock(&game_repo)?;
-
     let (game_id_from_token, player_name) = auth_service.extract_data(&request)?;
 
     let game_id = game_id_from_token.check(game_id)?;
 
-    let game = game_repo.get_game_by_id_mut(game_id)?;
+    let game = game_repo.get_game_by_id(game_id)?;
 
-    game.draw_cards(player_name.into_inner())?;
+    let player_name = player_name.into_inner();
+    let response = apply_once(
+        &request,
+        &mut *safe_lock(&game)?,
+        &player_name,
+        expected_turn,
+        Command::DrawCards {
+            player: player_name.clone(),
+        },
+    )?;
 
-    Ok(HttpResponse::NoContent().finish())
+    drive_ai(game);
+
+    Ok(response)
 }
 
-impl From<PlayerDrawError> for HttpResponse {
-    fn from(error: PlayerDrawError) -> HttpResponse {
+impl ErrorResponse for PlayerDrawError {
+    fn status_code(&self) -> StatusCode {
+        use PlayerDrawError::*;
+        match self {
+            TurnError(_) | GameNotRunning(_) | CanPlayInstead => StatusCode::CONFLICT,
+            PlayerExistError(_) => StatusCode::BAD_REQUEST,
+            CreateStatusError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
         use PlayerDrawError::*;
-        match error {
-            TurnError(_) => HttpResponse::Conflict().json(TypedErrMsg::new("NOT_YOUR_TURN", error)),
-            PlayerExistError(_) => HttpResponse::BadRequest().json(ErrMsg::new(error)),
-            CanPlayInstead => HttpResponse::Conflict().json(TypedErrMsg::new("CANNOT_DRAW", error)),
-            ChainedAiError => HttpResponse::InternalServerError().json(ErrMsg::new(error)),
-            _ => {
-                todo!("React to CreateStatusError")
-            }
+        match self {
+            TurnError(_) => Some("NOT_YOUR_TURN"),
+            GameNotRunning(_) => Some("GAME_NOT_RUNNING"),
+            CanPlayInstead => Some("CANNOT_DRAW"),
+            PlayerExistError(_) | CreateStatusError(_) => None,
         }
     }
 }
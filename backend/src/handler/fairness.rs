@@ -0,0 +1,141 @@
+use crate::handler::util::response::ErrorResponse;
+use crate::handler::util::safe_lock::safe_lock;
+use crate::{AuthService, InMemoryGameRepo};
+use actix_web::{get, http::StatusCode, post, web, HttpRequest, HttpResponse};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uno_core::err::fairness::FairnessError;
+use uno_core::gamestate::command::Command;
+use uno_core::gamestate::fairness::FairnessProof;
+
+/// Largest proof accepted for verification in bytes, every reshuffle of a long game adds about 5 KB to it.
+pub static MAX_PROOF_SIZE: usize = 1024 * 1024;
+
+#[derive(Deserialize, Debug)]
+pub struct EntropyBody {
+    entropy: String,
+}
+
+/// The player mixes their own randomness into the seed of a fair game, while it is in the lobby.
+/// Sending it again replaces what the player sent before.
+#[post("/game/{gameID}/entropy")]
+pub async fn add_entropy(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    request_body: web::Json<EntropyBody>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    match add_entropy_response(
+        route_params.into_inner(),
+        request,
+        request_body.into_inner(),
+        auth_service,
+        game_repo,
+    ) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn add_entropy_response(
+    game_id: String,
+    request: HttpRequest,
+    request_body: EntropyBody,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let (game_id_from_token, player_name) = auth_service.extract_data(&request)?;
+
+    let game_id = game_id_from_token.check(game_id)?;
+
+    let game = game_repo.get_game_by_id(game_id)?;
+
+    safe_lock(&game)?
+        .apply(Command::AddEntropy {
+            player: player_name.into_inner(),
+            entropy: request_body.entropy,
+        })
+        .map_err(ErrorResponse::error_response)?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// The fairness of a game before the seed is revealed.
+#[derive(Serialize, Debug)]
+pub struct Commitment {
+    commitment: String,
+    entropy: BTreeMap<String, String>,
+}
+
+/// The commitment of a fair game and the entropy mixed into it so far,
+/// or the whole proof once the game finished. No token needed.
+#[get("/game/{gameID}/fairness")]
+pub async fn get_fairness(
+    route_params: web::Path<String>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    match get_fairness_response(route_params.into_inner(), game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn get_fairness_response(
+    game_id: String,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let game = game_repo.get_game_by_id(game_id)?;
+    let session = safe_lock(&game)?;
+    let game = session.game();
+
+    if let Some(proof) = game.fairness_proof() {
+        return Ok(HttpResponse::Ok().json(proof));
+    }
+    match game.fairness() {
+        None => Err(FairnessError::NotFair.error_response()),
+        Some(fairness) => Ok(HttpResponse::Ok().json(Commitment {
+            commitment: fairness.commitment(),
+            entropy: fairness.entropy().clone(),
+        })),
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct Verification {
+    valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Re-runs the shuffles of a proof from `GET /game/{gameID}/fairness`, the same as the `verify_shuffle` binary of uno-core.
+/// Served at `POST /fairness/verify` with a body limit of `MAX_PROOF_SIZE`.
+pub async fn verify_fairness(request_body: web::Json<FairnessProof>) -> HttpResponse {
+    let error = request_body.verify().err().map(|error| error.to_string());
+
+    HttpResponse::Ok().json(Verification {
+        valid: error.is_none(),
+        error,
+    })
+}
+
+impl ErrorResponse for FairnessError {
+    fn status_code(&self) -> StatusCode {
+        use FairnessError::*;
+        match self {
+            NotFair | NotInLobby => StatusCode::CONFLICT,
+            NoSuchPlayer(_) => StatusCode::NOT_FOUND,
+            InvalidEntropy => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        use FairnessError::*;
+        match self {
+            NotFair => Some("GAME_NOT_FAIR"),
+            NotInLobby => Some("GAME_NOT_IN_LOBBY"),
+            NoSuchPlayer(_) => None,
+            InvalidEntropy => Some("INVALID_ENTROPY"),
+        }
+    }
+}
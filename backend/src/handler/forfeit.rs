@@ -0,0 +1,63 @@
+use crate::handler::util::response::ErrorResponse;
+use crate::handler::util::safe_lock::safe_lock;
+use crate::session::ai_driver::drive_ai;
+use crate::{AuthService, InMemoryGameRepo};
+use actix_web::{http::StatusCode, post, web, HttpRequest, HttpResponse};
+use uno_core::err::forfeit::ForfeitError;
+use uno_core::gamestate::command::Command;
+
+/// The player gives up the running game, the others keep playing without them.
+#[post("/game/{gameID}/forfeit")]
+pub async fn forfeit(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    match forfeit_response(route_params.into_inner(), request, auth_service, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn forfeit_response(
+    game_id: String,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let (game_id_from_token, player_name) = auth_service.extract_data(&request)?;
+
+    let game_id = game_id_from_token.check(game_id)?;
+
+    let game = game_repo.get_game_by_id(game_id)?;
+
+    safe_lock(&game)?
+        .apply(Command::Forfeit {
+            player: player_name.into_inner(),
+        })
+        .map_err(ErrorResponse::error_response)?;
+
+    // an AI may be at turn after the player who gave up
+    drive_ai(game);
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+impl ErrorResponse for ForfeitError {
+    fn status_code(&self) -> StatusCode {
+        use ForfeitError::*;
+        match self {
+            PlayerExistError(_) => StatusCode::NOT_FOUND,
+            GameNotRunning(_) | AlreadyOut(_) => StatusCode::CONFLICT,
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        use ForfeitError::*;
+        match self {
+            GameNotRunning(_) => Some("GAME_NOT_RUNNING"),
+            PlayerExistError(_) | AlreadyOut(_) => None,
+        }
+    }
+}
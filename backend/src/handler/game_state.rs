@@ -0,0 +1,122 @@
+use crate::handler::util::etag::json_with_etag;
+use crate::handler::util::response::{ErrMsg, ErrorResponse};
+use crate::handler::util::safe_lock::safe_lock;
+use crate::{AuthService, InMemoryGameRepo};
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use serde::Serialize;
+use uno_core::err::player_exist::PlayerExistError;
+use uno_core::gamestate::players::player::Player;
+
+/// The same personalized status the player gets as the STATUS message over the WebSocket.
+/// Supports `If-None-Match`, so polling clients only download it after it changes.
+#[get("/game/{gameID}")]
+pub async fn get_game(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let game_id = route_params.into_inner();
+    match get_game_response(game_id, request, auth_service, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn get_game_response(
+    game_id: String,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let (game_id_from_token, player_name) = auth_service.extract_data(&request)?;
+
+    let game_id = game_id_from_token.check(game_id)?;
+
+    let game = game_repo.get_game_by_id(game_id)?;
+    let session = safe_lock(&game)?;
+
+    let player_name = player_name.into_inner();
+    if session.game().find_player(player_name.clone()).is_none() {
+        return Err(PlayerExistError::NoSuchPlayer(player_name).error_response());
+    }
+
+    let status = session
+        .status(&player_name)
+        .map_err(|error| HttpResponse::InternalServerError().json(ErrMsg::new(error)))?;
+
+    Ok(json_with_etag(&request, &status.event))
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicPlayer {
+    name: String,
+    cards: usize,
+    is_ai: bool,
+    is_author: bool,
+    /// Place in which the player finished, starting at 0.
+    position: Option<usize>,
+    /// Whether the player plays with a registered account rather than as a guest.
+    is_registered: bool,
+}
+
+impl From<&Player> for PublicPlayer {
+    fn from(player: &Player) -> Self {
+        Self {
+            name: player.name(),
+            cards: player.get_card_count(),
+            is_ai: !player.is_human(),
+            is_author: player.is_author(),
+            position: player.position(),
+            is_registered: false,
+        }
+    }
+}
+
+/// What every player at the table can see about the others, needs the token of a player of the game.
+/// The card counts are live, so spectators only get them through their possibly delayed feed.
+#[get("/game/{gameID}/players")]
+pub async fn get_players(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let game_id = route_params.into_inner();
+    match get_players_response(game_id, request, auth_service, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn get_players_response(
+    game_id: String,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let (game_id_from_token, player_name) = auth_service.extract_data(&request)?;
+
+    let game_id = game_id_from_token.check(game_id)?;
+
+    let game = game_repo.get_game_by_id(game_id)?;
+    let session = safe_lock(&game)?;
+
+    let player_name = player_name.into_inner();
+    if session.game().find_player(player_name.clone()).is_none() {
+        return Err(PlayerExistError::NoSuchPlayer(player_name).error_response());
+    }
+
+    let players: Vec<PublicPlayer> = session
+        .game()
+        .players()
+        .iter()
+        .map(|player| PublicPlayer {
+            is_registered: session.account_of(&player.name()).is_some(),
+            ..PublicPlayer::from(player)
+        })
+        .collect();
+
+    Ok(json_with_etag(&request, &players))
+}
@@ -0,0 +1,50 @@
+use crate::handler::util::response::ErrorResponse;
+use crate::handler::util::safe_lock::safe_lock;
+use crate::{AuthService, InMemoryGameRepo};
+use actix_web::{get, http::StatusCode, web, HttpRequest, HttpResponse};
+use uno_core::err::player_exist::PlayerExistError;
+
+/// Legal moves of the authenticated player, so that the client can highlight the playable cards.
+#[get("/game/{gameID}/hints")]
+pub async fn hints(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let game_id = route_params.into_inner();
+    match hints_response(game_id, game_repo, auth_service, request) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn hints_response(
+    game_id: String,
+    game_repo: web::Data<InMemoryGameRepo>,
+    auth_service: web::Data<AuthService>,
+    request: HttpRequest,
+) -> Result<HttpResponse, HttpResponse> {
+    let (game_id_from_token, player_name) = auth_service.extract_data(&request)?;
+
+    let game_id = game_id_from_token.check(game_id)?;
+
+    let game = game_repo.get_game_by_id(game_id)?;
+
+    let moves = safe_lock(&game)?
+        .game()
+        .legal_moves(&player_name.into_inner())
+        .map_err(ErrorResponse::error_response)?;
+
+    Ok(HttpResponse::Ok().json(moves))
+}
+
+impl ErrorResponse for PlayerExistError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::NOT_FOUND
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        None
+    }
+}
@@ -1,32 +1,35 @@
-use crate::err::add_player::AddPlayerError;
-use crate::gamestate::game::GameStatus;
-use crate::handler::util::response::ErrMsg;
+use crate::handler::util::response::{ErrMsg, ErrorResponse};
 use crate::handler::util::safe_lock::safe_lock;
 use crate::{AuthService, InMemoryGameRepo};
-use actix_web::{post, web, HttpResponse, Responder};
+use actix_web::{http::StatusCode, post, web, HttpRequest, HttpResponse, Responder};
 use serde::Deserialize;
-use serde::Serialize;
-use std::sync::Mutex;
+use uno_core::err::add_player::AddPlayerError;
+use uno_core::gamestate::command::Command;
 
 #[derive(Deserialize, Debug)]
 pub struct RequestBody {
+    /// Ignored for logged in players, who play under their username
+    #[serde(default)]
     name: String,
 }
 
-#[derive(Serialize, Debug)]
-pub struct SuccessResponse {
-    token: String,
-}
-
 #[post("/game/{gameID}/player")]
 pub async fn join_game(
     route_params: web::Path<String>,
+    request: HttpRequest,
     request_body: web::Json<RequestBody>,
     auth_service: web::Data<AuthService>,
-    game_repo: web::Data<Mutex<InMemoryGameRepo>>,
+    game_repo: web::Data<InMemoryGameRepo>,
 ) -> impl Responder {
     let game_id = route_params.into_inner();
-    let player_name = &request_body.name;
+    let account = match auth_service.extract_account(&request) {
+        Err(response) => return response,
+        Ok(account) => account,
+    };
+    let player_name = match &account {
+        Some(account) => &account.username,
+        None => &request_body.name,
+    };
 
     if player_name.is_empty() {
         return HttpResponse::BadRequest().json(ErrMsg::new_from_scratch(
@@ -34,34 +37,50 @@ pub async fn join_game(
         ));
     }
 
-    let mut game_repo = match safe_lock(&game_repo) {
-        Err(response) => return response,
-        Ok(game_repo) => game_repo,
+    let game = match game_repo.get_game_by_id(game_id.clone()) {
+        Err(response) => return response.into(),
+        Ok(game) => game,
     };
 
-    let game = match game_repo.get_game_by_id_mut(game_id.clone()) {
-        Err(response) => return response.into(),
+    let mut game = match safe_lock(&game) {
+        Err(response) => return response,
         Ok(game) => game,
     };
 
-    if game.status() != GameStatus::Lobby {
-        return HttpResponse::Gone().json(ErrMsg::new_from_scratch(
-            "Game does not accept any new players.",
-        ));
+    // spectators keep their names, so that nobody chats as them
+    if game.spectators().has_name(player_name) {
+        return AddPlayerError::AlreadyExists(player_name.clone()).error_response();
     }
 
-    match game.add_player(player_name.clone()) {
-        Ok(_) => {
-            let jwt = auth_service.generate_jwt(player_name, &game_id);
-            HttpResponse::Created().json(SuccessResponse { token: jwt })
-        }
-        Err(err) => match err {
-            AddPlayerError::AlreadyExists(x) => {
-                HttpResponse::Conflict().json(ErrMsg::new_from_scratch(&x))
-            }
-            AddPlayerError::CreateStatusError(x) => {
-                HttpResponse::InternalServerError().json(ErrMsg::new(x))
+    match game.apply(Command::Join {
+        name: player_name.clone(),
+    }) {
+        Err(err) => err.error_response(),
+        Ok(_) => match &account {
+            Some(account) => {
+                game.set_account(&account.username, account.id);
+                HttpResponse::Created().json(auth_service.issue_account_tokens(account, &game_id))
             }
+            None => HttpResponse::Created().json(auth_service.issue_tokens(player_name, &game_id)),
         },
     }
 }
+
+impl ErrorResponse for AddPlayerError {
+    fn status_code(&self) -> StatusCode {
+        use AddPlayerError::*;
+        match self {
+            AlreadyExists(_) => StatusCode::CONFLICT,
+            NotAcceptingPlayers => StatusCode::GONE,
+            Kicked(_) => StatusCode::FORBIDDEN,
+            LobbyFull => StatusCode::CONFLICT,
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        match self {
+            AddPlayerError::LobbyFull => Some("LOBBY_FULL"),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::handler::util::safe_lock::safe_lock;
+use crate::InMemoryGameRepo;
+use actix_web::{get, web, HttpResponse};
+use serde::{Deserialize, Serialize};
+use uno_core::gamestate::game::GameStatus;
+use uno_core::gamestate::{CARDS_DEALT_TO_PLAYERS, PENALTY_CARDS};
+
+#[derive(Deserialize, Debug)]
+pub struct ListParams {
+    status: Option<GameStatus>,
+}
+
+/// Rules the game is played with. They are the same for every game for now.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Rules {
+    cards_dealt: usize,
+    penalty_cards: usize,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GameListing {
+    #[serde(rename = "gameID")]
+    game_id: String,
+    status: GameStatus,
+    author: String,
+    humans: usize,
+    ais: usize,
+    spectating_enabled: bool,
+    rules: Rules,
+}
+
+/// Games their authors made public, e.g. `/games?status=LOBBY` for the ones that can be joined.
+/// Private games are never listed.
+#[get("/games")]
+pub async fn list_games(
+    params: web::Query<ListParams>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let mut listings = vec![];
+
+    for session in game_repo.games() {
+        let session = match safe_lock(&session) {
+            Err(response) => return response,
+            Ok(session) => session,
+        };
+
+        let game = session.game();
+        if !session.is_public() || params.status.is_some_and(|status| status != game.status()) {
+            continue;
+        }
+
+        let humans = game.players().iter().filter(|p| p.is_human()).count();
+        listings.push(GameListing {
+            game_id: game.id.clone(),
+            status: game.status(),
+            author: game
+                .find_author()
+                .map_or_else(String::new, |author| author.name()),
+            humans,
+            ais: game.players().len() - humans,
+            spectating_enabled: session.spectators().is_enabled(),
+            rules: Rules {
+                cards_dealt: CARDS_DEALT_TO_PLAYERS,
+                penalty_cards: PENALTY_CARDS,
+            },
+        });
+    }
+    listings.sort_by(|a, b| a.game_id.cmp(&b.game_id));
+
+    HttpResponse::Ok().json(listings)
+}
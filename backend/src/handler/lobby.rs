@@ -0,0 +1,238 @@
+use crate::handler::util::response::ErrorResponse;
+use crate::handler::util::safe_lock::safe_lock;
+use crate::{AuthService, InMemoryGameRepo};
+use actix_web::{delete, http::StatusCode, post, put, web, HttpRequest, HttpResponse};
+use serde::{Deserialize, Serialize};
+use uno_core::err::lobby::LobbyError;
+use uno_core::gamestate::command::Command;
+
+#[derive(Deserialize, Debug)]
+pub struct RequestBody {
+    name: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AiResponse {
+    name: String,
+}
+
+/// The player leaves the lobby and their token stops working, games left without any human player are deleted.
+#[delete("/game/{gameID}/player")]
+pub async fn leave_game(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    match leave_game_response(route_params.into_inner(), request, auth_service, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn leave_game_response(
+    game_id: String,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let (game_id_from_token, player_name) = auth_service.extract_data(&request)?;
+
+    let game_id = game_id_from_token.check(game_id)?;
+
+    let player_name = player_name.into_inner();
+    let game = game_repo.get_game_by_id(game_id.clone())?;
+    let mut session = safe_lock(&game)?;
+
+    session
+        .apply(Command::Leave {
+            player: player_name.clone(),
+        })
+        .map_err(ErrorResponse::error_response)?;
+    // whoever joins under the same name later must not be able to act with the old token
+    auth_service.revoke(&game_id, &player_name);
+
+    if !session.game().players().iter().any(|p| p.is_human()) {
+        game_repo.remove_game(&game_id);
+        auth_service.revoke_game(&game_id);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// The author removes a human player from the lobby, their token stops working and they cannot join again.
+#[delete("/game/{gameID}/player/{name}")]
+pub async fn kick_player(
+    route_params: web::Path<(String, String)>,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let (game_id, kicked_name) = route_params.into_inner();
+    match kick_player_response(game_id, kicked_name, request, auth_service, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn kick_player_response(
+    game_id: String,
+    kicked_name: String,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let (game_id_from_token, author_name) = auth_service.extract_data(&request)?;
+
+    let game_id = game_id_from_token.check(game_id)?;
+
+    let game = game_repo.get_game_by_id(game_id.clone())?;
+
+    safe_lock(&game)?
+        .apply(Command::Kick {
+            author: author_name.into_inner(),
+            player: kicked_name.clone(),
+        })
+        .map_err(ErrorResponse::error_response)?;
+
+    auth_service.revoke(&game_id, &kicked_name);
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[post("/game/{gameID}/ai")]
+pub async fn add_ai(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    match add_ai_response(route_params.into_inner(), request, auth_service, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn add_ai_response(
+    game_id: String,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let (game_id_from_token, author_name) = auth_service.extract_data(&request)?;
+
+    let game_id = game_id_from_token.check(game_id)?;
+
+    let game = game_repo.get_game_by_id(game_id)?;
+    let mut session = safe_lock(&game)?;
+
+    session
+        .apply(Command::AddAi {
+            author: author_name.into_inner(),
+        })
+        .map_err(ErrorResponse::error_response)?;
+
+    // safe since the AI was just added as the last player
+    let name = session.game().players().last().unwrap().name();
+
+    Ok(HttpResponse::Created().json(AiResponse { name }))
+}
+
+#[delete("/game/{gameID}/ai/{name}")]
+pub async fn remove_ai(
+    route_params: web::Path<(String, String)>,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let (game_id, ai_name) = route_params.into_inner();
+    match remove_ai_response(game_id, ai_name, request, auth_service, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn remove_ai_response(
+    game_id: String,
+    ai_name: String,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let (game_id_from_token, author_name) = auth_service.extract_data(&request)?;
+
+    let game_id = game_id_from_token.check(game_id)?;
+
+    let game = game_repo.get_game_by_id(game_id)?;
+
+    safe_lock(&game)?
+        .apply(Command::RemoveAi {
+            author: author_name.into_inner(),
+            player: ai_name,
+        })
+        .map_err(ErrorResponse::error_response)?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// The author hands authorship over to another human player in the lobby.
+#[put("/game/{gameID}/author")]
+pub async fn transfer_author(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    request_body: web::Json<RequestBody>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let game_id = route_params.into_inner();
+    match transfer_author_response(game_id, request, request_body, auth_service, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn transfer_author_response(
+    game_id: String,
+    request: HttpRequest,
+    request_body: web::Json<RequestBody>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let (game_id_from_token, author_name) = auth_service.extract_data(&request)?;
+
+    let game_id = game_id_from_token.check(game_id)?;
+
+    let game = game_repo.get_game_by_id(game_id)?;
+
+    safe_lock(&game)?
+        .apply(Command::TransferAuthor {
+            author: author_name.into_inner(),
+            to: request_body.into_inner().name,
+        })
+        .map_err(ErrorResponse::error_response)?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+impl ErrorResponse for LobbyError {
+    fn status_code(&self) -> StatusCode {
+        use LobbyError::*;
+        match self {
+            NotAuthor(_) => StatusCode::FORBIDDEN,
+            NotInLobby => StatusCode::CONFLICT,
+            NoSuchPlayer(_) => StatusCode::NOT_FOUND,
+            LobbyFull => StatusCode::CONFLICT,
+            CannotKickSelf | NotAnAi(_) | NotHuman(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        use LobbyError::*;
+        match self {
+            NotAuthor(_) => Some("NOT_AUTHOR"),
+            NotInLobby => Some("GAME_NOT_IN_LOBBY"),
+            LobbyFull => Some("LOBBY_FULL"),
+            NoSuchPlayer(_) | CannotKickSelf | NotAnAi(_) | NotHuman(_) => None,
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::handler::service::auth::TokenPair;
+use crate::handler::service::matchmaking::{MatchmakingError, MatchmakingService, QueueStatus};
+use crate::handler::util::response::{ErrMsg, ErrorResponse};
+use crate::session::ai_driver::drive_ai;
+use crate::{AuthService, InMemoryGameRepo};
+use actix_web::{delete, get, http::StatusCode, post, web, HttpResponse};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestBody {
+    name: String,
+    /// Whether the player is fine with AIs taking the seats nobody took in time
+    #[serde(default = "default_fill_with_ai")]
+    fill_with_ai: bool,
+}
+
+fn default_fill_with_ai() -> bool {
+    true
+}
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "status", rename_all = "UPPERCASE")]
+pub enum TicketResponse {
+    Waiting {
+        ticket: String,
+        waiting: usize,
+    },
+    Matched {
+        ticket: String,
+        #[serde(rename = "gameID")]
+        game_id: String,
+        #[serde(flatten)]
+        tokens: TokenPair,
+    },
+}
+
+/// Puts the player into the quick match queue. Poll the returned ticket until it says MATCHED.
+#[post("/matchmaking")]
+pub async fn join_queue(
+    request_body: web::Json<RequestBody>,
+    matchmaking: web::Data<MatchmakingService>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    if request_body.name.is_empty() {
+        return HttpResponse::BadRequest().json(ErrMsg::new_from_scratch(
+            "Name of the player cannot be empty.",
+        ));
+    }
+
+    let request_body = request_body.into_inner();
+    let ticket =
+        match matchmaking.join(request_body.name, request_body.fill_with_ai, Instant::now()) {
+            Err(error) => return error.error_response(),
+            Ok(ticket) => ticket,
+        };
+
+    match ticket_response(ticket, matchmaking, auth_service, game_repo) {
+        Ok(response) => HttpResponse::Created().json(response),
+        Err(r) => r,
+    }
+}
+
+#[get("/matchmaking/{ticket}")]
+pub async fn poll_queue(
+    route_params: web::Path<String>,
+    matchmaking: web::Data<MatchmakingService>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let ticket = route_params.into_inner();
+    match ticket_response(ticket, matchmaking, auth_service, game_repo) {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(r) => r,
+    }
+}
+
+#[delete("/matchmaking/{ticket}")]
+pub async fn leave_queue(
+    route_params: web::Path<String>,
+    matchmaking: web::Data<MatchmakingService>,
+) -> HttpResponse {
+    match matchmaking.leave(&route_params.into_inner()) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => error.error_response(),
+    }
+}
+
+fn ticket_response(
+    ticket: String,
+    matchmaking: web::Data<MatchmakingService>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<TicketResponse, HttpResponse> {
+    let status = matchmaking
+        .poll(&ticket, Instant::now(), |game| {
+            drive_ai(game_repo.add_game(game));
+        })
+        .map_err(ErrorResponse::error_response)?;
+
+    Ok(match status {
+        QueueStatus::Waiting(waiting) => TicketResponse::Waiting { ticket, waiting },
+        QueueStatus::Matched {
+            game_id,
+            player_name,
+        } => TicketResponse::Matched {
+            ticket,
+            tokens: auth_service.issue_tokens(&player_name, &game_id),
+            game_id,
+        },
+    })
+}
+
+impl ErrorResponse for MatchmakingError {
+    fn status_code(&self) -> StatusCode {
+        use MatchmakingError::*;
+        match self {
+            AlreadyWaiting(_) => StatusCode::CONFLICT,
+            NoSuchTicket(_) => StatusCode::NOT_FOUND,
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        None
+    }
+}
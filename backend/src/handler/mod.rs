@@ -1,10 +1,28 @@
+pub mod account;
+pub mod admin;
+pub mod chat;
 pub mod create_game;
+#[cfg(feature = "dev-admin")]
+pub mod create_scenario;
 pub mod draw_card;
+pub mod fairness;
+pub mod forfeit;
+pub mod game_state;
+pub mod hints;
 pub mod join_game;
 pub mod lb_reconnect;
+pub mod list_games;
+pub mod lobby;
+pub mod matchmaking;
 pub mod play_card;
+pub mod public_keys;
+pub mod refresh_token;
+pub mod rematch;
 pub mod restart_game;
+pub mod results;
 pub mod service;
+pub mod spectate;
 pub mod ws_connect;
+pub mod ws_ticket;
 
-mod util;
+pub(crate) mod util;
@@ -1,13 +1,13 @@
-use crate::cards::card::{Card, CardColor};
-use crate::err::play_card::PlayCardError;
-use crate::gamestate::game::GameStatus;
-use crate::handler::util::response::{ErrMsg, TypedErrMsg};
+use crate::handler::util::idempotency::apply_once;
+use crate::handler::util::response::ErrorResponse;
 use crate::handler::util::safe_lock::safe_lock;
+use crate::session::ai_driver::drive_ai;
 use crate::{AuthService, InMemoryGameRepo};
-use actix_web::{post, web, HttpRequest, HttpResponse, Responder};
+use actix_web::{http::StatusCode, post, web, HttpRequest, HttpResponse, Responder};
 use serde::Deserialize;
-use serde::Serialize;
-use std::sync::Mutex;
+use uno_core::cards::card::{Card, CardColor};
+use uno_core::err::play_card::PlayCardError;
+use uno_core::gamestate::command::Command;
 
 #[derive(Deserialize, Debug)]
 pub struct RequestBody {
@@ -16,18 +16,12 @@ pub struct RequestBody {
     new_color: Option<CardColor>,
     #[serde(rename(serialize = "saidUno", deserialize = "saidUno"))]
     said_uno: bool,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct MessageResponse {
-    message: String,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct TypeMessageResponse {
-    #[serde(rename(serialize = "type", deserialize = "type"))]
-    type_of_error: String,
-    message: String,
+    /// Turn of the game the card is meant for, the card is not played if the game moved on
+    #[serde(
+        default,
+        rename(serialize = "expectedTurn", deserialize = "expectedTurn")
+    )]
+    expected_turn: Option<u64>,
 }
 
 #[post("/game/{gameID}/playCard")]
@@ -36,7 +30,7 @@ pub async fn play_card(
     request: HttpRequest,
     request_body: web::Json<RequestBody>,
     auth_service: web::Data<AuthService>,
-    game_repo: web::Data<Mutex<InMemoryGameRepo>>,
+    game_repo: web::Data<InMemoryGameRepo>,
 ) -> impl Responder {
     match play_card_response(route_params, request, request_body, auth_service, game_repo) {
         Err(response) => response,
@@ -49,7 +43,7 @@ fn play_card_response(
     request: HttpRequest,
     request_body: web::Json<RequestBody>,
     auth_service: web::Data<AuthService>,
-    game_repo: web::Data<Mutex<InMemoryGameRepo>>,
+    game_repo: web::Data<InMemoryGameRepo>,
 ) -> Result<HttpResponse, HttpResponse> {
     let game_id = route_params.into_inner();
     let card = &request_body.card;
@@ -60,44 +54,49 @@ fn play_card_response(
 
     let game_id = game_id_from_token.check(game_id)?;
 
-    let mut game_repo = safe_lock(&game_repo)?;
-
-    let game = game_repo.get_game_by_id_mut(game_id.clone())?;
-
-    if game.status() != GameStatus::Running {
-        return Err(HttpResponse::Conflict().json(TypedErrMsg::new_from_scratch(
-            "GAME_NOT_RUNNING",
-            format!("The game with id '{}' is not running", game_id),
-        )));
-    }
+    let game = game_repo.get_game_by_id(game_id)?;
 
-    game.play_card(
-        player_name.into_inner(),
-        card.clone(),
-        maybe_new_color,
-        said_uno,
+    let player_name = player_name.into_inner();
+    let response = apply_once(
+        &request,
+        &mut *safe_lock(&game)?,
+        &player_name,
+        request_body.expected_turn,
+        Command::PlayCard {
+            player: player_name.clone(),
+            card: card.clone(),
+            new_color: maybe_new_color,
+            said_uno,
+        },
     )?;
 
-    Ok(HttpResponse::NoContent().finish())
+    drive_ai(game);
+
+    Ok(response)
 }
 
-impl From<PlayCardError> for HttpResponse {
-    fn from(error: PlayCardError) -> HttpResponse {
+impl ErrorResponse for PlayCardError {
+    fn status_code(&self) -> StatusCode {
+        use PlayCardError::*;
+        match self {
+            PlayerHasNoSuchCard(_)
+            | CardCannotBePlayed(_, _)
+            | PlayerTurnError(_)
+            | GameNotRunning(_) => StatusCode::CONFLICT,
+            PlayerExistError(_) => StatusCode::NOT_FOUND,
+            CreateStatusError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            SaidUnoWhenShouldNotHave => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
         use PlayCardError::*;
-        match error {
-            PlayerHasNoSuchCard(_) => {
-                HttpResponse::Conflict().json(TypedErrMsg::new("CARD_NOT_IN_HAND", error))
-            }
-            CardCannotBePlayed(_, _) => {
-                HttpResponse::Conflict().json(TypedErrMsg::new("CANNOT_PLAY_THIS", error))
-            }
-            PlayerTurnError(_) => {
-                HttpResponse::Conflict().json(TypedErrMsg::new("NOT_YOUR_TURN", error))
-            }
-            PlayerExistError(_) => HttpResponse::NotFound().json(ErrMsg::new(error)),
-            CreateStatusError(_) => HttpResponse::InternalServerError().json(ErrMsg::new(error)),
-            SaidUnoWhenShouldNotHave => HttpResponse::BadRequest().json(ErrMsg::new(error)),
-            ChainedAiError => HttpResponse::InternalServerError().json(ErrMsg::new(error)),
+        match self {
+            PlayerHasNoSuchCard(_) => Some("CARD_NOT_IN_HAND"),
+            CardCannotBePlayed(_, _) => Some("CANNOT_PLAY_THIS"),
+            PlayerTurnError(_) => Some("NOT_YOUR_TURN"),
+            GameNotRunning(_) => Some("GAME_NOT_RUNNING"),
+            PlayerExistError(_) | CreateStatusError(_) | SaidUnoWhenShouldNotHave => None,
         }
     }
 }
@@ -0,0 +1,9 @@
+use crate::AuthService;
+use actix_web::{get, web, HttpResponse};
+
+/// Public keys the tokens can be verified with, so that other services do not need the signing secret.
+/// Only asymmetric keys are listed, the list is empty if the server signs with an HS256 secret.
+#[get("/keys")]
+pub async fn public_keys(auth_service: web::Data<AuthService>) -> HttpResponse {
+    HttpResponse::Ok().json(auth_service.keys().public_keys())
+}
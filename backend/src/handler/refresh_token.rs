@@ -0,0 +1,21 @@
+use crate::AuthService;
+use actix_web::{post, web, HttpResponse};
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestBody {
+    refresh_token: String,
+}
+
+/// Exchanges a refresh token for a new token and a new refresh token, the old refresh token stops working.
+#[post("/token/refresh")]
+pub async fn refresh_token(
+    request_body: web::Json<RequestBody>,
+    auth_service: web::Data<AuthService>,
+) -> HttpResponse {
+    match auth_service.refresh(&request_body.refresh_token) {
+        Ok(tokens) => HttpResponse::Ok().json(tokens),
+        Err(response) => response,
+    }
+}
@@ -0,0 +1,120 @@
+use crate::handler::util::response::ErrorResponse;
+use crate::handler::util::safe_lock::safe_lock;
+use crate::{AuthService, InMemoryGameRepo};
+use actix_web::{http::StatusCode, post, web, HttpRequest, HttpResponse};
+use uno_core::err::rematch::RematchError;
+use uno_core::gamestate::command::Command;
+use uno_core::gamestate::event::Event;
+
+/// The author of the finished game starts a ready-check for a rematch.
+#[post("/game/{gameID}/rematch")]
+pub async fn propose_rematch(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let command = |author| Command::ProposeRematch { author };
+    match rematch_response(
+        route_params.into_inner(),
+        request,
+        auth_service,
+        game_repo,
+        command,
+    ) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+/// The player agrees to the rematch.
+#[post("/game/{gameID}/rematch/ready")]
+pub async fn confirm_rematch(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let command = |player| Command::ConfirmRematch { player };
+    match rematch_response(
+        route_params.into_inner(),
+        request,
+        auth_service,
+        game_repo,
+        command,
+    ) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+/// The author ends the ready-check, whoever did not agree yet is dropped from the game and their token stops working.
+#[post("/game/{gameID}/statusLobby")]
+pub async fn return_to_lobby(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let command = |author| Command::ReturnToLobby { author };
+    match rematch_response(
+        route_params.into_inner(),
+        request,
+        auth_service,
+        game_repo,
+        command,
+    ) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn rematch_response(
+    game_id: String,
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+    command: impl FnOnce(String) -> Command,
+) -> Result<HttpResponse, HttpResponse> {
+    let (game_id_from_token, player_name) = auth_service.extract_data(&request)?;
+
+    let game_id = game_id_from_token.check(game_id)?;
+
+    let game = game_repo.get_game_by_id(game_id.clone())?;
+
+    let events = safe_lock(&game)?
+        .apply_with_events(command(player_name.into_inner()))
+        .map_err(ErrorResponse::error_response)?;
+
+    // players who did not agree to the rematch are dropped, someone else may take their name
+    for event in events {
+        if let Event::PlayerLeft { name, .. } = event {
+            auth_service.revoke(&game_id, &name);
+        }
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+impl ErrorResponse for RematchError {
+    fn status_code(&self) -> StatusCode {
+        use RematchError::*;
+        match self {
+            NotAuthor(_) => StatusCode::FORBIDDEN,
+            GameNotFinished(_) | NoReadyCheck | ReadyCheckRunning => StatusCode::CONFLICT,
+            NotHuman(_) => StatusCode::BAD_REQUEST,
+            NoSuchPlayer(_) => StatusCode::NOT_FOUND,
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        use RematchError::*;
+        match self {
+            NotAuthor(_) => Some("NOT_AUTHOR"),
+            GameNotFinished(_) => Some("GAME_NOT_FINISHED"),
+            NoReadyCheck => Some("NO_READY_CHECK"),
+            ReadyCheckRunning => Some("READY_CHECK_RUNNING"),
+            NotHuman(_) | NoSuchPlayer(_) => None,
+        }
+    }
+}
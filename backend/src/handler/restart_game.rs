@@ -1,16 +1,17 @@
-use super::util::response::ErrMsg;
-use crate::err::game_start::GameStartError;
+use super::util::response::ErrorResponse;
 use crate::handler::util::safe_lock::safe_lock;
+use crate::session::ai_driver::drive_ai;
 use crate::{AuthService, InMemoryGameRepo};
-use actix_web::{post, web, HttpRequest, HttpResponse, Responder};
-use std::sync::Mutex;
+use actix_web::{http::StatusCode, post, web, HttpRequest, HttpResponse, Responder};
+use uno_core::err::game_start::GameStartError;
+use uno_core::gamestate::command::Command;
 
 #[post("game/{gameID}/statusRunning")]
 pub async fn start_game(
     route_params: web::Path<String>,
     request: HttpRequest,
     auth_service: web::Data<AuthService>,
-    game_repo: web::Data<Mutex<InMemoryGameRepo>>,
+    game_repo: web::Data<InMemoryGameRepo>,
 ) -> impl Responder {
     match start_game_response(route_params, request, auth_service, game_repo) {
         Err(response) => response,
@@ -22,7 +23,7 @@ pub fn start_game_response(
     route_params: web::Path<String>,
     request: HttpRequest,
     auth_service: web::Data<AuthService>,
-    game_repo: web::Data<Mutex<InMemoryGameRepo>>,
+    game_repo: web::Data<InMemoryGameRepo>,
 ) -> Result<HttpResponse, HttpResponse> {
     let game_id = route_params.into_inner();
 
@@ -30,35 +31,34 @@ pub fn start_game_response(
 
     let game_id = game_id_from_token.check(game_id)?;
 
-    let mut game_repo = safe_lock(&game_repo)?;
+    let game = game_repo.get_game_by_id(game_id)?;
 
-    let game = game_repo.get_game_by_id_mut(game_id)?;
+    safe_lock(&game)?
+        .apply(Command::Start {
+            player: player_name_from_token.into_inner(),
+        })
+        .map_err(ErrorResponse::error_response)?;
 
-    let author_name = match game.find_author() {
-        None => {
-            return Err(HttpResponse::InternalServerError()
-                .json(ErrMsg::new_from_scratch("Author of the game not found")))
-        }
-        Some(author) => author.name(),
-    };
-
-    player_name_from_token.check(&author_name)?;
-
-    game.start()?;
+    drive_ai(game);
 
     Ok(HttpResponse::NoContent().finish())
 }
 
-impl From<GameStartError> for HttpResponse {
-    fn from(error: GameStartError) -> Self {
+impl ErrorResponse for GameStartError {
+    fn status_code(&self) -> StatusCode {
+        use GameStartError::*;
+        match self {
+            DeckEmptyWhenStartingGame => StatusCode::INTERNAL_SERVER_ERROR,
+            GameAlreadyStarted | FairGameNotInLobby => StatusCode::CONFLICT,
+            NotAuthor(_) => StatusCode::FORBIDDEN,
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
         use GameStartError::*;
-        match error {
-            DeckEmptyWhenStartingGame => {
-                HttpResponse::InternalServerError().json(ErrMsg::new(error))
-            }
-            GameAlreadyStarted => HttpResponse::Conflict().json(ErrMsg::new(error)),
-            CreateStatusError(_) => HttpResponse::InternalServerError().json(ErrMsg::new(error)),
-            ChainedAiError => HttpResponse::InternalServerError().json(ErrMsg::new(error)),
+        match self {
+            FairGameNotInLobby => Some("FAIR_GAME_NOT_IN_LOBBY"),
+            DeckEmptyWhenStartingGame | GameAlreadyStarted | NotAuthor(_) => None,
         }
     }
 }
@@ -0,0 +1,142 @@
+use crate::handler::service::jwt_keys::KeyRing;
+use crate::handler::util::response::ErrMsg;
+use crate::handler::util::safe_lock::safe_lock;
+use crate::session::results::GameResult;
+use crate::{AuthService, InMemoryGameRepo};
+use actix_web::{get, post, web, HttpResponse};
+use jwt_simple::prelude::*;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Subject of result certificates, which keeps them apart from every other token the server signs.
+pub static CERTIFICATE_SUBJECT: &str = "game-result";
+
+#[derive(Debug, PartialEq)]
+pub enum CertificateError {
+    /// Not signed by any key of the server, altered or not a JWT at all.
+    InvalidSignature,
+    NotACertificate,
+}
+
+impl Error for CertificateError {}
+
+impl Display for CertificateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use CertificateError::*;
+
+        match self {
+            InvalidSignature => write!(f, "The certificate was not signed by this server"),
+            NotACertificate => write!(f, "The token is not a game result certificate"),
+        }
+    }
+}
+
+/// Signs the result as a JWT that never expires, issued at the time the game finished.
+pub fn issue_certificate(keys: &KeyRing, result: &GameResult) -> Result<String, jwt_simple::Error> {
+    let mut claims = Claims::with_custom_claims(result.clone(), Duration::from_secs(0))
+        .with_subject(CERTIFICATE_SUBJECT);
+    let finished_at = Duration::from_secs(result.finished_at);
+    claims.issued_at = Some(finished_at);
+    claims.invalid_before = Some(finished_at);
+    claims.expires_at = None;
+
+    keys.sign(claims)
+}
+
+pub fn verify_certificate(
+    keys: &KeyRing,
+    certificate: &str,
+) -> Result<GameResult, CertificateError> {
+    // any claims, so that other tokens of the server are told apart from forged certificates
+    let claims = keys
+        .verify::<serde_json::Value>(certificate)
+        .map_err(|_| CertificateError::InvalidSignature)?;
+
+    match claims.subject.as_deref() {
+        Some(subject) if subject == CERTIFICATE_SUBJECT => {
+            serde_json::from_value(claims.custom).map_err(|_| CertificateError::NotACertificate)
+        }
+        _ => Err(CertificateError::NotACertificate),
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct Certificate {
+    /// The result signed with the signing key of the server, see `GET /keys`.
+    certificate: String,
+    result: GameResult,
+}
+
+/// Results of the games finished in the session with the certificates signed when they finished, the latest last.
+/// No token needed.
+#[get("/game/{gameID}/results")]
+pub async fn get_results(
+    route_params: web::Path<String>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    match get_results_response(route_params.into_inner(), game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn get_results_response(
+    game_id: String,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let game = game_repo.get_game_by_id(game_id)?;
+    let results = safe_lock(&game)?.results().finished().clone();
+
+    let certificates = results
+        .into_iter()
+        .map(|certified| match certified.certificate {
+            Some(certificate) => Ok(Certificate {
+                certificate,
+                result: certified.result,
+            }),
+            None => Err(
+                HttpResponse::InternalServerError().json(ErrMsg::new_from_scratch(
+                    "A result of the game could not be signed",
+                )),
+            ),
+        })
+        .collect::<Result<Vec<Certificate>, HttpResponse>>()?;
+
+    Ok(HttpResponse::Ok().json(certificates))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct VerifyBody {
+    certificate: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Verification {
+    valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<GameResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Checks that the certificate was signed by this server and returns the result it certifies.
+#[post("/results/verify")]
+pub async fn verify_result(
+    request_body: web::Json<VerifyBody>,
+    auth_service: web::Data<AuthService>,
+) -> HttpResponse {
+    let verification = match verify_certificate(auth_service.keys(), &request_body.certificate) {
+        Ok(result) => Verification {
+            valid: true,
+            result: Some(result),
+            error: None,
+        },
+        Err(error) => Verification {
+            valid: false,
+            result: None,
+            error: Some(error.to_string()),
+        },
+    };
+
+    HttpResponse::Ok().json(verification)
+}
@@ -0,0 +1,73 @@
+use actix_web::HttpRequest;
+use anyhow::bail;
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[cfg(test)]
+#[path = "../../tests/admin_test.rs"]
+mod tests;
+
+/// Header the admin key is sent in, apart from the `Authorization` header the tokens of the players use.
+pub static ADMIN_KEY_HEADER: &str = "X-Admin-Key";
+/// Shortest admin key accepted, so that it cannot be guessed.
+pub static MIN_ADMIN_KEY_LENGTH: usize = 32;
+
+#[derive(Debug, PartialEq)]
+pub enum AdminError {
+    MissingKey,
+    InvalidKey,
+    EmptyNotice,
+}
+
+impl Error for AdminError {}
+
+impl Display for AdminError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use AdminError::*;
+
+        match self {
+            MissingKey => write!(
+                f,
+                "The admin key is missing in the {} header",
+                ADMIN_KEY_HEADER
+            ),
+            InvalidKey => write!(f, "The admin key is not valid"),
+            EmptyNotice => write!(f, "The notice cannot be empty"),
+        }
+    }
+}
+
+/// Checks the key of the server operator, who can manage every game.
+pub struct AdminService {
+    /// Only the hash is kept, so that comparing it takes the same time whatever key is sent.
+    key_hash: [u8; 32],
+}
+
+impl AdminService {
+    pub fn new(key: &str) -> anyhow::Result<Self> {
+        if key.chars().count() < MIN_ADMIN_KEY_LENGTH {
+            bail!(
+                "The admin key must have at least {} characters",
+                MIN_ADMIN_KEY_LENGTH
+            );
+        }
+
+        Ok(Self {
+            key_hash: Sha256::digest(key).into(),
+        })
+    }
+
+    pub fn check(&self, request: &HttpRequest) -> Result<(), AdminError> {
+        let key = match request.headers().get(ADMIN_KEY_HEADER) {
+            None => return Err(AdminError::MissingKey),
+            Some(key) => key.as_bytes(),
+        };
+
+        let key_hash: [u8; 32] = Sha256::digest(key).into();
+        match key_hash == self.key_hash {
+            true => Ok(()),
+            false => Err(AdminError::InvalidKey),
+        }
+    }
+}
@@ -1,18 +1,96 @@
+use crate::handler::service::jwt_keys::KeyRing;
 use crate::handler::util::response::ErrMsg;
+use crate::repo::account_repo::Account;
 use actix_web::http::header::Header;
 use actix_web::HttpRequest;
 use actix_web::HttpResponse;
 use actix_web_httpauth::headers::authorization::{Authorization, Bearer};
+use jwt_simple::claims::DEFAULT_TIME_TOLERANCE_SECS;
 use jwt_simple::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+
+#[cfg(test)]
+#[path = "../../tests/auth_test.rs"]
+mod tests;
+
+/// How long a token can be used for requests and to connect to the WebSocket.
+pub static ACCESS_TOKEN_MINUTES: u64 = 15;
+/// How long a refresh token can be exchanged for new tokens.
+pub static REFRESH_TOKEN_DAYS: u64 = 7;
+/// How long a logged in player stays logged in.
+pub static ACCOUNT_TOKEN_DAYS: u64 = 30;
+/// How often revocations no token can match anymore are forgotten.
+static PRUNE_INTERVAL_SECS: u64 = 60;
 
 pub struct AuthService {
-    pub key: HS256Key,
+    keys: Arc<KeyRing>,
+    revoked: RwLock<Revocations>,
+}
+
+/// Tokens that are no longer accepted even though they did not expire yet.
+#[derive(Default)]
+struct Revocations {
+    /// (game id, player name) pairs, e.g. of kicked players, with the time of the revocation.
+    /// Tokens issued later are accepted, so somebody else can join under the name of a player who left.
+    players: HashMap<(String, String), UnixTimeStamp>,
+    /// Ids of deleted games with the time of the deletion.
+    games: HashMap<String, UnixTimeStamp>,
+    /// Ids of single tokens with their expiration, after which they are forgotten.
+    tokens: HashMap<String, UnixTimeStamp>,
+    pruned_at: UnixTimeStamp,
+}
+
+impl Revocations {
+    /// Forgets the revocations of tokens that expired, at most once every `PRUNE_INTERVAL_SECS`.
+    fn prune(&mut self) {
+        let now = now();
+        if now < self.pruned_at + Duration::from_secs(PRUNE_INTERVAL_SECS) {
+            return;
+        }
+        self.pruned_at = now;
+
+        // tokens are still accepted for a while after they expire
+        let tolerance = Duration::from_secs(DEFAULT_TIME_TOLERANCE_SECS);
+        // every token issued before a revocation has expired by then
+        let longest_lifetime = Duration::from_days(REFRESH_TOKEN_DAYS) + tolerance;
+        self.players
+            .retain(|_, revoked_at| *revoked_at + longest_lifetime > now);
+        self.games
+            .retain(|_, revoked_at| *revoked_at + longest_lifetime > now);
+        self.tokens
+            .retain(|_, expires_at| *expires_at + tolerance > now);
+    }
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct JwtData {
     player_name: String,
     game_id: String,
+    /// Spectators can only watch the game, tokens issued before spectating existed are players' ones
+    #[serde(default)]
+    spectator: bool,
+    /// Refresh tokens can only be exchanged for new tokens
+    #[serde(default)]
+    refresh: bool,
+    /// Registered account of the player, guests have none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    account_id: Option<i64>,
+}
+
+/// Claims of the token a registered player gets by logging in, it is not bound to any game.
+#[derive(Serialize, Deserialize)]
+pub struct AccountData {
+    account_id: i64,
+    username: String,
+}
+
+/// A short-lived token used for everything else and the refresh token to get a new one with.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPair {
+    pub token: String,
+    pub refresh_token: String,
 }
 
 #[derive(Serialize, Debug)]
@@ -50,34 +128,210 @@ pub struct PlayerName {
 }
 
 impl PlayerName {
-    pub fn check(&self, author: &str) -> Result<(), HttpResponse> {
-        if self.name != author {
-            return Err(HttpResponse::Forbidden().json(ErrMsg::new_from_scratch(
-                "This action can be done only by the author of the game",
-            )));
-        }
-        Ok(())
-    }
-
     pub fn into_inner(self) -> String {
         self.name
     }
 }
 
 impl AuthService {
-    pub fn new() -> AuthService {
+    pub fn new(keys: KeyRing) -> AuthService {
         Self {
-            key: HS256Key::generate(),
+            keys: Arc::new(keys),
+            revoked: RwLock::new(Revocations::default()),
+        }
+    }
+
+    fn revocations(&self) -> std::sync::RwLockWriteGuard<'_, Revocations> {
+        // a poisoned list only means that a panic happened while inserting, the list itself stays valid
+        let mut revoked = self.revoked.write().unwrap_or_else(PoisonError::into_inner);
+        revoked.prune();
+        revoked
+    }
+
+    /// Invalidates every token the player was given for the game so far.
+    pub fn revoke(&self, game_id: &str, player_name: &str) {
+        self.revocations()
+            .players
+            .insert((game_id.to_string(), player_name.to_string()), now());
+    }
+
+    /// Invalidates every token given for the game, e.g. when it is deleted.
+    pub fn revoke_game(&self, game_id: &str) {
+        self.revocations().games.insert(game_id.to_string(), now());
+    }
+
+    /// Invalidates a single token until it expires, returns false if it already was.
+    /// Checked and invalidated under one lock, so that only one of parallel requests with the token gets through.
+    fn use_once(&self, claims: &JWTClaims<JwtData>) -> bool {
+        match (&claims.jwt_id, claims.expires_at) {
+            (Some(jti), Some(expires_at)) => self
+                .revocations()
+                .tokens
+                .insert(jti.clone(), expires_at)
+                .is_none(),
+            _ => false,
         }
     }
 
-    pub fn generate_jwt(&self, player_name: &str, game_id: &str) -> String {
-        let jwt_data = JwtData {
-            player_name: player_name.to_string(),
+    fn is_revoked(&self, claims: &JWTClaims<JwtData>) -> bool {
+        let revoked = self.revoked.read().unwrap_or_else(PoisonError::into_inner);
+        let jwt_data = &claims.custom;
+        revoked
+            .players
+            .get(&(jwt_data.game_id.clone(), jwt_data.player_name.clone()))
+            .is_some_and(|revoked_at| {
+                claims
+                    .issued_at
+                    .is_none_or(|issued_at| issued_at <= *revoked_at)
+            })
+            || revoked.games.contains_key(&jwt_data.game_id)
+            || claims
+                .jwt_id
+                .as_ref()
+                .is_some_and(|jti| revoked.tokens.contains_key(jti))
+    }
+
+    pub fn issue_tokens(&self, player_name: &str, game_id: &str) -> TokenPair {
+        self.issue(player_name, game_id, false, None)
+    }
+
+    /// Tokens of a registered player, who plays under their username.
+    pub fn issue_account_tokens(&self, account: &Account, game_id: &str) -> TokenPair {
+        self.issue(&account.username, game_id, false, Some(account.id))
+    }
+
+    pub fn issue_spectator_tokens(&self, spectator_name: &str, game_id: &str) -> TokenPair {
+        self.issue(spectator_name, game_id, true, None)
+    }
+
+    fn issue(
+        &self,
+        name: &str,
+        game_id: &str,
+        spectator: bool,
+        account_id: Option<i64>,
+    ) -> TokenPair {
+        let jwt_data = |refresh| JwtData {
+            player_name: name.to_string(),
             game_id: game_id.to_string(),
+            spectator,
+            refresh,
+            account_id,
         };
-        let claims = Claims::with_custom_claims(jwt_data, Duration::from_days(9000));
-        self.key.authenticate(claims).unwrap()
+
+        // tokens are issued after the latest revocation, even if it happened within the same second
+        let revoked_at = self
+            .revoked
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .players
+            .get(&(game_id.to_string(), name.to_string()))
+            .copied();
+        let issued_at = match revoked_at {
+            Some(revoked_at) if revoked_at >= now() => revoked_at + Duration::from_secs(1),
+            _ => now(),
+        };
+
+        TokenPair {
+            token: self.sign(
+                jwt_data(false),
+                issued_at,
+                Duration::from_mins(ACCESS_TOKEN_MINUTES),
+            ),
+            refresh_token: self.sign(
+                jwt_data(true),
+                issued_at,
+                Duration::from_days(REFRESH_TOKEN_DAYS),
+            ),
+        }
+    }
+
+    pub fn issue_account_token(&self, account: &Account) -> String {
+        let account_data = AccountData {
+            account_id: account.id,
+            username: account.username.clone(),
+        };
+        let claims =
+            Claims::with_custom_claims(account_data, Duration::from_days(ACCOUNT_TOKEN_DAYS))
+                .with_jwt_id(nanoid::nanoid!());
+        self.keys.sign(claims).unwrap()
+    }
+
+    /// The account of a logged in player, None for guests, who send no account token.
+    /// Other tokens, e.g. a guest's game token of an earlier game, are ignored.
+    pub(in crate::handler) fn extract_account(
+        &self,
+        request: &HttpRequest,
+    ) -> Result<Option<Account>, HttpResponse> {
+        if Authorization::<Bearer>::parse(request).is_err() {
+            return Ok(None);
+        }
+
+        let token = self.bearer_token(request)?;
+        if !claims_account(&token) {
+            return Ok(None);
+        }
+
+        match self.keys.verify::<AccountData>(&token) {
+            Err(_) => Err(HttpResponse::Unauthorized().json(ErrRespLocal::new(
+                "Invalid account JWT, log in again or play as a guest",
+            ))),
+            Ok(data) => Ok(Some(Account {
+                id: data.custom.account_id,
+                username: data.custom.username,
+            })),
+        }
+    }
+
+    fn sign(&self, jwt_data: JwtData, issued_at: UnixTimeStamp, valid_for: Duration) -> String {
+        let mut claims =
+            Claims::with_custom_claims(jwt_data, valid_for).with_jwt_id(nanoid::nanoid!());
+        claims.issued_at = Some(issued_at);
+        self.keys.sign(claims).unwrap()
+    }
+
+    /// Exchanges a refresh token for new tokens, the refresh token cannot be used again.
+    pub fn refresh(&self, refresh_token: &str) -> Result<TokenPair, HttpResponse> {
+        match self.keys.verify::<JwtData>(refresh_token) {
+            Err(_) => Err(HttpResponse::Unauthorized().json(ErrRespLocal::new("Invalid JWT"))),
+            Ok(claims) if !claims.custom.refresh => {
+                Err(HttpResponse::BadRequest()
+                    .json(ErrRespLocal::new("JWT is not a refresh token")))
+            }
+            Ok(claims) if self.is_revoked(&claims) || !self.use_once(&claims) => {
+                Err(HttpResponse::Unauthorized().json(ErrRespLocal::new("Revoked JWT")))
+            }
+            Ok(claims) => {
+                let data = &claims.custom;
+                Ok(self.issue(
+                    &data.player_name,
+                    &data.game_id,
+                    data.spectator,
+                    data.account_id,
+                ))
+            }
+        }
+    }
+
+    pub fn keys(&self) -> &KeyRing {
+        &self.keys
+    }
+
+    /// The keys for those who sign with them too, e.g. the certificates of game results.
+    pub fn shared_keys(&self) -> Arc<KeyRing> {
+        Arc::clone(&self.keys)
+    }
+
+    /// Who the token belongs to, a player (or spectator) of a game or an account. None if it is not valid.
+    pub fn identify(&self, token: &str) -> Option<String> {
+        if let Ok(data) = self.verify_access(token) {
+            return Some(format!("{} {}", data.game_id, data.player_name));
+        }
+
+        self.keys
+            .verify::<AccountData>(token)
+            .ok()
+            .map(|claims| format!("account {}", claims.custom.account_id))
     }
 
     // Extracts and returns (game_id, player_name) from a request
@@ -85,36 +339,81 @@ impl AuthService {
         &self,
         request: &HttpRequest,
     ) -> Result<(GameID, PlayerName), HttpResponse> {
-        let auth_bearer =
-            match Authorization::<Bearer>::parse(request) {
-                Err(_) => return Err(HttpResponse::BadRequest().json(ErrRespLocal::new(
-                    "Request could not be parsed properly to extract authorisation header content",
-                ))),
-                Ok(x) => x.to_string(),
-            };
-
-        let token = self.remove_bearer_prefix(auth_bearer)?;
+        let token = self.bearer_token(request)?;
 
         self.extract_data_from_token(token)
     }
 
+    // Extracts and returns (game_id, name, whether they are a spectator) from a request of a player or a spectator
+    pub(in crate::handler) fn extract_any(
+        &self,
+        request: &HttpRequest,
+    ) -> Result<(GameID, PlayerName, bool), HttpResponse> {
+        let data = self.verify_access(&self.bearer_token(request)?)?;
+        let spectator = data.spectator;
+        let (game_id, player_name) = Self::split(data);
+
+        Ok((game_id, player_name, spectator))
+    }
+
     pub fn extract_data_from_token(
         &self,
         token: String,
     ) -> Result<(GameID, PlayerName), HttpResponse> {
-        match self.key.verify_token::<JwtData>(&token, None) {
+        match self.verify_access(&token)? {
+            data if data.spectator => Err(HttpResponse::Forbidden()
+                .json(ErrRespLocal::new("Spectators can only watch the game"))),
+            data => Ok(Self::split(data)),
+        }
+    }
+
+    // Extracts and returns (game_id, spectator_name) from a spectator's token
+    pub fn extract_spectator_from_token(
+        &self,
+        token: String,
+    ) -> Result<(GameID, PlayerName), HttpResponse> {
+        match self.verify_access(&token)? {
+            data if !data.spectator => Err(HttpResponse::Forbidden()
+                .json(ErrRespLocal::new("JWT does not belong to a spectator"))),
+            data => Ok(Self::split(data)),
+        }
+    }
+
+    /// Verifies a token that is not a refresh token and was not revoked.
+    fn verify_access(&self, token: &str) -> Result<JwtData, HttpResponse> {
+        match self.keys.verify::<JwtData>(token) {
             Err(_) => Err(HttpResponse::Unauthorized().json(ErrRespLocal::new("Invalid JWT"))),
-            Ok(data) => Ok((
-                GameID {
-                    id: data.custom.game_id,
-                },
-                PlayerName {
-                    name: data.custom.player_name,
-                },
+            Ok(data) if data.custom.refresh => Err(HttpResponse::Unauthorized().json(
+                ErrRespLocal::new("Refresh tokens can only be exchanged for new tokens"),
             )),
+            Ok(data) if self.is_revoked(&data) => {
+                Err(HttpResponse::Unauthorized().json(ErrRespLocal::new("Revoked JWT")))
+            }
+            Ok(data) => Ok(data.custom),
         }
     }
 
+    fn split(data: JwtData) -> (GameID, PlayerName) {
+        (
+            GameID { id: data.game_id },
+            PlayerName {
+                name: data.player_name,
+            },
+        )
+    }
+
+    fn bearer_token(&self, request: &HttpRequest) -> Result<String, HttpResponse> {
+        let auth_bearer =
+            match Authorization::<Bearer>::parse(request) {
+                Err(_) => return Err(HttpResponse::BadRequest().json(ErrRespLocal::new(
+                    "Request could not be parsed properly to extract authorisation header content",
+                ))),
+                Ok(x) => x.to_string(),
+            };
+
+        self.remove_bearer_prefix(auth_bearer)
+    }
+
     fn remove_bearer_prefix(&self, auth_bearer: String) -> Result<String, HttpResponse> {
         let parts = auth_bearer.split_whitespace().collect::<Vec<&str>>();
 
@@ -132,3 +431,22 @@ impl AuthService {
         Ok(parts[1].into())
     }
 }
+
+/// The current time in whole seconds, as precise as the times in tokens.
+/// Whether the token has the claims of an account token, read without checking it,
+/// so that an expired or forged account token is told apart from tokens of other kinds.
+fn claims_account(token: &str) -> bool {
+    let claims = token
+        .split('.')
+        .nth(1)
+        .and_then(|payload| Base64UrlSafeNoPadding::decode_to_vec(payload, None).ok())
+        .and_then(|payload| serde_json::from_slice::<serde_json::Value>(&payload).ok());
+
+    claims.is_some_and(|claims| {
+        claims.get("account_id").is_some() && claims.get("username").is_some()
+    })
+}
+
+fn now() -> UnixTimeStamp {
+    Duration::from_secs(Clock::now_since_epoch().as_secs())
+}
@@ -0,0 +1,239 @@
+use jwt_simple::prelude::*;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[cfg(test)]
+#[path = "../../tests/jwt_keys_test.rs"]
+mod tests;
+
+/// Shortest HS256 secret accepted in bytes, the length of the hash, shorter ones can be brute forced.
+pub static MIN_HS256_SECRET_LENGTH: usize = 32;
+
+/// Algorithms the keys in a key file can be used with.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum KeyAlgorithm {
+    HS256,
+    #[serde(alias = "Ed25519")]
+    EdDSA,
+    ES256,
+}
+
+/// One key of a key file. `key` is a base64 encoded secret for HS256, a PEM for the other algorithms:
+/// a private key signs and verifies, a public key only verifies.
+#[derive(Deserialize, Debug)]
+pub struct KeyEntry {
+    kid: String,
+    alg: KeyAlgorithm,
+    key: String,
+    /// Whether new tokens are signed with this key, the first key able to sign is used if no key says so.
+    #[serde(default)]
+    sign: bool,
+}
+
+#[derive(Debug)]
+pub enum KeyRingError {
+    Parse(serde_json::Error),
+    InvalidKey(String, String),
+    DuplicateKeyId(String),
+    CannotSign(String),
+    MultipleSigningKeys,
+    NoSigningKey,
+}
+
+impl Error for KeyRingError {}
+
+impl Display for KeyRingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use KeyRingError::*;
+
+        match self {
+            Parse(err) => write!(f, "Key file is not a JSON array of keys: {}", err),
+            InvalidKey(kid, reason) => write!(f, "Key '{}' is invalid: {}", kid, reason),
+            DuplicateKeyId(kid) => write!(f, "Key id '{}' is used more than once", kid),
+            CannotSign(kid) => write!(f, "Key '{}' is a public key, it cannot sign", kid),
+            MultipleSigningKeys => write!(f, "Only one key can be used for signing"),
+            NoSigningKey => write!(f, "There is no key able to sign tokens"),
+        }
+    }
+}
+
+enum Signer {
+    HS256(HS256Key),
+    EdDSA(Ed25519KeyPair),
+    ES256(ES256KeyPair),
+}
+
+enum Verifier {
+    HS256(HS256Key),
+    EdDSA(Ed25519PublicKey),
+    ES256(ES256PublicKey),
+}
+
+/// Public key other services can verify tokens with, without knowing any secret.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct PublicKeyInfo {
+    pub kid: String,
+    pub alg: KeyAlgorithm,
+    pub pem: String,
+}
+
+/// Keys tokens are signed and verified with. Tokens name their key in the `kid` header,
+/// so keys can be rotated by signing with a new key while the old one still verifies.
+pub struct KeyRing {
+    signer: Signer,
+    verifiers: HashMap<String, Verifier>,
+    /// Key id the tokens without one were signed with.
+    signing_kid: String,
+}
+
+impl KeyRing {
+    /// A random key that lives only as long as the process, every token dies on restart.
+    pub fn generate() -> Self {
+        let kid = nanoid::nanoid!(10);
+        let key = HS256Key::generate().with_key_id(&kid);
+
+        Self {
+            verifiers: HashMap::from([(kid.clone(), Verifier::HS256(key.clone()))]),
+            signer: Signer::HS256(key),
+            signing_kid: kid,
+        }
+    }
+
+    /// Parses a key file, a JSON array of `KeyEntry`.
+    pub fn from_json(json: &str) -> Result<Self, KeyRingError> {
+        let entries: Vec<KeyEntry> = serde_json::from_str(json).map_err(KeyRingError::Parse)?;
+        Self::from_entries(entries)
+    }
+
+    fn from_entries(entries: Vec<KeyEntry>) -> Result<Self, KeyRingError> {
+        if entries.iter().filter(|entry| entry.sign).count() > 1 {
+            return Err(KeyRingError::MultipleSigningKeys);
+        }
+
+        let mut signer = None;
+        let mut verifiers = HashMap::new();
+        for entry in entries.iter() {
+            let (entry_signer, verifier) = entry.parse()?;
+            if verifiers.insert(entry.kid.clone(), verifier).is_some() {
+                return Err(KeyRingError::DuplicateKeyId(entry.kid.clone()));
+            }
+
+            match entry_signer {
+                None if entry.sign => return Err(KeyRingError::CannotSign(entry.kid.clone())),
+                Some(entry_signer) if entry.sign || signer.is_none() => {
+                    signer = Some((entry.kid.clone(), entry_signer))
+                }
+                _ => (),
+            }
+        }
+
+        let (signing_kid, signer) = signer.ok_or(KeyRingError::NoSigningKey)?;
+        Ok(Self {
+            signer,
+            verifiers,
+            signing_kid,
+        })
+    }
+
+    pub fn sign<T: Serialize + DeserializeOwned>(
+        &self,
+        claims: JWTClaims<T>,
+    ) -> Result<String, jwt_simple::Error> {
+        match &self.signer {
+            Signer::HS256(key) => key.authenticate(claims),
+            Signer::EdDSA(key) => key.sign(claims),
+            Signer::ES256(key) => key.sign(claims),
+        }
+    }
+
+    /// Verifies the token with the key it names, tokens without a key id with the signing key.
+    pub fn verify<T: Serialize + DeserializeOwned>(
+        &self,
+        token: &str,
+    ) -> Result<JWTClaims<T>, jwt_simple::Error> {
+        let metadata = Token::decode_metadata(token)?;
+        let kid = metadata.key_id().unwrap_or(&self.signing_kid);
+        let verifier = match self.verifiers.get(kid) {
+            None => anyhow::bail!("Unknown key id '{}'", kid),
+            Some(verifier) => verifier,
+        };
+
+        match verifier {
+            Verifier::HS256(key) => key.verify_token(token, None),
+            Verifier::EdDSA(key) => key.verify_token(token, None),
+            Verifier::ES256(key) => key.verify_token(token, None),
+        }
+    }
+
+    /// The asymmetric verification keys, sorted by key id. Secrets are never part of it.
+    pub fn public_keys(&self) -> Vec<PublicKeyInfo> {
+        let mut keys: Vec<PublicKeyInfo> = self
+            .verifiers
+            .iter()
+            .filter_map(|(kid, verifier)| {
+                let (alg, pem) = match verifier {
+                    Verifier::HS256(_) => return None,
+                    Verifier::EdDSA(key) => (KeyAlgorithm::EdDSA, key.to_pem()),
+                    Verifier::ES256(key) => (KeyAlgorithm::ES256, key.to_pem().ok()?),
+                };
+                Some(PublicKeyInfo {
+                    kid: kid.clone(),
+                    alg,
+                    pem,
+                })
+            })
+            .collect();
+        keys.sort_by(|a, b| a.kid.cmp(&b.kid));
+        keys
+    }
+}
+
+impl KeyEntry {
+    /// Returns the signer if the key is a private one (or a secret) and the verifier.
+    fn parse(&self) -> Result<(Option<Signer>, Verifier), KeyRingError> {
+        let invalid = |reason: String| KeyRingError::InvalidKey(self.kid.clone(), reason);
+        let is_private = self.key.contains("PRIVATE KEY");
+
+        Ok(match self.alg {
+            KeyAlgorithm::HS256 => {
+                let secret = Base64::decode_to_vec(self.key.trim(), None)
+                    .map_err(|err| invalid(err.to_string()))?;
+                if secret.len() < MIN_HS256_SECRET_LENGTH {
+                    return Err(invalid(format!(
+                        "the secret has {} bytes, at least {} are needed",
+                        secret.len(),
+                        MIN_HS256_SECRET_LENGTH
+                    )));
+                }
+                let key = HS256Key::from_bytes(&secret).with_key_id(&self.kid);
+                (Some(Signer::HS256(key.clone())), Verifier::HS256(key))
+            }
+            KeyAlgorithm::EdDSA if is_private => {
+                let key = Ed25519KeyPair::from_pem(&self.key)
+                    .map_err(|err| invalid(err.to_string()))?
+                    .with_key_id(&self.kid);
+                let verifier = Verifier::EdDSA(key.public_key().with_key_id(&self.kid));
+                (Some(Signer::EdDSA(key)), verifier)
+            }
+            KeyAlgorithm::EdDSA => {
+                let key = Ed25519PublicKey::from_pem(&self.key)
+                    .map_err(|err| invalid(err.to_string()))?;
+                (None, Verifier::EdDSA(key.with_key_id(&self.kid)))
+            }
+            KeyAlgorithm::ES256 if is_private => {
+                let key = ES256KeyPair::from_pem(&self.key)
+                    .map_err(|err| invalid(err.to_string()))?
+                    .with_key_id(&self.kid);
+                let verifier = Verifier::ES256(key.public_key().with_key_id(&self.kid));
+                (Some(Signer::ES256(key)), verifier)
+            }
+            KeyAlgorithm::ES256 => {
+                let key =
+                    ES256PublicKey::from_pem(&self.key).map_err(|err| invalid(err.to_string()))?;
+                (None, Verifier::ES256(key.with_key_id(&self.kid)))
+            }
+        })
+    }
+}
@@ -0,0 +1,213 @@
+use nanoid::nanoid;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use uno_core::gamestate::game::Game;
+
+#[cfg(test)]
+#[path = "../../tests/matchmaking_test.rs"]
+mod tests;
+
+/// Number of seats in a game created by matchmaking.
+pub static MATCH_SIZE: usize = 4;
+/// How long the oldest waiting player waits for other people before the empty seats are given to AIs,
+/// if they allowed that.
+pub static AI_FILL_TIMEOUT: Duration = Duration::from_secs(30);
+/// Tickets not polled for this long are dropped, so that players who left are not put into games,
+/// and matches nobody picked up are forgotten.
+pub static POLL_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug)]
+pub enum MatchmakingError {
+    AlreadyWaiting(String),
+    NoSuchTicket(String),
+}
+
+impl Error for MatchmakingError {}
+
+impl Display for MatchmakingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use MatchmakingError::*;
+
+        match self {
+            AlreadyWaiting(name) => write!(f, "Player {} is already waiting for a game", name),
+            NoSuchTicket(ticket) => write!(f, "Ticket '{}' is not in the queue", ticket),
+        }
+    }
+}
+
+/// Where a player in the queue stands.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueueStatus {
+    /// Number of players waiting, including the asking one.
+    Waiting(usize),
+    Matched {
+        game_id: String,
+        player_name: String,
+    },
+}
+
+struct WaitingPlayer {
+    ticket: String,
+    name: String,
+    since: Instant,
+    polled_at: Instant,
+    fill_with_ai: bool,
+}
+
+struct Match {
+    status: QueueStatus,
+    matched_at: Instant,
+}
+
+#[derive(Default)]
+struct Queue {
+    waiting: VecDeque<WaitingPlayer>,
+    /// Tickets of the players who were put into a game but were not told so yet.
+    matched: HashMap<String, Match>,
+}
+
+/// Quick match: groups players waiting for a game into new games with random strangers.
+/// Matches are formed whenever somebody joins or polls the queue, so waiting clients are expected to poll.
+#[derive(Default)]
+pub struct MatchmakingService {
+    queue: Mutex<Queue>,
+}
+
+impl MatchmakingService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Puts the player into the queue, returns the ticket to poll with.
+    pub fn join(
+        &self,
+        name: String,
+        fill_with_ai: bool,
+        now: Instant,
+    ) -> Result<String, MatchmakingError> {
+        let mut queue = self.lock();
+        queue.drop_stale(now);
+        if queue.waiting.iter().any(|waiting| waiting.name == name) {
+            return Err(MatchmakingError::AlreadyWaiting(name));
+        }
+
+        let ticket = nanoid!(10);
+        queue.waiting.push_back(WaitingPlayer {
+            ticket: ticket.clone(),
+            name,
+            since: now,
+            polled_at: now,
+            fill_with_ai,
+        });
+
+        Ok(ticket)
+    }
+
+    pub fn leave(&self, ticket: &str) -> Result<(), MatchmakingError> {
+        let mut queue = self.lock();
+        let before = queue.waiting.len();
+        queue.waiting.retain(|waiting| waiting.ticket != ticket);
+
+        match queue.waiting.len() < before {
+            true => Ok(()),
+            false => Err(MatchmakingError::NoSuchTicket(ticket.to_string())),
+        }
+    }
+
+    /// Forms whatever matches are possible, handing every new game to `on_match` before anybody is told about it,
+    /// then returns the status of the ticket. A match is reported only once, the ticket is forgotten afterwards.
+    /// Tickets not polled within `POLL_TIMEOUT` are dropped before matching.
+    pub fn poll(
+        &self,
+        ticket: &str,
+        now: Instant,
+        on_match: impl FnMut(Game),
+    ) -> Result<QueueStatus, MatchmakingError> {
+        let mut queue = self.lock();
+        if let Some(waiting) = queue
+            .waiting
+            .iter_mut()
+            .find(|waiting| waiting.ticket == ticket)
+        {
+            waiting.polled_at = now;
+        }
+        queue.drop_stale(now);
+        queue.form_matches(now, on_match);
+
+        if let Some(matched) = queue.matched.remove(ticket) {
+            return Ok(matched.status);
+        }
+
+        match queue.waiting.iter().any(|waiting| waiting.ticket == ticket) {
+            true => Ok(QueueStatus::Waiting(queue.waiting.len())),
+            false => Err(MatchmakingError::NoSuchTicket(ticket.to_string())),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Queue> {
+        // a poisoned queue only means that a panic happened while matching, the queue itself stays valid
+        self.queue.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Queue {
+    fn drop_stale(&mut self, now: Instant) {
+        let is_stale = |at: Instant| now.saturating_duration_since(at) > POLL_TIMEOUT;
+        self.waiting.retain(|waiting| !is_stale(waiting.polled_at));
+        self.matched
+            .retain(|_, matched| !is_stale(matched.matched_at));
+    }
+
+    fn form_matches(&mut self, now: Instant, mut on_match: impl FnMut(Game)) {
+        while self.waiting.len() >= MATCH_SIZE {
+            let group: Vec<WaitingPlayer> = self.waiting.drain(..MATCH_SIZE).collect();
+            on_match(self.start_match(group, now));
+        }
+
+        let timed_out = self
+            .waiting
+            .iter()
+            .find(|waiting| waiting.fill_with_ai)
+            .is_some_and(|oldest| now.duration_since(oldest.since) >= AI_FILL_TIMEOUT);
+        if timed_out {
+            let (group, rest): (Vec<_>, Vec<_>) = self
+                .waiting
+                .drain(..)
+                .partition(|waiting| waiting.fill_with_ai);
+            self.waiting = rest.into();
+            on_match(self.start_match(group, now));
+        }
+    }
+
+    /// Seats the group in a new running game, the oldest waiting player becomes its author.
+    fn start_match(&mut self, group: Vec<WaitingPlayer>, now: Instant) -> Game {
+        let mut game = Game::new(group[0].name.clone());
+        for waiting in group.iter().skip(1) {
+            // safe since the names in the queue are unique and the game is in the lobby
+            game.add_player(waiting.name.clone()).unwrap();
+        }
+        (group.len()..MATCH_SIZE).for_each(|_| game.add_ai());
+
+        if let Err(error) = game.start() {
+            println!("Could not start matched game {}: {}", game.id, error);
+        }
+
+        for waiting in group {
+            self.matched.insert(
+                waiting.ticket,
+                Match {
+                    status: QueueStatus::Matched {
+                        game_id: game.id.clone(),
+                        player_name: waiting.name,
+                    },
+                    matched_at: now,
+                },
+            );
+        }
+
+        game
+    }
+}
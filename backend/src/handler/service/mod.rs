@@ -1,2 +1,6 @@
+pub mod admin;
 pub mod auth;
+pub mod jwt_keys;
 pub mod lb_connector;
+pub mod matchmaking;
+pub mod ws_ticket;
@@ -0,0 +1,53 @@
+use nanoid::nanoid;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+#[path = "../../tests/ws_ticket_test.rs"]
+mod tests;
+
+/// How long a ticket can be used to open a WebSocket.
+pub static TICKET_LIFETIME: Duration = Duration::from_secs(30);
+
+/// Who a ticket lets in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TicketHolder {
+    pub game_id: String,
+    pub name: String,
+    pub spectator: bool,
+}
+
+/// One-time tickets for opening a WebSocket, so that tokens never appear in URLs.
+#[derive(Default)]
+pub struct WsTicketService {
+    tickets: Mutex<HashMap<String, (TicketHolder, Instant)>>,
+}
+
+impl WsTicketService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn issue(&self, holder: TicketHolder, now: Instant) -> String {
+        let mut tickets = self.lock();
+        tickets.retain(|_, (_, expires_at)| *expires_at > now);
+
+        let ticket = nanoid!(32);
+        tickets.insert(ticket.clone(), (holder, now + TICKET_LIFETIME));
+        ticket
+    }
+
+    /// Returns who the ticket belongs to, unless it expired. Every ticket can be redeemed only once.
+    pub fn redeem(&self, ticket: &str, now: Instant) -> Option<TicketHolder> {
+        match self.lock().remove(ticket) {
+            Some((holder, expires_at)) if expires_at > now => Some(holder),
+            _ => None,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (TicketHolder, Instant)>> {
+        // a poisoned map only means that a panic happened while inserting, the map itself stays valid
+        self.tickets.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,131 @@
+use crate::handler::util::response::{ErrMsg, ErrorResponse};
+use crate::handler::util::safe_lock::safe_lock;
+use crate::session::spectators::SpectateError;
+use crate::{AuthService, InMemoryGameRepo};
+use actix_web::{http::StatusCode, post, put, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use std::time::Duration;
+
+#[derive(Deserialize, Debug)]
+pub struct SpectatorRequestBody {
+    name: String,
+}
+
+#[post("/game/{gameID}/spectator")]
+pub async fn create_spectator(
+    route_params: web::Path<String>,
+    request_body: web::Json<SpectatorRequestBody>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let game_id = route_params.into_inner();
+    match create_spectator_response(game_id, request_body, auth_service, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn create_spectator_response(
+    game_id: String,
+    request_body: web::Json<SpectatorRequestBody>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let spectator_name = &request_body.name;
+
+    if spectator_name.is_empty() {
+        return Err(HttpResponse::BadRequest().json(ErrMsg::new_from_scratch(
+            "Name of the spectator cannot be empty.",
+        )));
+    }
+
+    let game = game_repo.get_game_by_id(game_id.clone())?;
+
+    let mut session = safe_lock(&game)?;
+    if !session.spectators().is_enabled() {
+        return Err(SpectateError::Disabled.error_response());
+    }
+    session
+        .add_spectator(spectator_name)
+        .map_err(ErrorResponse::error_response)?;
+
+    let tokens = auth_service.issue_spectator_tokens(spectator_name, &game_id);
+    Ok(HttpResponse::Created().json(tokens))
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsRequestBody {
+    enabled: bool,
+    /// How long spectators lag behind the game
+    #[serde(default)]
+    delay_seconds: u64,
+}
+
+#[put("/game/{gameID}/spectatorSettings")]
+pub async fn spectator_settings(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    request_body: web::Json<SettingsRequestBody>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let game_id = route_params.into_inner();
+    match spectator_settings_response(game_id, request, request_body, auth_service, game_repo) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+fn spectator_settings_response(
+    game_id: String,
+    request: HttpRequest,
+    request_body: web::Json<SettingsRequestBody>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let (game_id_from_token, player_name) = auth_service.extract_data(&request)?;
+
+    let game_id = game_id_from_token.check(game_id)?;
+
+    let game = game_repo.get_game_by_id(game_id)?;
+    let mut session = safe_lock(&game)?;
+
+    let player_name = player_name.into_inner();
+    match session.game().find_author() {
+        Some(author) if author.name() == player_name => (),
+        _ => return Err(SpectateError::NotAuthor(player_name).error_response()),
+    }
+
+    session
+        .spectators_mut()
+        .configure(
+            request_body.enabled,
+            Duration::from_secs(request_body.delay_seconds),
+        )
+        .map_err(ErrorResponse::error_response)?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+impl ErrorResponse for SpectateError {
+    fn status_code(&self) -> StatusCode {
+        use SpectateError::*;
+        match self {
+            Disabled | NotAuthor(_) | CannotPlay => StatusCode::FORBIDDEN,
+            DelayTooLong(_) => StatusCode::BAD_REQUEST,
+            NameTaken(_) => StatusCode::CONFLICT,
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        use SpectateError::*;
+        match self {
+            Disabled => Some("SPECTATING_DISABLED"),
+            NotAuthor(_) => Some("NOT_AUTHOR"),
+            CannotPlay => Some("SPECTATOR"),
+            DelayTooLong(_) => None,
+            NameTaken(_) => Some("NAME_TAKEN"),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use actix_web::http::header::{ETAG, IF_NONE_MATCH};
+use actix_web::{HttpRequest, HttpResponse};
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+#[cfg(test)]
+#[path = "../../tests/etag_test.rs"]
+mod tests;
+
+/// Responds with the body as JSON tagged by a hash of it, or with an empty 304 Not Modified
+/// if the client already has the same body, so that polling clients do not download the state again.
+pub fn json_with_etag<T: Serialize>(request: &HttpRequest, body: &T) -> HttpResponse {
+    let json = match serde_json::to_string(body) {
+        Ok(json) => json,
+        Err(_) => return HttpResponse::InternalServerError().finish(),
+    };
+
+    let mut hasher = DefaultHasher::new();
+    json.hash(&mut hasher);
+    let etag = format!("\"{:x}\"", hasher.finish());
+
+    let unchanged = request
+        .headers()
+        .get_all(IF_NONE_MATCH)
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|tag| tag.trim() == etag || tag.trim() == "*");
+
+    if unchanged {
+        return HttpResponse::NotModified().header(ETAG, etag).finish();
+    }
+
+    HttpResponse::Ok()
+        .header(ETAG, etag)
+        .content_type("application/json")
+        .body(json)
+}
@@ -0,0 +1,186 @@
+use crate::handler::util::response::{ErrBody, ErrorResponse};
+use crate::session::game_session::GameSession;
+use crate::session::idempotency::IdempotentResult;
+use actix_web::http::{HeaderName, HeaderValue, StatusCode};
+use actix_web::{HttpRequest, HttpResponse};
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use uno_core::gamestate::command::Command;
+
+#[cfg(test)]
+#[path = "../../tests/idempotency_test.rs"]
+mod tests;
+
+pub static IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+static MAX_KEY_LENGTH: usize = 128;
+
+#[derive(Debug)]
+pub enum ConcurrencyError {
+    StaleState { expected: u64, actual: u64 },
+    InvalidIdempotencyKey,
+    IdempotencyKeyReused(String),
+    IdempotencyKeyReusedForOtherBody,
+}
+
+impl Error for ConcurrencyError {}
+
+impl Display for ConcurrencyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use ConcurrencyError::*;
+
+        match self {
+            StaleState { expected, actual } => write!(
+                f,
+                "The game moved on to turn {}, the action was meant for turn {}",
+                actual, expected
+            ),
+            InvalidIdempotencyKey => write!(
+                f,
+                "{} must be 1 to {} visible ASCII characters",
+                IDEMPOTENCY_KEY_HEADER, MAX_KEY_LENGTH
+            ),
+            IdempotencyKeyReused(route) => write!(
+                f,
+                "{} was already used for a request to {}",
+                IDEMPOTENCY_KEY_HEADER, route
+            ),
+            IdempotencyKeyReusedForOtherBody => write!(
+                f,
+                "{} was already used for a request with another body",
+                IDEMPOTENCY_KEY_HEADER
+            ),
+        }
+    }
+}
+
+impl ErrorResponse for ConcurrencyError {
+    fn status_code(&self) -> StatusCode {
+        use ConcurrencyError::*;
+        match self {
+            StaleState { .. } => StatusCode::CONFLICT,
+            InvalidIdempotencyKey => StatusCode::BAD_REQUEST,
+            IdempotencyKeyReused(_) | IdempotencyKeyReusedForOtherBody => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        use ConcurrencyError::*;
+        match self {
+            StaleState { .. } => Some("STALE_STATE"),
+            InvalidIdempotencyKey => Some("INVALID_IDEMPOTENCY_KEY"),
+            IdempotencyKeyReused(_) | IdempotencyKeyReusedForOtherBody => {
+                Some("IDEMPOTENCY_KEY_REUSED")
+            }
+        }
+    }
+}
+
+/// Applies the command of the player and answers with `204 No Content`, or the error.
+/// A request retried with the same `Idempotency-Key` gets the result of the first one without applying the command again,
+/// a request with an `expectedTurn` the game already moved on from is rejected.
+pub fn apply_once(
+    request: &HttpRequest,
+    session: &mut GameSession,
+    player_name: &str,
+    expected_turn: Option<u64>,
+    command: Command,
+) -> Result<HttpResponse, HttpResponse> {
+    let key = idempotency_key(request).map_err(ErrorResponse::error_response)?;
+    let route = request.match_pattern().unwrap_or_default();
+    let request_hash = request_hash(&command, expected_turn);
+
+    if let Some(key) = &key {
+        if let Some(result) = session.idempotency().get(player_name, key) {
+            if result.route != route {
+                return Err(
+                    ConcurrencyError::IdempotencyKeyReused(result.route.clone()).error_response()
+                );
+            }
+            if result.request_hash != request_hash {
+                return Err(ConcurrencyError::IdempotencyKeyReusedForOtherBody.error_response());
+            }
+            let mut response = to_response(&result.outcome);
+            response.headers_mut().insert(
+                HeaderName::from_static("idempotent-replayed"),
+                HeaderValue::from_static("true"),
+            );
+            return Ok(response);
+        }
+    }
+
+    let outcome = match check_turn(session, expected_turn) {
+        Err(error) => Err(error_parts(error)),
+        Ok(()) => session.apply(command).map_err(error_parts),
+    };
+
+    let response = to_response(&outcome);
+    if let Some(key) = key {
+        session.idempotency_mut().insert(
+            player_name,
+            key,
+            IdempotentResult {
+                route,
+                request_hash,
+                outcome,
+            },
+        );
+    }
+    match response.status().is_success() {
+        true => Ok(response),
+        false => Err(response),
+    }
+}
+
+/// Rejects an action meant for another turn than the current one of the game.
+pub fn check_turn(
+    session: &GameSession,
+    expected_turn: Option<u64>,
+) -> Result<(), ConcurrencyError> {
+    let turn = session.game().turn();
+    match expected_turn {
+        Some(expected) if expected != turn => Err(ConcurrencyError::StaleState {
+            expected,
+            actual: turn,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Hash of what the request asks for, taken from its parsed body, so that a retry formatted differently still matches.
+fn request_hash(command: &Command, expected_turn: Option<u64>) -> [u8; 32] {
+    // serializing plain data cannot fail
+    let json = serde_json::to_vec(&(command, expected_turn)).unwrap_or_default();
+    Sha256::digest(json).into()
+}
+
+fn error_parts(error: impl ErrorResponse) -> (StatusCode, ErrBody) {
+    (error.status_code(), error.error_body())
+}
+
+fn to_response(outcome: &Result<(), (StatusCode, ErrBody)>) -> HttpResponse {
+    match outcome {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err((status, body)) => HttpResponse::build(*status).json(body),
+    }
+}
+
+fn idempotency_key(request: &HttpRequest) -> Result<Option<String>, ConcurrencyError> {
+    let header = match request.headers().get(IDEMPOTENCY_KEY_HEADER) {
+        None => return Ok(None),
+        Some(header) => header,
+    };
+
+    match header.to_str() {
+        Ok(key)
+            if !key.is_empty()
+                && key.len() <= MAX_KEY_LENGTH
+                && key.chars().all(|c| c.is_ascii_graphic()) =>
+        {
+            Ok(Some(key.to_string()))
+        }
+        _ => Err(ConcurrencyError::InvalidIdempotencyKey),
+    }
+}
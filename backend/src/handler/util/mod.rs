@@ -1,2 +1,4 @@
+pub mod etag;
+pub mod idempotency;
 pub mod response;
 pub mod safe_lock;
@@ -1,11 +1,14 @@
 use std::error::Error;
 
-use actix_web::HttpResponse;
+use actix_web::{http::StatusCode, HttpResponse};
 use serde::Serialize;
 
 use crate::repo::game_repo::GameRepoError;
+use crate::session::chat::ChatError;
+use uno_core::err::call_uno::CallUnoError;
+use uno_core::err::command::CommandError;
 
-#[derive(Serialize)]
+#[derive(Serialize, Debug, Clone, PartialEq)]
 pub struct TypedErrMsg {
     #[serde(rename(serialize = "type", deserialize = "type"))]
     type_of_error: String,
@@ -19,16 +22,9 @@ impl TypedErrMsg {
             message: error.to_string(),
         }
     }
-
-    pub fn new_from_scratch(type_of_error: &str, message: String) -> Self {
-        Self {
-            type_of_error: type_of_error.into(),
-            message,
-        }
-    }
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone, PartialEq)]
 pub struct ErrMsg {
     message: String,
 }
@@ -55,3 +51,109 @@ impl From<GameRepoError> for HttpResponse {
         }
     }
 }
+
+/// Body describing an error, with a type if the error has one.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ErrBody {
+    Typed(TypedErrMsg),
+    Plain(ErrMsg),
+}
+
+/// Errors of the game rules that can be turned into an HTTP response.
+pub trait ErrorResponse: Error + Sized {
+    fn status_code(&self) -> StatusCode;
+
+    /// Machine readable type of the error, errors without one are sent only with a message.
+    fn error_type(&self) -> Option<&'static str>;
+
+    fn error_body(&self) -> ErrBody {
+        match self.error_type() {
+            Some(type_of_error) => ErrBody::Typed(TypedErrMsg::new(type_of_error, self)),
+            None => ErrBody::Plain(ErrMsg::new(self)),
+        }
+    }
+
+    fn error_response(self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(self.error_body())
+    }
+}
+
+impl ErrorResponse for CommandError {
+    fn status_code(&self) -> StatusCode {
+        use CommandError::*;
+        match self {
+            AddPlayer(err) => err.status_code(),
+            Start(err) => err.status_code(),
+            Lobby(err) => err.status_code(),
+            PlayCard(err) => err.status_code(),
+            DrawCards(err) => err.status_code(),
+            CallUno(err) => err.status_code(),
+            Forfeit(err) => err.status_code(),
+            Rematch(err) => err.status_code(),
+            Fairness(err) => err.status_code(),
+            ForceFinish(err) => err.status_code(),
+            CreateStatusError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        use CommandError::*;
+        match self {
+            AddPlayer(err) => err.error_type(),
+            Start(err) => err.error_type(),
+            Lobby(err) => err.error_type(),
+            PlayCard(err) => err.error_type(),
+            DrawCards(err) => err.error_type(),
+            CallUno(err) => err.error_type(),
+            Forfeit(err) => err.error_type(),
+            Rematch(err) => err.error_type(),
+            Fairness(err) => err.error_type(),
+            ForceFinish(err) => err.error_type(),
+            CreateStatusError(_) => None,
+        }
+    }
+}
+
+impl ErrorResponse for CallUnoError {
+    fn status_code(&self) -> StatusCode {
+        use CallUnoError::*;
+        match self {
+            PlayerTurnError(_) | GameNotRunning(_) => StatusCode::CONFLICT,
+            PlayerExistError(_) => StatusCode::NOT_FOUND,
+            CannotCallUno => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        use CallUnoError::*;
+        match self {
+            PlayerTurnError(_) => Some("NOT_YOUR_TURN"),
+            GameNotRunning(_) => Some("GAME_NOT_RUNNING"),
+            PlayerExistError(_) | CannotCallUno => None,
+        }
+    }
+}
+
+impl ErrorResponse for ChatError {
+    fn status_code(&self) -> StatusCode {
+        use ChatError::*;
+        match self {
+            EmptyMessage | TooLong(_) => StatusCode::BAD_REQUEST,
+            RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            Muted(_) | NotAuthor(_) => StatusCode::FORBIDDEN,
+            PlayerExistError(_) => StatusCode::NOT_FOUND,
+        }
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        use ChatError::*;
+        match self {
+            EmptyMessage | TooLong(_) => Some("INVALID_CHAT_MESSAGE"),
+            RateLimited => Some("CHAT_RATE_LIMITED"),
+            Muted(_) => Some("MUTED"),
+            NotAuthor(_) => Some("NOT_AUTHOR"),
+            PlayerExistError(_) => None,
+        }
+    }
+}
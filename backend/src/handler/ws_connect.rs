@@ -1,58 +1,279 @@
-use crate::handler::util::response::ErrMsg;
+use crate::handler::service::ws_ticket::WsTicketService;
+use crate::handler::util::response::{ErrMsg, ErrorResponse};
 use crate::handler::util::safe_lock::safe_lock;
 use crate::{AuthService, InMemoryGameRepo};
-use actix_web::{get, web, HttpRequest, HttpResponse};
-use std::sync::Mutex;
+use actix_web::{get, http::header, http::StatusCode, web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use std::time::Instant;
 
-use crate::ws::{ws_conn::WSConn, ws_message::WSMsg};
+use crate::session::spectators::SpectateError;
+use crate::ws::protocol::{ProtocolError, ProtocolVersion};
+use crate::ws::ws_conn::{WSConn, WS_PROTOCOL};
+use serde::Deserialize;
 
-#[get("/ws/token/{token}")]
+#[derive(Deserialize, Debug)]
+pub struct ConnectParams {
+    /// Sequence number of the last message the client received before reconnecting.
+    since: Option<u64>,
+    /// Newest protocol version the client understands.
+    version: Option<u32>,
+    /// One-time ticket from `POST /ws/ticket`.
+    ticket: Option<String>,
+}
+
+/// Connection of a player, authenticated by a ticket or by the token offered as a subprotocol.
+#[get("/ws")]
 pub async fn ws_connect(
+    request: HttpRequest,
+    stream: web::Payload,
+    connect_params: web::Query<ConnectParams>,
+    auth_service: web::Data<AuthService>,
+    tickets: web::Data<WsTicketService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let (game_id, player_name) =
+        match authenticate(&request, &connect_params, &auth_service, &tickets, false) {
+            Err(response) => return response,
+            Ok(data) => data,
+        };
+
+    match connect_player(
+        game_id,
+        player_name,
+        request,
+        stream,
+        connect_params,
+        game_repo,
+    ) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+/// Connection of a player with the token in the URL, only enabled for old frontends.
+#[get("/ws/token/{token}")]
+pub async fn ws_connect_legacy(
     route_params: web::Path<String>,
     request: HttpRequest,
     stream: web::Payload,
+    connect_params: web::Query<ConnectParams>,
     auth_service: web::Data<AuthService>,
-    game_repo: web::Data<Mutex<InMemoryGameRepo>>,
+    game_repo: web::Data<InMemoryGameRepo>,
 ) -> HttpResponse {
-    let jwt = route_params.into_inner();
+    let (game_id, player_name) =
+        match auth_service.extract_data_from_token(route_params.into_inner()) {
+            Err(response) => return response,
+            Ok(data) => data,
+        };
 
-    let (game_id, player_name) = match auth_service.extract_data_from_token(jwt) {
-        Err(response) => return response,
-        Ok(data) => data,
-    };
-    let player_name = player_name.into_inner();
+    match connect_player(
+        game_id.into_inner(),
+        player_name.into_inner(),
+        request,
+        stream,
+        connect_params,
+        game_repo,
+    ) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
 
-    let mut game_repo = match safe_lock(&game_repo) {
-        Err(response) => return response,
-        Ok(repo) => repo,
-    };
+/// Connection of a spectator, who gets only the public messages of the game.
+#[get("/ws/spectator")]
+pub async fn ws_spectate(
+    request: HttpRequest,
+    stream: web::Payload,
+    connect_params: web::Query<ConnectParams>,
+    auth_service: web::Data<AuthService>,
+    tickets: web::Data<WsTicketService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let (game_id, spectator_name) =
+        match authenticate(&request, &connect_params, &auth_service, &tickets, true) {
+            Err(response) => return response,
+            Ok(data) => data,
+        };
 
-    let game = match game_repo.get_game_by_id_mut(game_id.into_inner()) {
-        Err(response) => return response.into(),
-        Ok(game) => game,
-    };
+    match connect_spectator(
+        game_id,
+        spectator_name,
+        request,
+        stream,
+        connect_params,
+        game_repo,
+    ) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
 
-    let msg = match WSMsg::status(game, player_name.clone()) {
-        Err(error) => return HttpResponse::InternalServerError().json(ErrMsg::new(error)),
-        Ok(msg) => msg,
-    };
+/// Connection of a spectator with the token in the URL, only enabled for old frontends.
+#[get("/ws/spectator/{token}")]
+pub async fn ws_spectate_legacy(
+    route_params: web::Path<String>,
+    request: HttpRequest,
+    stream: web::Payload,
+    connect_params: web::Query<ConnectParams>,
+    auth_service: web::Data<AuthService>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> HttpResponse {
+    let (game_id, spectator_name) =
+        match auth_service.extract_spectator_from_token(route_params.into_inner()) {
+            Err(response) => return response,
+            Ok(data) => data,
+        };
 
-    let player = match game.find_player_mut(&player_name) {
-        None => {
-            return HttpResponse::NotFound().json(ErrMsg::new_from_scratch(
-                "Player with this name does not exist",
-            ))
-        }
-        Some(player) => player,
-    };
+    match connect_spectator(
+        game_id.into_inner(),
+        spectator_name.into_inner(),
+        request,
+        stream,
+        connect_params,
+        game_repo,
+    ) {
+        Ok(r) => r,
+        Err(r) => r,
+    }
+}
+
+/// Returns (game_id, name) of whoever holds the ticket in the query, or else the token offered as a subprotocol.
+fn authenticate(
+    request: &HttpRequest,
+    connect_params: &ConnectParams,
+    auth_service: &AuthService,
+    tickets: &WsTicketService,
+    spectator: bool,
+) -> Result<(String, String), HttpResponse> {
+    if let Some(ticket) = &connect_params.ticket {
+        return match tickets.redeem(ticket, Instant::now()) {
+            Some(holder) if holder.spectator == spectator => Ok((holder.game_id, holder.name)),
+            Some(_) => Err(HttpResponse::Forbidden().json(ErrMsg::new_from_scratch(
+                "Ticket was issued for a different kind of connection",
+            ))),
+            None => Err(HttpResponse::Unauthorized()
+                .json(ErrMsg::new_from_scratch("Invalid or expired ticket"))),
+        };
+    }
 
-    let (conn, response) = match WSConn::new(&request, stream) {
-        Err(error) => return HttpResponse::InternalServerError().json(ErrMsg::new(error)),
-        Ok(data) => data,
+    let token = protocol_token(request).ok_or_else(|| {
+        HttpResponse::Unauthorized().json(ErrMsg::new_from_scratch(
+            "Missing ticket or token in Sec-WebSocket-Protocol",
+        ))
+    })?;
+    let (game_id, name) = match spectator {
+        false => auth_service.extract_data_from_token(token)?,
+        true => auth_service.extract_spectator_from_token(token)?,
     };
 
-    player.set_connection(conn);
-    player.message(msg);
+    Ok((game_id.into_inner(), name.into_inner()))
+}
+
+/// The token offered as a subprotocol next to `WS_PROTOCOL`, the only header browsers let WebSocket clients set.
+fn protocol_token(request: &HttpRequest) -> Option<String> {
+    let offered = request
+        .headers()
+        .get(header::SEC_WEBSOCKET_PROTOCOL)?
+        .to_str()
+        .ok()?;
+    let protocols: Vec<&str> = offered.split(',').map(str::trim).collect();
+    if !protocols.contains(&WS_PROTOCOL) {
+        return None;
+    }
+
+    protocols
+        .into_iter()
+        .find(|protocol| *protocol != WS_PROTOCOL)
+        .map(String::from)
+}
+
+fn connect_player(
+    game_id: String,
+    player_name: String,
+    request: HttpRequest,
+    stream: web::Payload,
+    connect_params: web::Query<ConnectParams>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let version = ProtocolVersion::negotiate(connect_params.version)
+        .map_err(ErrorResponse::error_response)?;
+
+    let game = game_repo.get_game_by_id(game_id)?;
+    let mut session = safe_lock(&game)?;
+
+    if session.game().find_player(player_name.clone()).is_none() {
+        return Err(HttpResponse::NotFound().json(ErrMsg::new_from_scratch(
+            "Player with this name does not exist",
+        )));
+    }
+
+    let msgs = session
+        .catch_up(&player_name, connect_params.since)
+        .map_err(|error| HttpResponse::InternalServerError().json(ErrMsg::new(error)))?;
+
+    let (conn, response) = WSConn::new(
+        &request,
+        stream,
+        Arc::clone(&game),
+        player_name.clone(),
+        version,
+        false,
+    )
+    .map_err(|error| HttpResponse::InternalServerError().json(ErrMsg::new(error)))?;
+
+    session.connect(player_name.clone(), conn);
+    session.replay(&player_name, msgs);
+
+    Ok(response)
+}
+
+fn connect_spectator(
+    game_id: String,
+    spectator_name: String,
+    request: HttpRequest,
+    stream: web::Payload,
+    connect_params: web::Query<ConnectParams>,
+    game_repo: web::Data<InMemoryGameRepo>,
+) -> Result<HttpResponse, HttpResponse> {
+    let version = ProtocolVersion::negotiate(connect_params.version)
+        .map_err(ErrorResponse::error_response)?;
+
+    let game = game_repo.get_game_by_id(game_id)?;
+    let mut session = safe_lock(&game)?;
+
+    if !session.spectators().is_enabled() {
+        return Err(SpectateError::Disabled.error_response());
+    }
+
+    let status = session
+        .spectator_status()
+        .map_err(|error| HttpResponse::InternalServerError().json(ErrMsg::new(error)))?;
+
+    let (conn, response) = WSConn::new(
+        &request,
+        stream,
+        Arc::clone(&game),
+        spectator_name.clone(),
+        version,
+        true,
+    )
+    .map_err(|error| HttpResponse::InternalServerError().json(ErrMsg::new(error)))?;
+
+    session
+        .spectators_mut()
+        .connect(spectator_name.clone(), conn)
+        .map_err(ErrorResponse::error_response)?;
+    session.spectators().message(&spectator_name, status);
+
+    Ok(response)
+}
+
+impl ErrorResponse for ProtocolError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
 
-    response
+    fn error_type(&self) -> Option<&'static str> {
+        Some("UNSUPPORTED_PROTOCOL_VERSION")
+    }
 }
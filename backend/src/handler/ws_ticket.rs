@@ -0,0 +1,32 @@
+use crate::handler::service::ws_ticket::{TicketHolder, WsTicketService};
+use crate::AuthService;
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Serialize;
+use std::time::Instant;
+
+#[derive(Serialize, Debug)]
+pub struct SuccessResponse {
+    ticket: String,
+}
+
+/// Exchanges the token of a player or a spectator for a one-time ticket to open their WebSocket with.
+#[post("/ws/ticket")]
+pub async fn ws_ticket(
+    request: HttpRequest,
+    auth_service: web::Data<AuthService>,
+    tickets: web::Data<WsTicketService>,
+) -> HttpResponse {
+    let (game_id, name, spectator) = match auth_service.extract_any(&request) {
+        Err(response) => return response,
+        Ok(data) => data,
+    };
+
+    let holder = TicketHolder {
+        game_id: game_id.into_inner(),
+        name: name.into_inner(),
+        spectator,
+    };
+    let ticket = tickets.issue(holder, Instant::now());
+
+    HttpResponse::Created().json(SuccessResponse { ticket })
+}
@@ -0,0 +1,138 @@
+use crate::handler::util::response::{ErrMsg, TypedErrMsg};
+use actix_cors::Cors;
+use actix_web::error::{InternalError, JsonPayloadError};
+use actix_web::http::{Method, Uri};
+use actix_web::middleware::DefaultHeaders;
+use actix_web::{web, HttpResponse};
+use anyhow::bail;
+
+#[cfg(test)]
+#[path = "tests/http_policy_test.rs"]
+mod tests;
+
+/// Who may call the API from a browser and how big the request bodies may be.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpPolicy {
+    /// Origins allowed to make requests, any origin if empty.
+    origins: Vec<String>,
+    /// Methods allowed in cross-origin requests, any method if empty.
+    methods: Vec<Method>,
+    /// Whether browsers may send cookies and Authorization headers cross-origin.
+    credentials: bool,
+    /// Largest JSON body accepted, in bytes.
+    max_body_size: usize,
+}
+
+impl HttpPolicy {
+    /// `origins` and `methods` are comma separated lists, `*` allows any.
+    pub fn new(
+        origins: &str,
+        methods: &str,
+        credentials: bool,
+        max_body_size: usize,
+    ) -> anyhow::Result<Self> {
+        let origins = parse_list(origins);
+        for origin in origins.iter() {
+            match origin.parse::<Uri>() {
+                Ok(uri) if uri.scheme().is_some() && uri.host().is_some() => (),
+                _ => bail!(
+                    "CORS origin '{}' must look like https://example.com",
+                    origin
+                ),
+            }
+        }
+        if credentials && origins.is_empty() {
+            bail!("CORS credentials can only be allowed for a list of origins, not for any origin");
+        }
+
+        let mut parsed_methods = Vec::new();
+        for method in parse_list(methods) {
+            match Method::from_bytes(method.to_uppercase().as_bytes()) {
+                Ok(method) => parsed_methods.push(method),
+                Err(_) => bail!("'{}' is not an HTTP method", method),
+            }
+        }
+
+        if max_body_size == 0 {
+            bail!("Maximal body size must be positive");
+        }
+
+        Ok(Self {
+            origins,
+            methods: parsed_methods,
+            credentials,
+            max_body_size,
+        })
+    }
+
+    pub fn cors(&self) -> Cors {
+        let mut cors = Cors::default().allow_any_header();
+
+        cors = match self.origins.is_empty() {
+            true => cors.allow_any_origin(),
+            false => self
+                .origins
+                .iter()
+                .fold(cors, |cors, origin| cors.allowed_origin(origin)),
+        };
+        cors = match self.methods.is_empty() {
+            true => cors.allow_any_method(),
+            false => cors.allowed_methods(self.methods.clone()),
+        };
+        match self.credentials {
+            true => cors.supports_credentials(),
+            false => cors,
+        }
+    }
+
+    /// Limits the JSON bodies of every endpoint and answers bodies that cannot be read with a JSON error.
+    pub fn json_config(&self) -> web::JsonConfig {
+        json_config(self.max_body_size)
+    }
+
+    /// The same as `json_config`, for endpoints whose bodies can legitimately be bigger than the usual limit.
+    pub fn json_config_up_to(&self, limit: usize) -> web::JsonConfig {
+        json_config(limit.max(self.max_body_size))
+    }
+}
+
+fn json_config(limit: usize) -> web::JsonConfig {
+    web::JsonConfig::default()
+        .limit(limit)
+        .error_handler(|error, _| {
+            let response = match &error {
+                JsonPayloadError::Overflow => HttpResponse::PayloadTooLarge()
+                    .json(TypedErrMsg::new("PAYLOAD_TOO_LARGE", &error)),
+                _ => HttpResponse::BadRequest().json(ErrMsg::new(&error)),
+            };
+            InternalError::from_response(error, response).into()
+        })
+}
+
+/// Headers sent with every response. The server only answers with JSON,
+/// so nothing it sends is ever meant to be rendered, framed or sniffed by a browser.
+pub fn security_headers() -> DefaultHeaders {
+    DefaultHeaders::new()
+        .header("X-Content-Type-Options", "nosniff")
+        .header("X-Frame-Options", "DENY")
+        .header("Referrer-Policy", "no-referrer")
+        .header(
+            "Content-Security-Policy",
+            "default-src 'none'; frame-ancestors 'none'",
+        )
+}
+
+/// Splits a comma separated list, where `*` stands for anything and gives an empty list.
+fn parse_list(list: &str) -> Vec<String> {
+    let items: Vec<String> = list
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect();
+
+    match items.iter().any(|item| item == "*") {
+        true => Vec::new(),
+        false => items,
+    }
+}
@@ -1,21 +1,52 @@
+use crate::handler::account::{get_account, login, register};
+use crate::handler::admin::{
+    admin_delete_game, admin_finish_game, admin_get_game, admin_kick_player, admin_list_games,
+    admin_notice,
+};
+use crate::handler::chat::{chat, mute};
 use crate::handler::draw_card::draw_card;
+use crate::handler::fairness::{add_entropy, get_fairness, verify_fairness, MAX_PROOF_SIZE};
+use crate::handler::forfeit::forfeit;
+use crate::handler::game_state::{get_game, get_players};
+use crate::handler::hints::hints;
 use crate::handler::join_game::join_game;
 use crate::handler::lb_reconnect::lb_reconnect;
+use crate::handler::list_games::list_games;
+use crate::handler::lobby::{add_ai, kick_player, leave_game, remove_ai, transfer_author};
+use crate::handler::matchmaking::{join_queue, leave_queue, poll_queue};
+use crate::handler::public_keys::public_keys;
+use crate::handler::refresh_token::refresh_token;
+use crate::handler::rematch::{confirm_rematch, propose_rematch, return_to_lobby};
 use crate::handler::restart_game::start_game;
+use crate::handler::results::{get_results, verify_result};
+use crate::handler::service::admin::AdminService;
 use crate::handler::service::auth::AuthService;
+use crate::handler::service::jwt_keys::KeyRing;
+use crate::handler::service::matchmaking::MatchmakingService;
+use crate::handler::service::ws_ticket::WsTicketService;
 use crate::handler::{create_game::create_game, service::lb_connector::LoadBalancerConnector};
+use crate::http_policy::{security_headers, HttpPolicy};
+use crate::rate_limit::{RateLimit, RateLimiter, RateLimits};
+use crate::repo::account_repo::AccountRepo;
 use crate::repo::game_repo::InMemoryGameRepo;
-use actix_cors::Cors;
+use crate::session::chat::WordFilter;
 use actix_web::{web, App, HttpServer};
 use clap::Parser;
-use handler::{play_card::play_card, ws_connect::ws_connect};
-use std::{env, sync::Mutex};
+use handler::spectate::{create_spectator, spectator_settings};
+use handler::{
+    play_card::play_card,
+    ws_connect::{ws_connect, ws_connect_legacy, ws_spectate, ws_spectate_legacy},
+    ws_ticket::ws_ticket,
+};
+use std::env;
+use std::fs;
+use std::sync::Arc;
 
-mod cards;
-mod err;
-mod gamestate;
 mod handler;
+mod http_policy;
+mod rate_limit;
 mod repo;
+mod session;
 mod ws;
 
 #[derive(Parser)]
@@ -27,6 +58,39 @@ struct Opts {
     load_balancer_addr: String,
     #[clap(short = 's', long = "servername")]
     server_addr: String,
+    /// Comma separated words that are censored in chat, e.g. "darn,heck"
+    #[clap(long = "chatfilter", default_value = "")]
+    chat_filter: String,
+    /// JSON file with the keys tokens are signed and verified with, a random key is used without it
+    #[clap(long = "jwtkeys")]
+    jwt_keys_file: Option<String>,
+    /// SQLite file with the registered accounts, accounts are kept in memory without it
+    #[clap(long = "accountsdb")]
+    accounts_db: Option<String>,
+    /// Comma separated origins allowed to call the server from a browser, e.g. "https://uno.example.com", * for any
+    #[clap(long = "corsorigins", default_value = "*")]
+    cors_origins: String,
+    /// Comma separated methods allowed in cross-origin requests, * for any
+    #[clap(long = "corsmethods", default_value = "*")]
+    cors_methods: String,
+    /// Allow cross-origin requests with credentials, needs a list of origins
+    #[clap(long = "corscredentials")]
+    cors_credentials: bool,
+    /// Largest JSON request body accepted, in bytes
+    #[clap(long = "maxbodysize", default_value = "16384")]
+    max_body_size: usize,
+    /// Comma separated quotas overriding the default ones, e.g. "ip=600/60,POST /game=10/60"
+    #[clap(long = "ratelimits", default_value = "")]
+    rate_limits: String,
+    /// Take the client IP address from the Forwarded headers, only behind a proxy that sets them. Always on Heroku
+    #[clap(long = "trustforwarded")]
+    trust_forwarded: bool,
+    /// Accept tokens in WebSocket URLs (/ws/token/{token}), which old frontends use
+    #[clap(long = "legacywstoken")]
+    legacy_ws_token: bool,
+    /// Key of the server operator for the /admin endpoints, at least 32 characters. They are disabled without it
+    #[clap(long = "adminkey")]
+    admin_key: Option<String>,
 }
 
 #[actix_web::main]
@@ -37,8 +101,66 @@ async fn main() -> anyhow::Result<()> {
         Err(_) => opts.port,
     };
 
-    let game_repo = web::Data::new(Mutex::new(InMemoryGameRepo::new()));
-    let auth_service = web::Data::new(AuthService::new());
+    let chat_filter = match env::var("CHAT_FILTER") {
+        Ok(words) => words,
+        Err(_) => opts.chat_filter,
+    };
+
+    // the content of the key file itself, so that no secret has to be stored on disk
+    let jwt_keys = match (env::var("JWT_KEYS"), opts.jwt_keys_file) {
+        (Ok(json), _) => KeyRing::from_json(&json)?,
+        (Err(_), Some(path)) => KeyRing::from_json(&fs::read_to_string(path)?)?,
+        (Err(_), None) => {
+            println!("No JWT keys given, tokens will not survive a restart");
+            KeyRing::generate()
+        }
+    };
+    let auth_service = web::Data::new(AuthService::new(jwt_keys));
+    let game_repo = web::Data::new(InMemoryGameRepo::new(
+        WordFilter::from_list(&chat_filter),
+        auth_service.shared_keys(),
+    ));
+    let accounts = match env::var("ACCOUNTS_DB").ok().or(opts.accounts_db) {
+        Some(path) => AccountRepo::open(&path)?,
+        None => {
+            println!("No accounts database given, accounts will not survive a restart");
+            AccountRepo::in_memory()?
+        }
+    };
+    let accounts = web::Data::new(accounts);
+    let matchmaking = web::Data::new(MatchmakingService::new());
+    let ws_tickets = web::Data::new(WsTicketService::new());
+    let legacy_ws_token = opts.legacy_ws_token || env::var("LEGACY_WS_TOKEN").is_ok();
+
+    let http_policy = HttpPolicy::new(
+        &env::var("CORS_ORIGINS").unwrap_or(opts.cors_origins),
+        &env::var("CORS_METHODS").unwrap_or(opts.cors_methods),
+        opts.cors_credentials || env::var("CORS_CREDENTIALS").is_ok(),
+        match env::var("MAX_BODY_SIZE") {
+            Ok(size) => size.parse()?,
+            Err(_) => opts.max_body_size,
+        },
+    )?;
+
+    let rate_limits = RateLimits::default()
+        .with_overrides(&env::var("RATE_LIMITS").unwrap_or(opts.rate_limits))?;
+    // Heroku routes every request through its proxy
+    let trust_forwarded =
+        opts.trust_forwarded || env::var("TRUST_FORWARDED").is_ok() || env::var("DYNO").is_ok();
+    let rate_limiter = Arc::new(
+        RateLimiter::new(rate_limits, trust_forwarded).with_identities({
+            let auth_service = auth_service.clone();
+            move |token| auth_service.identify(token)
+        }),
+    );
+
+    let admin = match env::var("ADMIN_KEY").ok().or(opts.admin_key) {
+        Some(key) => Some(web::Data::new(AdminService::new(&key)?)),
+        None => {
+            println!("No admin key given, the admin API is disabled");
+            None
+        }
+    };
 
     let lb_connector = LoadBalancerConnector::new(opts.load_balancer_addr, opts.server_addr);
     if lb_connector.connect().await.is_err() {}
@@ -47,23 +169,82 @@ async fn main() -> anyhow::Result<()> {
     println!("Starting server on port {}", port);
 
     HttpServer::new(move || {
-        let cors = Cors::default()
-            .allow_any_header()
-            .allow_any_origin()
-            .allow_any_method();
-
-        App::new()
-            .wrap(cors)
+        let app = App::new()
+            .wrap(RateLimit::new(Arc::clone(&rate_limiter)))
+            .wrap(http_policy.cors())
+            .wrap(security_headers())
+            .app_data(http_policy.json_config())
             .app_data(game_repo.clone())
             .app_data(auth_service.clone())
             .app_data(lb_connector.clone())
+            .app_data(matchmaking.clone())
+            .app_data(ws_tickets.clone())
+            .app_data(accounts.clone())
             .service(create_game)
             .service(start_game)
             .service(draw_card)
             .service(join_game)
+            .service(leave_game)
+            .service(kick_player)
+            .service(add_ai)
+            .service(remove_ai)
+            .service(transfer_author)
             .service(play_card)
+            .service(forfeit)
+            .service(propose_rematch)
+            .service(confirm_rematch)
+            .service(return_to_lobby)
+            .service(hints)
+            .service(add_entropy)
+            .service(get_fairness)
+            .service(get_results)
+            .service(verify_result)
+            .service(
+                web::resource("/fairness/verify")
+                    .app_data(http_policy.json_config_up_to(MAX_PROOF_SIZE))
+                    .route(web::post().to(verify_fairness)),
+            )
+            .service(get_game)
+            .service(get_players)
+            .service(list_games)
+            .service(join_queue)
+            .service(poll_queue)
+            .service(leave_queue)
+            .service(chat)
+            .service(mute)
             .service(ws_connect)
-            .service(lb_reconnect)
+            .service(ws_spectate)
+            .service(ws_ticket)
+            .service(create_spectator)
+            .service(spectator_settings)
+            .service(public_keys)
+            .service(refresh_token)
+            .service(register)
+            .service(login)
+            .service(get_account)
+            .service(lb_reconnect);
+
+        let app = match legacy_ws_token {
+            true => app.service(ws_connect_legacy).service(ws_spectate_legacy),
+            false => app,
+        };
+
+        let app = match &admin {
+            Some(admin) => app
+                .app_data(admin.clone())
+                .service(admin_list_games)
+                .service(admin_get_game)
+                .service(admin_finish_game)
+                .service(admin_delete_game)
+                .service(admin_kick_player)
+                .service(admin_notice),
+            None => app,
+        };
+
+        #[cfg(feature = "dev-admin")]
+        let app = app.service(handler::create_scenario::create_scenario);
+
+        app
     })
     .bind(format!("0.0.0.0:{}", port))?
     .run()
@@ -0,0 +1,385 @@
+use crate::handler::util::response::ErrorResponse;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::StatusCode;
+use actix_web::HttpResponse;
+use anyhow::bail;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::future::{ready, Future, Ready};
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+#[path = "tests/rate_limit_test.rs"]
+mod tests;
+
+/// How often the windows that ended are forgotten.
+static PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How many requests a client can make in a window of time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quota {
+    requests: u32,
+    window: Duration,
+}
+
+impl Quota {
+    pub fn new(requests: u32, window_secs: u64) -> Self {
+        Self {
+            requests,
+            window: Duration::from_secs(window_secs),
+        }
+    }
+}
+
+/// Quotas of the server. Routes are named by their method and pattern, e.g. `POST /game/{gameID}/playCard`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimits {
+    /// Every request from an IP address counts towards it, whatever the route.
+    per_ip: Quota,
+    /// Requests to a route count towards its quota, separately for every IP address,
+    /// and for every player or account signed in from it.
+    routes: HashMap<String, Quota>,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            per_ip: Quota::new(600, 60),
+            routes: HashMap::from([
+                ("POST /game".into(), Quota::new(10, 60)),
+                ("POST /game/{gameID}/player".into(), Quota::new(20, 60)),
+                ("POST /game/{gameID}/ai".into(), Quota::new(30, 60)),
+                ("POST /game/{gameID}/playCard".into(), Quota::new(30, 10)),
+                ("POST /game/{gameID}/drawnCards".into(), Quota::new(30, 10)),
+                ("POST /game/{gameID}/chat".into(), Quota::new(10, 10)),
+                ("POST /game/{gameID}/entropy".into(), Quota::new(10, 60)),
+                ("POST /fairness/verify".into(), Quota::new(30, 60)),
+                ("POST /matchmaking".into(), Quota::new(10, 60)),
+                ("POST /account".into(), Quota::new(5, 60)),
+                ("POST /account/login".into(), Quota::new(10, 60)),
+            ]),
+        }
+    }
+}
+
+impl RateLimits {
+    /// Overrides the default quotas with a comma separated list like `ip=600/60,POST /game=10/60`,
+    /// which allows 600 requests from every IP address per minute and creating 10 games per minute.
+    pub fn with_overrides(mut self, overrides: &str) -> anyhow::Result<Self> {
+        for entry in overrides
+            .split(',')
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+        {
+            let (route, quota) = match entry.rsplit_once('=') {
+                Some((route, quota)) => (route.trim(), quota.trim()),
+                None => bail!("Rate limit '{}' must look like 'POST /game=10/60'", entry),
+            };
+            let quota = match quota.split_once('/') {
+                Some((requests, secs)) => match (requests.parse(), secs.parse()) {
+                    (Ok(requests), Ok(secs)) if secs > 0 => Quota::new(requests, secs),
+                    _ => bail!("Quota '{}' must be <requests>/<seconds>", quota),
+                },
+                None => bail!("Quota '{}' must be <requests>/<seconds>", quota),
+            };
+
+            match route {
+                "ip" => self.per_ip = quota,
+                _ => match route.split_once(' ') {
+                    Some((method, pattern)) if pattern.starts_with('/') => {
+                        self.routes
+                            .insert(format!("{} {}", method.to_uppercase(), pattern), quota);
+                    }
+                    _ => bail!("Route '{}' must look like 'POST /game'", route),
+                },
+            }
+        }
+
+        Ok(self)
+    }
+}
+
+/// The client is over its quota.
+#[derive(Debug, PartialEq)]
+pub struct RateLimited {
+    retry_after: Duration,
+}
+
+impl Error for RateLimited {}
+
+impl Display for RateLimited {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Too many requests, try again in {} seconds",
+            retry_after_secs(self.retry_after)
+        )
+    }
+}
+
+impl ErrorResponse for RateLimited {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::TOO_MANY_REQUESTS
+    }
+
+    fn error_type(&self) -> Option<&'static str> {
+        Some("RATE_LIMITED")
+    }
+
+    fn error_response(self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .header(
+                "Retry-After",
+                retry_after_secs(self.retry_after).to_string(),
+            )
+            .json(self.error_body())
+    }
+}
+
+fn retry_after_secs(retry_after: Duration) -> u64 {
+    // rounded up, a client retrying after the rounded down time would be rejected again
+    retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0)
+}
+
+struct Window {
+    ends_at: Instant,
+    requests: u32,
+}
+
+struct Windows {
+    windows: HashMap<String, Window>,
+    pruned_at: Option<Instant>,
+}
+
+/// Tells who a valid token belongs to, None for invalid tokens.
+type Identify = Box<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+/// Counts the requests of every client in fixed windows of time.
+pub struct RateLimiter {
+    limits: RateLimits,
+    /// Whether the client IP address is taken from the `Forwarded`/`X-Forwarded-For` headers,
+    /// which only the proxy in front of the server can be trusted to set.
+    trust_forwarded: bool,
+    /// Whether the operator was told that requests come through a proxy that is not trusted.
+    warned_about_proxy: AtomicBool,
+    identify: Option<Identify>,
+    windows: Mutex<Windows>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: RateLimits, trust_forwarded: bool) -> Self {
+        Self {
+            limits,
+            trust_forwarded,
+            warned_about_proxy: AtomicBool::new(false),
+            identify: None,
+            windows: Mutex::new(Windows {
+                windows: HashMap::new(),
+                pruned_at: None,
+            }),
+        }
+    }
+
+    /// Gives players and accounts a quota of their own on every IP address,
+    /// `identify` tells who a token belongs to after verifying it.
+    pub fn with_identities(
+        mut self,
+        identify: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.identify = Some(Box::new(identify));
+        self
+    }
+
+    /// Counts the request of the client, unless the client already used up the quota.
+    pub fn check(&self, key: String, quota: Quota, now: Instant) -> Result<(), RateLimited> {
+        let mut windows = self.lock();
+        match windows.pruned_at {
+            Some(pruned_at) if now < pruned_at + PRUNE_INTERVAL => (),
+            _ => {
+                windows.windows.retain(|_, window| window.ends_at > now);
+                windows.pruned_at = Some(now);
+            }
+        }
+
+        let window = windows.windows.entry(key).or_insert(Window {
+            ends_at: now + quota.window,
+            requests: 0,
+        });
+        if window.ends_at <= now {
+            *window = Window {
+                ends_at: now + quota.window,
+                requests: 0,
+            };
+        }
+
+        if window.requests >= quota.requests {
+            return Err(RateLimited {
+                retry_after: window.ends_at - now,
+            });
+        }
+        window.requests += 1;
+        Ok(())
+    }
+
+    fn check_request(&self, request: &ServiceRequest, now: Instant) -> Result<(), RateLimited> {
+        let ip = self.client_ip(request);
+        self.check(format!("ip {}", ip), self.limits.per_ip, now)?;
+
+        let route = format!(
+            "{} {}",
+            request.method(),
+            request.match_pattern().unwrap_or_default()
+        );
+        match self.limits.routes.get(&route) {
+            None => Ok(()),
+            Some(quota) => {
+                let client = self.client(ip, bearer_token(request).as_deref());
+                self.check(format!("{} {}", route, client), *quota, now)
+            }
+        }
+    }
+
+    /// The IP address, followed by the player or account if the token is valid.
+    /// Anything else in the Authorization header must not get the client a fresh quota.
+    pub fn client(&self, ip: String, token: Option<&str>) -> String {
+        let identity = self
+            .identify
+            .as_ref()
+            .zip(token)
+            .and_then(|(identify, token)| identify(token));
+
+        match identity {
+            Some(identity) => format!("{} {}", ip, identity),
+            None => ip,
+        }
+    }
+
+    fn client_ip(&self, request: &ServiceRequest) -> String {
+        let forwarded = forwarded_ip(request);
+        if forwarded.is_some()
+            && !self.trust_forwarded
+            && !self.warned_about_proxy.swap(true, Ordering::Relaxed)
+        {
+            println!("Requests come through a proxy, start the server with --trustforwarded to rate limit its clients separately");
+        }
+
+        let address = match self.trust_forwarded {
+            true => forwarded.or_else(|| request.peer_addr().map(|address| address.to_string())),
+            false => request.peer_addr().map(|address| address.to_string()),
+        };
+
+        match address {
+            None => "unknown".into(),
+            Some(address) => match address.parse::<SocketAddr>() {
+                Ok(address) => address.ip().to_string(),
+                Err(_) => address,
+            },
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Windows> {
+        // a poisoned map only means that a panic happened while counting, the counts stay valid
+        self.windows.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The address the proxy in front of the server got the request from. Proxies append it to the headers,
+/// so it is the last one, the ones before could have been made up by the client.
+fn forwarded_ip(request: &ServiceRequest) -> Option<String> {
+    let header = |name| request.headers().get(name)?.to_str().ok();
+
+    if let Some(forwarded_for) = header("X-Forwarded-For") {
+        return forwarded_for
+            .rsplit(',')
+            .map(str::trim)
+            .find(|address| !address.is_empty())
+            .map(String::from);
+    }
+
+    header("Forwarded")?
+        .rsplit(',')
+        .flat_map(|element| element.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, _)| name.eq_ignore_ascii_case("for"))
+        .map(|(_, address)| {
+            address
+                .trim_matches('"')
+                .trim_start_matches('[')
+                .split(']')
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        })
+}
+
+fn bearer_token(request: &ServiceRequest) -> Option<String> {
+    let header = request.headers().get("Authorization")?.to_str().ok()?;
+    header.strip_prefix("Bearer ").map(String::from)
+}
+
+/// Middleware answering requests over their quota with `429 Too Many Requests`.
+pub struct RateLimit(Arc<RateLimiter>);
+
+impl RateLimit {
+    pub fn new(limiter: Arc<RateLimiter>) -> Self {
+        Self(limiter)
+    }
+}
+
+impl<S, B> Transform<S> for RateLimit
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type InitError = ();
+    type Transform = RateLimitMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimitMiddleware {
+            service,
+            limiter: Arc::clone(&self.0),
+        }))
+    }
+}
+
+pub struct RateLimitMiddleware<S> {
+    service: S,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S, B> Service for RateLimitMiddleware<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: ServiceRequest) -> Self::Future {
+        match self.limiter.check_request(&request, Instant::now()) {
+            Err(error) => {
+                let response = error.error_response().into_body();
+                Box::pin(ready(Ok(request.into_response(response))))
+            }
+            Ok(()) => Box::pin(self.service.call(request)),
+        }
+    }
+}
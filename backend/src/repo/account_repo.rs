@@ -0,0 +1,177 @@
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::{Mutex, PoisonError};
+
+#[cfg(test)]
+#[path = "../tests/account_repo_test.rs"]
+mod tests;
+
+pub static MIN_PASSWORD_LENGTH: usize = 8;
+pub static MAX_USERNAME_LENGTH: usize = 20;
+
+#[derive(Debug)]
+pub enum AccountError {
+    InvalidUsername(String),
+    WeakPassword,
+    UsernameTaken(String),
+    InvalidCredentials,
+    NoSuchAccount(i64),
+    Storage(String),
+}
+
+impl Error for AccountError {}
+
+impl Display for AccountError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use AccountError::*;
+
+        match self {
+            InvalidUsername(name) => write!(
+                f,
+                "Username '{}' must have 1 to {} letters, digits, '-' or '_'",
+                name, MAX_USERNAME_LENGTH
+            ),
+            WeakPassword => write!(
+                f,
+                "Password must have at least {} characters",
+                MIN_PASSWORD_LENGTH
+            ),
+            UsernameTaken(name) => write!(f, "Username '{}' is already taken", name),
+            InvalidCredentials => write!(f, "Wrong username or password"),
+            NoSuchAccount(id) => write!(f, "Account {} does not exist", id),
+            Storage(message) => write!(f, "Account storage failed: {}", message),
+        }
+    }
+}
+
+impl From<rusqlite::Error> for AccountError {
+    fn from(e: rusqlite::Error) -> Self {
+        AccountError::Storage(e.to_string())
+    }
+}
+
+/// A registered player, recognized across games.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Account {
+    pub id: i64,
+    pub username: String,
+}
+
+/// Registered accounts in a local SQLite database. Passwords are only stored as Argon2id hashes.
+pub struct AccountRepo {
+    connection: Mutex<Connection>,
+}
+
+impl AccountRepo {
+    /// Opens (or creates) the database file.
+    pub fn open(path: &str) -> Result<Self, AccountError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// A database that lives only as long as the process.
+    pub fn in_memory() -> Result<Self, AccountError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, AccountError> {
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS accounts (
+                id INTEGER PRIMARY KEY,
+                username TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                password_hash TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    pub fn register(&self, username: &str, password: &str) -> Result<Account, AccountError> {
+        let valid_username = !username.is_empty()
+            && username.chars().count() <= MAX_USERNAME_LENGTH
+            && username
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+        if !valid_username {
+            return Err(AccountError::InvalidUsername(username.to_string()));
+        }
+        if password.chars().count() < MIN_PASSWORD_LENGTH {
+            return Err(AccountError::WeakPassword);
+        }
+
+        let salt = SaltString::generate(&mut OsRng);
+        let password_hash = Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(|error| AccountError::Storage(error.to_string()))?
+            .to_string();
+
+        let connection = self.lock();
+        let inserted = connection.execute(
+            "INSERT INTO accounts (username, password_hash) VALUES (?1, ?2)",
+            params![username, password_hash],
+        );
+        match inserted {
+            Err(rusqlite::Error::SqliteFailure(error, _))
+                if error.code == rusqlite::ErrorCode::ConstraintViolation =>
+            {
+                Err(AccountError::UsernameTaken(username.to_string()))
+            }
+            Err(error) => Err(error.into()),
+            Ok(_) => Ok(Account {
+                id: connection.last_insert_rowid(),
+                username: username.to_string(),
+            }),
+        }
+    }
+
+    /// Returns the account if the password is right, usernames are case insensitive.
+    pub fn login(&self, username: &str, password: &str) -> Result<Account, AccountError> {
+        let found = self
+            .lock()
+            .query_row(
+                "SELECT id, username, password_hash FROM accounts WHERE username = ?1",
+                params![username],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get::<_, String>(2)?)),
+            )
+            .optional()?;
+
+        let (id, username, password_hash) = found.ok_or(AccountError::InvalidCredentials)?;
+        let password_hash = PasswordHash::new(&password_hash)
+            .map_err(|error| AccountError::Storage(error.to_string()))?;
+        Argon2::default()
+            .verify_password(password.as_bytes(), &password_hash)
+            .map_err(|_| AccountError::InvalidCredentials)?;
+
+        Ok(Account { id, username })
+    }
+
+    pub fn find(&self, id: i64) -> Result<Account, AccountError> {
+        self.lock()
+            .query_row(
+                "SELECT id, username FROM accounts WHERE id = ?1",
+                params![id],
+                |row| {
+                    Ok(Account {
+                        id: row.get(0)?,
+                        username: row.get(1)?,
+                    })
+                },
+            )
+            .optional()?
+            .ok_or(AccountError::NoSuchAccount(id))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Connection> {
+        // a poisoned connection only means that a panic happened during a query, the database itself stays valid
+        self.connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
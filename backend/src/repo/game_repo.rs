@@ -1,9 +1,21 @@
 use std::{
+    collections::HashMap,
     error::Error,
     fmt::{Display, Formatter},
+    sync::{Arc, Mutex, PoisonError, RwLock},
 };
 
-use crate::gamestate::game::Game;
+use crate::handler::service::jwt_keys::KeyRing;
+use crate::session::chat::WordFilter;
+use crate::session::game_session::GameSession;
+use uno_core::gamestate::game::Game;
+
+#[cfg(test)]
+#[path = "../tests/game_repo_test.rs"]
+mod tests;
+
+/// A game session that can be locked independently of every other one in the repo.
+pub type SharedSession = Arc<Mutex<GameSession>>;
 
 #[derive(Debug)]
 pub enum GameRepoError {
@@ -22,24 +34,56 @@ impl Display for GameRepoError {
     }
 }
 
-#[derive(Clone)]
+/// Concurrent id-to-game map. The map itself is only locked for the duration of a lookup or an insert,
+/// handlers then lock the single game they work with, so unrelated games never wait on each other.
 pub struct InMemoryGameRepo {
-    games: Vec<Game>,
+    games: RwLock<HashMap<String, SharedSession>>,
+    chat_filter: Arc<WordFilter>,
+    /// Keys the results of finished games are certified with.
+    keys: Arc<KeyRing>,
 }
 
 impl InMemoryGameRepo {
-    pub fn new() -> Self {
-        Self { games: Vec::new() }
+    pub fn new(chat_filter: WordFilter, keys: Arc<KeyRing>) -> Self {
+        Self {
+            games: RwLock::new(HashMap::new()),
+            chat_filter: Arc::new(chat_filter),
+            keys,
+        }
+    }
+
+    /// Stores the game in a new session and returns that session.
+    pub fn add_game(&self, game: Game) -> SharedSession {
+        let game_id = game.id.clone();
+        let session = Arc::new(Mutex::new(GameSession::new(
+            game,
+            Arc::clone(&self.chat_filter),
+            Arc::clone(&self.keys),
+        )));
+
+        // a poisoned map only means that a panic happened while inserting, the map itself stays valid
+        let mut games = self.games.write().unwrap_or_else(PoisonError::into_inner);
+        games.insert(game_id, Arc::clone(&session));
+
+        session
+    }
+
+    pub fn remove_game(&self, game_id: &str) {
+        let mut games = self.games.write().unwrap_or_else(PoisonError::into_inner);
+        games.remove(game_id);
     }
 
-    pub fn add_game(&mut self, game: Game) {
-        self.games.push(game);
+    /// Snapshot of all the sessions, the map is not locked while the caller goes through them.
+    pub fn games(&self) -> Vec<SharedSession> {
+        let games = self.games.read().unwrap_or_else(PoisonError::into_inner);
+        games.values().map(Arc::clone).collect()
     }
 
-    pub fn get_game_by_id_mut(&mut self, game_id: String) -> Result<&mut Game, GameRepoError> {
-        match self.games.iter_mut().find(|game| game.id == game_id) {
+    pub fn get_game_by_id(&self, game_id: String) -> Result<SharedSession, GameRepoError> {
+        let games = self.games.read().unwrap_or_else(PoisonError::into_inner);
+        match games.get(&game_id) {
             None => Err(GameRepoError::GameNotFound(game_id)),
-            Some(game) => Ok(game),
+            Some(game) => Ok(Arc::clone(game)),
         }
     }
 }
@@ -1 +1,2 @@
+pub mod account_repo;
 pub mod game_repo;
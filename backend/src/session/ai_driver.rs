@@ -0,0 +1,40 @@
+use crate::repo::game_repo::SharedSession;
+use actix_web::rt::{spawn, time::delay_for};
+use uno_core::gamestate::players::ai::decide_sleep_time;
+
+/// Plays the turns of the game's AI players in the background, pausing before each of them like a human would.
+/// The game is only locked while an AI actually plays, so the pauses never block anyone else.
+pub fn drive_ai(session: SharedSession) {
+    {
+        let mut locked = match session.lock() {
+            Err(_) => return,
+            Ok(locked) => locked,
+        };
+
+        if !locked.game().is_ai_turn() || !locked.start_driving_ai() {
+            return;
+        }
+    }
+
+    spawn(async move {
+        loop {
+            delay_for(decide_sleep_time()).await;
+
+            let mut locked = match session.lock() {
+                Err(_) => return,
+                Ok(locked) => locked,
+            };
+
+            if let Err(error) = locked.play_ai_turn() {
+                println!("AI turn in game '{}' failed: {}", locked.game().id, error);
+                locked.stop_driving_ai();
+                return;
+            }
+
+            if !locked.game().is_ai_turn() {
+                locked.stop_driving_ai();
+                return;
+            }
+        }
+    });
+}
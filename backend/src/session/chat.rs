@@ -0,0 +1,190 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uno_core::err::player_exist::PlayerExistError;
+
+#[cfg(test)]
+#[path = "../tests/chat_test.rs"]
+mod tests;
+
+/// How many of the latest messages are kept and sent along with the status.
+pub static HISTORY_SIZE: usize = 50;
+/// Longest chat message in characters.
+pub static MAX_MESSAGE_LENGTH: usize = 300;
+/// Every author can send at most this many messages within `RATE_LIMIT_WINDOW`.
+pub static RATE_LIMIT_MESSAGES: usize = 5;
+pub static RATE_LIMIT_WINDOW: Duration = Duration::from_secs(10);
+
+#[derive(Debug)]
+pub enum ChatError {
+    EmptyMessage,
+    TooLong(usize),
+    RateLimited,
+    Muted(String),
+    NotAuthor(String),
+    PlayerExistError(PlayerExistError),
+}
+
+impl Error for ChatError {}
+
+impl Display for ChatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use ChatError::*;
+
+        match self {
+            EmptyMessage => write!(f, "Chat message cannot be empty"),
+            TooLong(length) => write!(
+                f,
+                "Chat message has {} characters, the limit is {}",
+                length, MAX_MESSAGE_LENGTH
+            ),
+            RateLimited => write!(
+                f,
+                "At most {} messages can be sent in {}s",
+                RATE_LIMIT_MESSAGES,
+                RATE_LIMIT_WINDOW.as_secs()
+            ),
+            Muted(name) => write!(f, "{} was muted by the author", name),
+            NotAuthor(name) => write!(
+                f,
+                "Player {} is not the author, only the author can mute others",
+                name
+            ),
+            PlayerExistError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<PlayerExistError> for ChatError {
+    fn from(e: PlayerExistError) -> Self {
+        ChatError::PlayerExistError(e)
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ChatEntry {
+    pub who: String,
+    pub text: String,
+}
+
+/// Words that are replaced by asterisks in every chat message, configured when the server starts.
+#[derive(Debug, Clone, Default)]
+pub struct WordFilter {
+    words: HashSet<String>,
+}
+
+impl WordFilter {
+    /// Parses a comma separated list of words, e.g. `"darn,heck"`. Case does not matter.
+    pub fn from_list(list: &str) -> Self {
+        Self {
+            words: list
+                .split(',')
+                .map(|word| word.trim().to_lowercase())
+                .filter(|word| !word.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn censor(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut word = String::new();
+
+        for c in text.chars().chain(std::iter::once(' ')) {
+            if c.is_alphanumeric() {
+                word.push(c);
+                continue;
+            }
+            self.push_word(&mut result, &word);
+            word.clear();
+            result.push(c);
+        }
+
+        // the space chained above is not part of the message
+        result.pop();
+        result
+    }
+
+    fn push_word(&self, result: &mut String, word: &str) {
+        if self.words.contains(&word.to_lowercase()) {
+            result.extend(word.chars().map(|_| '*'));
+        } else {
+            result.push_str(word);
+        }
+    }
+}
+
+/// One chat channel of a game with its moderation: length and rate limits, the word filter and muted authors.
+pub struct Chat {
+    history: VecDeque<ChatEntry>,
+    /// When the authors sent their messages within the current rate limit window.
+    recent: HashMap<String, VecDeque<Instant>>,
+    muted: HashSet<String>,
+    filter: Arc<WordFilter>,
+}
+
+impl Chat {
+    pub fn new(filter: Arc<WordFilter>) -> Self {
+        Self {
+            history: VecDeque::new(),
+            recent: HashMap::new(),
+            muted: HashSet::new(),
+            filter,
+        }
+    }
+
+    pub fn history(&self) -> Vec<ChatEntry> {
+        self.history.iter().cloned().collect()
+    }
+
+    pub fn set_muted(&mut self, name: &str, muted: bool) {
+        if muted {
+            self.muted.insert(name.to_string());
+        } else {
+            self.muted.remove(name);
+        }
+    }
+
+    /// Moderates the message and keeps it in the history. Returns the entry that should be sent out.
+    pub fn post(&mut self, who: &str, text: &str) -> Result<ChatEntry, ChatError> {
+        self.post_at(who, text, Instant::now())
+    }
+
+    fn post_at(&mut self, who: &str, text: &str, now: Instant) -> Result<ChatEntry, ChatError> {
+        if self.muted.contains(who) {
+            return Err(ChatError::Muted(who.to_string()));
+        }
+
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(ChatError::EmptyMessage);
+        }
+        let length = text.chars().count();
+        if length > MAX_MESSAGE_LENGTH {
+            return Err(ChatError::TooLong(length));
+        }
+
+        let recent = self.recent.entry(who.to_string()).or_default();
+        while matches!(recent.front(), Some(sent) if now.duration_since(*sent) >= RATE_LIMIT_WINDOW)
+        {
+            recent.pop_front();
+        }
+        if recent.len() >= RATE_LIMIT_MESSAGES {
+            return Err(ChatError::RateLimited);
+        }
+        recent.push_back(now);
+
+        let entry = ChatEntry {
+            who: who.to_string(),
+            text: self.filter.censor(text),
+        };
+        if self.history.len() == HISTORY_SIZE {
+            self.history.pop_front();
+        }
+        self.history.push_back(entry.clone());
+
+        Ok(entry)
+    }
+}
@@ -0,0 +1,324 @@
+use crate::handler::service::jwt_keys::KeyRing;
+use crate::session::chat::{Chat, ChatError, WordFilter};
+use crate::session::idempotency::IdempotencyCache;
+use crate::session::outbox::Outbox;
+use crate::session::results::Results;
+use crate::session::spectators::{SpectateError, Spectators};
+use crate::ws::ws_conn::WSConn;
+use crate::ws::ws_message::WSMsg;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uno_core::err::ai::AiError;
+use uno_core::err::command::CommandError;
+use uno_core::err::player_exist::PlayerExistError;
+use uno_core::err::status::CreateStatusError;
+use uno_core::gamestate::command::Command;
+use uno_core::gamestate::event::Event;
+use uno_core::gamestate::game::Game;
+
+/// A game together with the WebSocket connections of its players.
+/// Commands go through the session, which delivers the resulting events to the players as WS messages.
+pub struct GameSession {
+    game: Game,
+    connections: HashMap<String, WSConn>,
+    /// Every message sent to a human player, kept even while they are disconnected.
+    outboxes: HashMap<String, Outbox>,
+    spectators: Spectators,
+    chat: Chat,
+    /// Whether the game is listed in the lobby browser.
+    public: bool,
+    /// Whether the AI players are already being played for in the background.
+    ai_driven: bool,
+    /// Account ids of the players who play with a registered account.
+    accounts: HashMap<String, i64>,
+    idempotency: IdempotencyCache,
+    results: Results,
+}
+
+impl GameSession {
+    pub fn new(game: Game, chat_filter: Arc<WordFilter>, keys: Arc<KeyRing>) -> Self {
+        Self {
+            results: Results::new(&game, keys),
+            game,
+            connections: HashMap::new(),
+            outboxes: HashMap::new(),
+            spectators: Spectators::new(Arc::clone(&chat_filter)),
+            chat: Chat::new(chat_filter),
+            public: false,
+            ai_driven: false,
+            accounts: HashMap::new(),
+            idempotency: IdempotencyCache::new(),
+        }
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.public
+    }
+
+    pub fn set_public(&mut self, public: bool) {
+        self.public = public;
+    }
+
+    pub fn set_account(&mut self, player_name: &str, account_id: i64) {
+        self.accounts.insert(player_name.to_string(), account_id);
+    }
+
+    /// Account id of the player, None for guests.
+    pub fn account_of(&self, player_name: &str) -> Option<i64> {
+        self.accounts.get(player_name).copied()
+    }
+
+    pub fn idempotency(&self) -> &IdempotencyCache {
+        &self.idempotency
+    }
+
+    pub fn idempotency_mut(&mut self) -> &mut IdempotencyCache {
+        &mut self.idempotency
+    }
+
+    pub fn results(&self) -> &Results {
+        &self.results
+    }
+
+    pub fn spectators(&self) -> &Spectators {
+        &self.spectators
+    }
+
+    pub fn spectators_mut(&mut self) -> &mut Spectators {
+        &mut self.spectators
+    }
+
+    /// Applies the command to the game and sends the resulting messages to the players.
+    pub fn apply(&mut self, command: Command) -> Result<(), CommandError> {
+        self.apply_with_events(command).map(|_| ())
+    }
+
+    /// Like `apply`, but returns the events for the caller to act on those the session does not handle itself.
+    pub fn apply_with_events(&mut self, command: Command) -> Result<Vec<Event>, CommandError> {
+        let events = self.game.apply(command)?;
+        self.results.record(&self.game, &events, &self.accounts);
+        self.dispatch(&events)?;
+
+        for event in &events {
+            if let Event::PlayerLeft { name, .. } = event {
+                self.disconnect(name);
+                self.accounts.remove(name);
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Plays the turn of the AI player at turn, if there is one, and sends the resulting messages to the players.
+    pub fn play_ai_turn(&mut self) -> Result<(), AiError> {
+        let events = self.game.play_ai_turn()?;
+        self.results.record(&self.game, &events, &self.accounts);
+        self.dispatch(&events)?;
+
+        Ok(())
+    }
+
+    /// Sends a chat message of the player to everyone in the game, spectators included.
+    pub fn chat(&mut self, player_name: &str, text: String) -> Result<(), ChatError> {
+        if self.game.find_player(player_name.to_string()).is_none() {
+            return Err(PlayerExistError::NoSuchPlayer(player_name.to_string()).into());
+        }
+
+        let entry = self.chat.post(player_name, &text)?;
+
+        let msg = WSMsg::chat(entry.who, entry.text);
+        self.message_all(msg.clone());
+        self.spectators.broadcast(msg);
+
+        Ok(())
+    }
+
+    /// Gives a new spectator the name, unless a player or another spectator has it.
+    pub fn add_spectator(&mut self, spectator_name: &str) -> Result<(), SpectateError> {
+        if self.game.find_player(spectator_name.to_string()).is_some() {
+            return Err(SpectateError::NameTaken(spectator_name.to_string()));
+        }
+        self.spectators.add_name(spectator_name)
+    }
+
+    /// Sends a chat message of the spectator to the other spectators only.
+    pub fn spectator_chat(&mut self, spectator_name: &str, text: String) -> Result<(), ChatError> {
+        self.spectators.chat(spectator_name, &text)
+    }
+
+    /// Mutes or unmutes a player, or a spectator in the spectator chat. Only the author of the game can do that.
+    pub fn set_muted(
+        &mut self,
+        author_name: &str,
+        name: &str,
+        muted: bool,
+        spectator: bool,
+    ) -> Result<(), ChatError> {
+        match self.game.find_author() {
+            Some(author) if author.name() == author_name => (),
+            _ => return Err(ChatError::NotAuthor(author_name.to_string())),
+        }
+
+        if spectator {
+            self.spectators.set_muted(name, muted);
+            return Ok(());
+        }
+
+        if self.game.find_player(name.to_string()).is_none() {
+            return Err(PlayerExistError::NoSuchPlayer(name.to_string()).into());
+        }
+        self.chat.set_muted(name, muted);
+
+        Ok(())
+    }
+
+    /// Status of the game for the player, with the player chat history.
+    pub fn status(&self, player_name: &str) -> Result<WSMsg, CreateStatusError> {
+        Ok(WSMsg::status(&self.game, player_name.to_string())?.with_chat(self.chat.history()))
+    }
+
+    /// Status of the game for spectators, with both chat histories.
+    pub fn spectator_status(&self) -> Result<WSMsg, CreateStatusError> {
+        Ok(WSMsg::public_status(&self.game)?
+            .with_chat(self.chat.history())
+            .with_spectator_chat(self.spectators.chat_history()))
+    }
+
+    /// Registers the connection of a player, closing their previous one.
+    pub fn connect(&mut self, player_name: String, connection: WSConn) {
+        if let Some(previous) = self.connections.insert(player_name, connection) {
+            previous.close();
+        }
+    }
+
+    /// Forgets the connection once it closed, unless the player connected again in the meantime.
+    /// Their messages are kept for a reconnect.
+    pub fn disconnected(&mut self, player_name: &str, connection: &WSConn) {
+        if self.connections.get(player_name) == Some(connection) {
+            self.connections.remove(player_name);
+        }
+    }
+
+    /// Names of the players connected over a WebSocket.
+    pub fn connected_players(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.connections.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Sends a notice of the server operator to every player and spectator.
+    pub fn notify(&mut self, text: &str) {
+        self.message_all(WSMsg::notice(text.to_string()));
+        self.spectators
+            .broadcast_now(WSMsg::notice(text.to_string()));
+    }
+
+    /// Closes every connection to the game, which is about to be deleted.
+    pub fn disconnect_all(&mut self) {
+        for player_name in self.connections.keys().cloned().collect::<Vec<String>>() {
+            self.disconnect(&player_name);
+        }
+        self.spectators.disconnect_all();
+    }
+
+    /// Closes the connection of a player who is no longer in the game and forgets their messages.
+    pub fn disconnect(&mut self, player_name: &str) {
+        if let Some(connection) = self.connections.remove(player_name) {
+            connection.close();
+        }
+        self.outboxes.remove(player_name);
+    }
+
+    /// Sends the message to every human player, see `message`.
+    pub fn message_all(&mut self, msg: WSMsg) {
+        for player_name in self.human_names() {
+            self.message(&player_name, msg.clone());
+        }
+    }
+
+    /// Numbers the message, keeps it for a possible reconnect and sends it if the player is connected.
+    pub fn message(&mut self, player_name: &str, msg: WSMsg) {
+        let msg = self
+            .outboxes
+            .entry(player_name.to_string())
+            .or_insert_with(Outbox::new)
+            .record(msg);
+
+        if let Some(connection) = self.connections.get(player_name) {
+            connection.send(msg)
+        }
+    }
+
+    /// Returns what a (re)connecting player has to be sent: the messages they missed after the one numbered `since`,
+    /// or a fresh status if those are no longer kept or nothing was received yet.
+    pub fn catch_up(
+        &mut self,
+        player_name: &str,
+        since: Option<u64>,
+    ) -> Result<Vec<WSMsg>, CreateStatusError> {
+        let missed = since.and_then(|since| self.outboxes.get(player_name)?.since(since));
+        if let Some(missed) = missed {
+            return Ok(missed);
+        }
+
+        let status = self.status(player_name)?;
+        Ok(vec![self
+            .outboxes
+            .entry(player_name.to_string())
+            .or_insert_with(Outbox::new)
+            .record(status)])
+    }
+
+    /// Sends already numbered messages again, e.g. the ones missed while disconnected.
+    pub fn replay(&self, player_name: &str, msgs: Vec<WSMsg>) {
+        if let Some(connection) = self.connections.get(player_name) {
+            msgs.into_iter().for_each(|msg| connection.send(msg));
+        }
+    }
+
+    /// Returns false if the AI players are already being played for.
+    pub(super) fn start_driving_ai(&mut self) -> bool {
+        !std::mem::replace(&mut self.ai_driven, true)
+    }
+
+    pub(super) fn stop_driving_ai(&mut self) {
+        self.ai_driven = false;
+    }
+
+    fn human_names(&self) -> Vec<String> {
+        self.game
+            .players()
+            .iter()
+            .filter(|player| player.is_human())
+            .map(|player| player.name())
+            .collect()
+    }
+
+    fn dispatch(&mut self, events: &[Event]) -> Result<(), CreateStatusError> {
+        // the last human left the lobby, there is nobody to tell and the game is about to be deleted
+        if self.game.find_author().is_none() {
+            return Ok(());
+        }
+
+        let recipients = self.human_names();
+        for event in events {
+            for player_name in recipients.iter() {
+                if let Some(msg) = WSMsg::for_event(&self.game, event, player_name)? {
+                    self.message(player_name, msg.with_chat(self.chat.history()));
+                }
+            }
+            if let Some(msg) = WSMsg::for_spectator(&self.game, event)? {
+                self.spectators.broadcast(
+                    msg.with_chat(self.chat.history())
+                        .with_spectator_chat(self.spectators.chat_history()),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
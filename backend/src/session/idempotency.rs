@@ -0,0 +1,44 @@
+use crate::handler::util::response::ErrBody;
+use actix_web::http::StatusCode;
+use std::collections::VecDeque;
+
+/// How many results every game keeps, the oldest are forgotten first.
+pub static KEPT_RESULTS: usize = 256;
+
+/// What a request made with an `Idempotency-Key` resulted in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotentResult {
+    /// Route pattern the request was made to, the key cannot be reused for another route.
+    pub route: String,
+    /// SHA-256 of what the request asked for, the key cannot be reused for another request either.
+    pub request_hash: [u8; 32],
+    pub outcome: Result<(), (StatusCode, ErrBody)>,
+}
+
+/// Results of the latest requests the players made with an `Idempotency-Key`,
+/// so that a retried request gets the original result instead of being applied twice.
+#[derive(Default)]
+pub struct IdempotencyCache {
+    results: VecDeque<(String, String, IdempotentResult)>,
+}
+
+impl IdempotencyCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, player_name: &str, key: &str) -> Option<&IdempotentResult> {
+        self.results
+            .iter()
+            .find(|(name, k, _)| name == player_name && k == key)
+            .map(|(_, _, result)| result)
+    }
+
+    pub fn insert(&mut self, player_name: &str, key: String, result: IdempotentResult) {
+        if self.results.len() >= KEPT_RESULTS {
+            self.results.pop_front();
+        }
+        self.results
+            .push_back((player_name.to_string(), key, result));
+    }
+}
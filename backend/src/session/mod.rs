@@ -0,0 +1,7 @@
+pub mod ai_driver;
+pub mod chat;
+pub mod game_session;
+pub mod idempotency;
+pub mod outbox;
+pub mod results;
+pub mod spectators;
@@ -0,0 +1,60 @@
+use crate::ws::ws_message::WSMsg;
+use std::collections::VecDeque;
+
+#[cfg(test)]
+#[path = "../tests/outbox_test.rs"]
+mod tests;
+
+/// How many of the latest messages of every player are kept for replaying them after a reconnect.
+pub static BACKLOG_SIZE: usize = 128;
+
+/// Messages sent to a single player, numbered so that a reconnecting client can ask for the ones it missed.
+pub struct Outbox {
+    next_seq: u64,
+    backlog: VecDeque<(u64, WSMsg)>,
+}
+
+impl Outbox {
+    pub fn new() -> Self {
+        Self {
+            next_seq: 1,
+            backlog: VecDeque::with_capacity(BACKLOG_SIZE),
+        }
+    }
+
+    /// Gives the message the next sequence number and keeps it, forgetting the oldest message if the backlog is full.
+    pub fn record(&mut self, msg: WSMsg) -> WSMsg {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+
+        let msg = msg.with_seq(seq);
+        if self.backlog.len() == BACKLOG_SIZE {
+            self.backlog.pop_front();
+        }
+        self.backlog.push_back((seq, msg.clone()));
+
+        msg
+    }
+
+    /// Returns the messages sent after the one numbered `since`, in order.
+    /// Returns None if some of them are no longer kept, or if no such message was sent yet.
+    pub fn since(&self, since: u64) -> Option<Vec<WSMsg>> {
+        let last_seq = self.next_seq - 1;
+        if since > last_seq {
+            return None;
+        }
+
+        let oldest_kept = self.backlog.front().map_or(self.next_seq, |(seq, _)| *seq);
+        if since + 1 < oldest_kept {
+            return None;
+        }
+
+        Some(
+            self.backlog
+                .iter()
+                .filter(|(seq, _)| *seq > since)
+                .map(|(_, msg)| msg.clone())
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,189 @@
+use crate::handler::results::issue_certificate;
+use crate::handler::service::jwt_keys::KeyRing;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use uno_core::gamestate::event::Event;
+use uno_core::gamestate::game::{Game, GameStatus};
+use uno_core::gamestate::{CARDS_DEALT_TO_PLAYERS, MAX_PLAYERS, PENALTY_CARDS};
+
+#[cfg(test)]
+#[path = "../tests/results_test.rs"]
+mod tests;
+
+/// How many results of finished games every session keeps, the oldest are forgotten first.
+pub static KEPT_RESULTS: usize = 64;
+
+/// Outcome of one finished game, the document result certificates are signed over.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GameResult {
+    pub game_id: String,
+    /// Number of the game within the session, rematches count on from the first game.
+    pub round: u32,
+    /// Players in their seating order.
+    pub players: Vec<ResultPlayer>,
+    /// Names of the players from the winner to the last, those who gave up come last.
+    pub finishing_order: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forfeited: Vec<String>,
+    /// Unix timestamps in seconds.
+    pub started_at: u64,
+    pub finished_at: u64,
+    pub rules: Rules,
+    /// Whether the server operator ended the game before it was played out, nobody won it then.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub force_finished: bool,
+    /// Hex encoded SHA-256 of the events of the game, one JSON object per line.
+    /// None if the game was already running when the server started to track it, e.g. a matched game.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_log_hash: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultPlayer {
+    pub name: String,
+    pub is_ai: bool,
+    /// Registered account of the player, guests have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Rules {
+    pub starting_cards: usize,
+    pub penalty_cards: usize,
+    pub max_players: usize,
+    /// Commitment to the shuffle seed of a fair game, see `GET /game/{gameID}/fairness`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed_commitment: Option<String>,
+}
+
+/// A result with its certificate, signed once when the game finished,
+/// so that every request gets the same document even after the signing key changes.
+#[derive(Debug, Clone)]
+pub struct CertifiedResult {
+    pub result: GameResult,
+    /// None if the result could not be signed.
+    pub certificate: Option<String>,
+}
+
+/// Results of the games played in a session, recorded from the events as they happen.
+pub struct Results {
+    keys: Arc<KeyRing>,
+    started_at: Option<u64>,
+    /// Hash of the events since the game started, None while no game runs or if its start was missed.
+    event_log: Option<Sha256>,
+    rounds: u32,
+    finished: VecDeque<CertifiedResult>,
+}
+
+impl Results {
+    pub fn new(game: &Game, keys: Arc<KeyRing>) -> Self {
+        Self {
+            keys,
+            // matched games are started before they get a session
+            started_at: match game.status() {
+                GameStatus::Running => Some(unix_now()),
+                _ => None,
+            },
+            event_log: None,
+            rounds: 0,
+            finished: VecDeque::new(),
+        }
+    }
+
+    /// The results of the finished games, the latest last.
+    pub fn finished(&self) -> &VecDeque<CertifiedResult> {
+        &self.finished
+    }
+
+    pub fn record(&mut self, game: &Game, events: &[Event], accounts: &HashMap<String, i64>) {
+        for event in events {
+            if *event == Event::GameStarted {
+                self.started_at = Some(unix_now());
+                self.event_log = Some(Sha256::new());
+            }
+
+            if let Some(event_log) = self.event_log.as_mut() {
+                // serializing plain data cannot fail
+                event_log.update(serde_json::to_vec(event).unwrap_or_default());
+                event_log.update(b"\n");
+            }
+
+            if *event == Event::GameFinished {
+                self.finish(game, accounts);
+            }
+        }
+    }
+
+    fn finish(&mut self, game: &Game, accounts: &HashMap<String, i64>) {
+        let finished_at = unix_now();
+        let event_log_hash = self
+            .event_log
+            .take()
+            .map(|event_log| to_hex(&event_log.finalize()));
+        self.rounds += 1;
+
+        let result = GameResult {
+            game_id: game.id.clone(),
+            round: self.rounds,
+            players: game
+                .players()
+                .iter()
+                .map(|player| ResultPlayer {
+                    name: player.name(),
+                    is_ai: !player.is_human(),
+                    account_id: accounts.get(&player.name()).copied(),
+                })
+                .collect(),
+            finishing_order: game
+                .get_finished_players()
+                .iter()
+                .map(|player| player.name())
+                .collect(),
+            forfeited: game.forfeited_players().clone(),
+            started_at: self.started_at.take().unwrap_or(finished_at),
+            finished_at,
+            rules: Rules {
+                starting_cards: CARDS_DEALT_TO_PLAYERS,
+                penalty_cards: PENALTY_CARDS,
+                max_players: MAX_PLAYERS,
+                seed_commitment: game.fairness().map(|fairness| fairness.commitment()),
+            },
+            force_finished: game.was_force_finished(),
+            event_log_hash,
+        };
+
+        let certificate = match issue_certificate(&self.keys, &result) {
+            Ok(certificate) => Some(certificate),
+            Err(error) => {
+                eprintln!("Result of game {} could not be signed: {}", game.id, error);
+                None
+            }
+        };
+
+        if self.finished.len() >= KEPT_RESULTS {
+            self.finished.pop_front();
+        }
+        self.finished.push_back(CertifiedResult {
+            result,
+            certificate,
+        });
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or_default()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
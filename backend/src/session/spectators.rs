@@ -0,0 +1,190 @@
+use crate::session::chat::{Chat, ChatEntry, ChatError, WordFilter};
+use crate::ws::ws_conn::WSConn;
+use crate::ws::ws_message::WSMsg;
+use actix_web::rt::{spawn, time::delay_for};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(test)]
+#[path = "../tests/spectators_test.rs"]
+mod tests;
+
+/// The longest delay an author can put between the game and what spectators see.
+pub static MAX_SPECTATOR_DELAY: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug)]
+pub enum SpectateError {
+    Disabled,
+    NotAuthor(String),
+    CannotPlay,
+    DelayTooLong(Duration),
+    NameTaken(String),
+}
+
+impl Error for SpectateError {}
+
+impl Display for SpectateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use SpectateError::*;
+
+        match self {
+            Disabled => write!(f, "The author does not allow spectating this game"),
+            CannotPlay => write!(f, "Spectators can only watch the game"),
+            NotAuthor(name) => write!(
+                f,
+                "Player {} is not the author, only the author can change spectating",
+                name
+            ),
+            DelayTooLong(delay) => write!(
+                f,
+                "Delay of {}s is too long, the limit is {}s",
+                delay.as_secs(),
+                MAX_SPECTATOR_DELAY.as_secs()
+            ),
+            NameTaken(name) => write!(f, "Name {} is already taken in this game", name),
+        }
+    }
+}
+
+/// People watching a game. They get only public messages, optionally delayed to prevent ghosting,
+/// and have their own chat that players never see.
+pub struct Spectators {
+    enabled: bool,
+    delay: Duration,
+    /// Names of everyone who got a spectator token, every name is given out only once.
+    names: HashSet<String>,
+    connections: HashMap<String, WSConn>,
+    chat: Chat,
+}
+
+impl Spectators {
+    pub fn new(chat_filter: Arc<WordFilter>) -> Self {
+        Self {
+            enabled: true,
+            delay: Duration::from_secs(0),
+            names: HashSet::new(),
+            connections: HashMap::new(),
+            chat: Chat::new(chat_filter),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Disabling spectating disconnects everyone who is watching.
+    pub fn configure(&mut self, enabled: bool, delay: Duration) -> Result<(), SpectateError> {
+        if delay > MAX_SPECTATOR_DELAY {
+            return Err(SpectateError::DelayTooLong(delay));
+        }
+
+        self.enabled = enabled;
+        self.delay = delay;
+        if !enabled {
+            self.connections
+                .drain()
+                .for_each(|(_, connection)| connection.close());
+        }
+
+        Ok(())
+    }
+
+    /// Reserves the name of a new spectator, so that nobody else can connect or chat under it.
+    pub fn add_name(&mut self, spectator_name: &str) -> Result<(), SpectateError> {
+        if !self.names.insert(spectator_name.to_string()) {
+            return Err(SpectateError::NameTaken(spectator_name.to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn has_name(&self, name: &str) -> bool {
+        self.names.contains(name)
+    }
+
+    /// Registers the connection of a spectator, closing their previous one.
+    pub fn connect(
+        &mut self,
+        spectator_name: String,
+        connection: WSConn,
+    ) -> Result<(), SpectateError> {
+        if !self.enabled {
+            return Err(SpectateError::Disabled);
+        }
+
+        if let Some(previous) = self.connections.insert(spectator_name, connection) {
+            previous.close();
+        }
+        Ok(())
+    }
+
+    /// Forgets the connection once it closed, unless the spectator connected again in the meantime.
+    pub fn disconnected(&mut self, spectator_name: &str, connection: &WSConn) {
+        if self.connections.get(spectator_name) == Some(connection) {
+            self.connections.remove(spectator_name);
+        }
+    }
+
+    /// Sends a message of the spectator to the other spectators. It is not delayed, since it tells nothing about the game.
+    pub fn chat(&mut self, spectator_name: &str, text: &str) -> Result<(), ChatError> {
+        let entry = self.chat.post(spectator_name, text)?;
+
+        let msg = WSMsg::spectator_chat(entry.who, entry.text);
+        self.connections
+            .values()
+            .for_each(|connection| connection.send(msg.clone()));
+
+        Ok(())
+    }
+
+    pub fn chat_history(&self) -> Vec<ChatEntry> {
+        self.chat.history()
+    }
+
+    pub fn set_muted(&mut self, spectator_name: &str, muted: bool) {
+        self.chat.set_muted(spectator_name, muted)
+    }
+
+    pub fn message(&self, spectator_name: &str, msg: WSMsg) {
+        if let Some(connection) = self.connections.get(spectator_name) {
+            self.send(connection.clone(), msg);
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.connections.len()
+    }
+
+    /// Sends the message to every spectator right away, for messages that tell nothing about the game.
+    pub fn broadcast_now(&self, msg: WSMsg) {
+        for connection in self.connections.values() {
+            connection.send(msg.clone());
+        }
+    }
+
+    pub fn disconnect_all(&mut self) {
+        self.connections
+            .drain()
+            .for_each(|(_, connection)| connection.close());
+    }
+
+    pub fn broadcast(&self, msg: WSMsg) {
+        for connection in self.connections.values() {
+            self.send(connection.clone(), msg.clone());
+        }
+    }
+
+    fn send(&self, connection: WSConn, msg: WSMsg) {
+        if self.delay.as_secs() == 0 {
+            return connection.send(msg);
+        }
+
+        let delay = self.delay;
+        spawn(async move {
+            delay_for(delay).await;
+            connection.send(msg);
+        });
+    }
+}
@@ -0,0 +1,44 @@
+use crate::repo::account_repo::{AccountError, AccountRepo};
+
+#[test]
+fn test_register_and_login() {
+    let accounts = AccountRepo::in_memory().unwrap();
+
+    let andy = accounts.register("Andy", "correct horse").unwrap();
+    assert_eq!(andy.username, "Andy");
+    assert_eq!(accounts.login("Andy", "correct horse").unwrap(), andy);
+    assert_eq!(accounts.login("andy", "correct horse").unwrap(), andy);
+    assert_eq!(accounts.find(andy.id).unwrap(), andy);
+
+    assert!(matches!(
+        accounts.login("Andy", "wrong horse"),
+        Err(AccountError::InvalidCredentials)
+    ));
+    assert!(matches!(
+        accounts.login("Bob", "correct horse"),
+        Err(AccountError::InvalidCredentials)
+    ));
+}
+
+#[test]
+fn test_register_errors() {
+    let accounts = AccountRepo::in_memory().unwrap();
+    accounts.register("Andy", "correct horse").unwrap();
+
+    assert!(matches!(
+        accounts.register("ANDY", "battery staple"),
+        Err(AccountError::UsernameTaken(_))
+    ));
+    assert!(matches!(
+        accounts.register("Bob", "short"),
+        Err(AccountError::WeakPassword)
+    ));
+    assert!(matches!(
+        accounts.register("Bob Smith", "correct horse"),
+        Err(AccountError::InvalidUsername(_))
+    ));
+    assert!(matches!(
+        accounts.register("", "correct horse"),
+        Err(AccountError::InvalidUsername(_))
+    ));
+}
@@ -0,0 +1,30 @@
+use crate::handler::service::admin::{AdminError, AdminService, ADMIN_KEY_HEADER};
+use actix_web::test::TestRequest;
+
+static KEY: &str = "0123456789abcdef0123456789abcdef";
+
+#[test]
+fn test_check() {
+    let admin = AdminService::new(KEY).unwrap();
+
+    let request = TestRequest::default()
+        .header(ADMIN_KEY_HEADER, KEY)
+        .to_http_request();
+    assert_eq!(admin.check(&request), Ok(()));
+
+    let request = TestRequest::default()
+        .header(ADMIN_KEY_HEADER, &KEY[1..])
+        .to_http_request();
+    assert_eq!(admin.check(&request), Err(AdminError::InvalidKey));
+
+    // a player's token is no admin key
+    let request = TestRequest::default()
+        .header("Authorization", format!("Bearer {}", KEY))
+        .to_http_request();
+    assert_eq!(admin.check(&request), Err(AdminError::MissingKey));
+}
+
+#[test]
+fn test_short_key() {
+    assert!(AdminService::new("hunter2").is_err());
+}
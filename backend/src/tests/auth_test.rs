@@ -0,0 +1,148 @@
+use crate::handler::service::auth::{AuthService, JwtData, ACCESS_TOKEN_MINUTES};
+use crate::handler::service::jwt_keys::KeyRing;
+use crate::repo::account_repo::Account;
+use actix_web::http::StatusCode;
+use actix_web::test::TestRequest;
+
+fn extract(auth_service: &AuthService, token: &str) -> Result<(String, String), StatusCode> {
+    auth_service
+        .extract_data_from_token(token.to_string())
+        .map(|(game_id, name)| (game_id.into_inner(), name.into_inner()))
+        .map_err(|response| response.status())
+}
+
+#[test]
+fn test_access_tokens_are_short_lived() {
+    let auth_service = AuthService::new(KeyRing::generate());
+    let tokens = auth_service.issue_tokens("Andy", "game");
+
+    let claims = auth_service
+        .keys()
+        .verify::<JwtData>(&tokens.token)
+        .unwrap();
+    assert!(claims.jwt_id.is_some());
+    let lifetime = claims.expires_at.unwrap() - claims.issued_at.unwrap();
+    assert_eq!(lifetime.as_secs(), ACCESS_TOKEN_MINUTES * 60);
+
+    assert_eq!(
+        extract(&auth_service, &tokens.token),
+        Ok(("game".into(), "Andy".into()))
+    );
+    assert_eq!(
+        extract(&auth_service, &tokens.refresh_token),
+        Err(StatusCode::UNAUTHORIZED)
+    );
+}
+
+#[test]
+fn test_refresh() {
+    let auth_service = AuthService::new(KeyRing::generate());
+    let tokens = auth_service.issue_tokens("Andy", "game");
+
+    assert_eq!(
+        auth_service.refresh(&tokens.token).unwrap_err().status(),
+        StatusCode::BAD_REQUEST
+    );
+
+    let refreshed = auth_service.refresh(&tokens.refresh_token).unwrap();
+    assert_eq!(
+        extract(&auth_service, &refreshed.token),
+        Ok(("game".into(), "Andy".into()))
+    );
+    // every refresh token can be used once
+    assert_eq!(
+        auth_service
+            .refresh(&tokens.refresh_token)
+            .unwrap_err()
+            .status(),
+        StatusCode::UNAUTHORIZED
+    );
+    assert!(auth_service.refresh(&refreshed.refresh_token).is_ok());
+
+    // only one of parallel refreshes with the same token gets new tokens
+    let tokens = auth_service.issue_tokens("Bob", "game");
+    let refreshed = std::thread::scope(|scope| {
+        let refreshes: Vec<_> = (0..8)
+            .map(|_| scope.spawn(|| auth_service.refresh(&tokens.refresh_token).is_ok()))
+            .collect();
+        refreshes
+            .into_iter()
+            .map(|refresh| refresh.join().unwrap())
+            .filter(|refreshed| *refreshed)
+            .count()
+    });
+    assert_eq!(refreshed, 1);
+}
+
+#[test]
+fn test_revocation() {
+    let auth_service = AuthService::new(KeyRing::generate());
+    let andy = auth_service.issue_tokens("Andy", "game");
+    let bob = auth_service.issue_tokens("Bob", "game");
+    let carl = auth_service.issue_tokens("Carl", "other");
+
+    auth_service.revoke("game", "Andy");
+    assert_eq!(
+        extract(&auth_service, &andy.token),
+        Err(StatusCode::UNAUTHORIZED)
+    );
+    assert!(auth_service.refresh(&andy.refresh_token).is_err());
+    assert!(extract(&auth_service, &bob.token).is_ok());
+
+    // somebody joining under the same name later gets working tokens
+    let new_andy = auth_service.issue_tokens("Andy", "game");
+    assert!(extract(&auth_service, &new_andy.token).is_ok());
+    assert!(extract(&auth_service, &andy.token).is_err());
+
+    auth_service.revoke_game("game");
+    assert!(extract(&auth_service, &bob.token).is_err());
+    assert!(auth_service.refresh(&bob.refresh_token).is_err());
+    assert!(extract(&auth_service, &carl.token).is_ok());
+}
+
+#[test]
+fn test_account_tokens() {
+    let auth_service = AuthService::new(KeyRing::generate());
+    let account = Account {
+        id: 7,
+        username: "Andy".into(),
+    };
+    let extract_account = |token: Option<&str>| {
+        let request = match token {
+            None => TestRequest::default(),
+            Some(token) => {
+                TestRequest::default().header("Authorization", format!("Bearer {}", token))
+            }
+        };
+        auth_service
+            .extract_account(&request.to_http_request())
+            .map_err(|response| response.status())
+    };
+
+    assert_eq!(extract_account(None), Ok(None));
+    let account_token = auth_service.issue_account_token(&account);
+    assert_eq!(
+        extract_account(Some(&account_token)),
+        Ok(Some(account.clone()))
+    );
+
+    // game tokens are bound to the account, but they are no account tokens
+    let tokens = auth_service.issue_account_tokens(&account, "game");
+    assert_eq!(extract_account(Some(&tokens.token)), Ok(None));
+    // a guest's token of an earlier game does not keep them from playing as a guest
+    let guest = auth_service.issue_tokens("Bob", "earlier game");
+    assert_eq!(extract_account(Some(&guest.token)), Ok(None));
+    assert_eq!(extract_account(Some("garbage")), Ok(None));
+    // an account token that does not verify is rejected
+    let foreign = AuthService::new(KeyRing::generate()).issue_account_token(&account);
+    assert_eq!(
+        extract_account(Some(&foreign)),
+        Err(StatusCode::UNAUTHORIZED)
+    );
+    let refreshed = auth_service.refresh(&tokens.refresh_token).unwrap();
+    let claims = auth_service
+        .keys()
+        .verify::<JwtData>(&refreshed.token)
+        .unwrap();
+    assert_eq!(claims.custom.account_id, Some(7));
+}
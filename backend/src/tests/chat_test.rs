@@ -0,0 +1,81 @@
+use crate::session::chat::{
+    Chat, ChatEntry, ChatError, WordFilter, HISTORY_SIZE, MAX_MESSAGE_LENGTH, RATE_LIMIT_MESSAGES,
+    RATE_LIMIT_WINDOW,
+};
+use std::sync::Arc;
+use std::time::Instant;
+
+fn chat() -> Chat {
+    Chat::new(Arc::new(WordFilter::from_list("darn, Heck")))
+}
+
+#[test]
+fn test_word_filter() {
+    let filter = WordFilter::from_list("darn, Heck,");
+    assert_eq!(
+        filter.censor("Darn it, what the heck!"),
+        "**** it, what the ****!"
+    );
+    assert_eq!(filter.censor("darnation"), "darnation");
+    assert_eq!(WordFilter::default().censor(" heck "), " heck ");
+}
+
+#[test]
+fn test_message_limits() {
+    let mut chat = chat();
+    assert!(matches!(
+        chat.post("Andy", "  "),
+        Err(ChatError::EmptyMessage)
+    ));
+
+    let long = "a".repeat(MAX_MESSAGE_LENGTH + 1);
+    assert!(matches!(
+        chat.post("Andy", &long),
+        Err(ChatError::TooLong(_))
+    ));
+
+    assert_eq!(
+        chat.post("Andy", " heck ").unwrap(),
+        ChatEntry {
+            who: "Andy".into(),
+            text: "****".into()
+        }
+    );
+}
+
+#[test]
+fn test_rate_limit() {
+    let mut chat = chat();
+    let start = Instant::now();
+
+    for _ in 0..RATE_LIMIT_MESSAGES {
+        chat.post_at("Andy", "hi", start).unwrap();
+    }
+    assert!(matches!(
+        chat.post_at("Andy", "hi", start),
+        Err(ChatError::RateLimited)
+    ));
+    assert!(chat.post_at("Bob", "hi", start).is_ok());
+    assert!(chat
+        .post_at("Andy", "hi", start + RATE_LIMIT_WINDOW)
+        .is_ok());
+}
+
+#[test]
+fn test_mute_and_history() {
+    let mut chat = chat();
+    chat.set_muted("Bob", true);
+    assert!(matches!(chat.post("Bob", "hi"), Err(ChatError::Muted(_))));
+    chat.set_muted("Bob", false);
+
+    let start = Instant::now();
+    for i in 0..=HISTORY_SIZE {
+        chat.post_at(&format!("player{}", i), &i.to_string(), start)
+            .unwrap();
+    }
+
+    let history = chat.history();
+    assert_eq!(history.len(), HISTORY_SIZE);
+    assert_eq!(history[0].text, "1");
+    assert_eq!(history[HISTORY_SIZE - 1].text, HISTORY_SIZE.to_string());
+}
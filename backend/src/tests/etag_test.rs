@@ -0,0 +1,24 @@
+use crate::handler::util::etag::json_with_etag;
+use actix_web::http::header::{ETAG, IF_NONE_MATCH};
+use actix_web::http::StatusCode;
+use actix_web::test::TestRequest;
+
+#[test]
+fn test_not_modified_with_matching_etag() {
+    let body = vec!["Andy", "Bob"];
+
+    let first = json_with_etag(&TestRequest::default().to_http_request(), &body);
+    assert_eq!(first.status(), StatusCode::OK);
+    let etag = first.headers().get(ETAG).unwrap().to_str().unwrap();
+
+    let request = TestRequest::default()
+        .header(IF_NONE_MATCH, format!("\"other\", {}", etag))
+        .to_http_request();
+    let second = json_with_etag(&request, &body);
+    assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(second.headers().get(ETAG).unwrap(), etag);
+
+    let changed = json_with_etag(&request, &vec!["Andy"]);
+    assert_eq!(changed.status(), StatusCode::OK);
+    assert_ne!(changed.headers().get(ETAG).unwrap(), etag);
+}
@@ -0,0 +1,158 @@
+use crate::handler::service::jwt_keys::KeyRing;
+use crate::repo::game_repo::{GameRepoError, InMemoryGameRepo};
+use crate::session::chat::WordFilter;
+use crate::ws::protocol::ProtocolVersion;
+use crate::ws::ws_message::WSMsg;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use uno_core::gamestate::game::Game;
+
+#[test]
+fn test_add_and_get_game() {
+    let repo = InMemoryGameRepo::new(WordFilter::default(), Arc::new(KeyRing::generate()));
+    let game = Game::new("Andy".into());
+    let game_id = game.id.clone();
+    repo.add_game(game);
+
+    let game = repo.get_game_by_id(game_id.clone()).unwrap();
+    assert_eq!(game.lock().unwrap().game().id, game_id);
+}
+
+#[test]
+fn test_game_not_found() {
+    let repo = InMemoryGameRepo::new(WordFilter::default(), Arc::new(KeyRing::generate()));
+    repo.add_game(Game::new("Andy".into()));
+
+    match repo.get_game_by_id("nonexistent".into()) {
+        Err(GameRepoError::GameNotFound(id)) => assert_eq!(id, "nonexistent"),
+        Ok(_) => panic!("Game should not have been found"),
+    }
+}
+
+#[test]
+fn test_games_lock_independently() {
+    let repo = Arc::new(InMemoryGameRepo::new(
+        WordFilter::default(),
+        Arc::new(KeyRing::generate()),
+    ));
+    let first = Game::new("Andy".into());
+    let second = Game::new("Bob".into());
+    let (first_id, second_id) = (first.id.clone(), second.id.clone());
+    repo.add_game(first);
+    repo.add_game(second);
+
+    let first = repo.get_game_by_id(first_id.clone()).unwrap();
+    let _first_guard = first.lock().unwrap();
+
+    let other_repo = Arc::clone(&repo);
+    let handle = thread::spawn(move || {
+        let first = other_repo.get_game_by_id(first_id).unwrap();
+        let second = other_repo.get_game_by_id(second_id).unwrap();
+        let first_locked = first.try_lock().is_err();
+        let second_free = second.try_lock().is_ok();
+        (first_locked, second_free)
+    });
+
+    assert_eq!(handle.join().unwrap(), (true, true));
+}
+
+static BENCH_GAMES: usize = 64;
+static BENCH_THREADS: usize = 8;
+static BENCH_DURATION: Duration = Duration::from_secs(2);
+/// Models an AI turn, which blocks while the game is locked.
+static BENCH_HOLD: Duration = Duration::from_millis(1);
+
+/// One unit of work done with a game's lock held: building the personalized status of every player.
+fn status_for_everyone(game: &Game) -> usize {
+    let size = game
+        .players()
+        .iter()
+        .map(|player| {
+            WSMsg::status(game, player.name())
+                .unwrap()
+                .to_json(ProtocolVersion::LATEST)
+                .len()
+        })
+        .sum();
+    thread::sleep(BENCH_HOLD);
+    size
+}
+
+fn new_bench_game() -> Game {
+    // humans only, so that starting the game never waits on AI turns
+    let mut game = Game::new("Andy".into());
+    for name in ["Bob", "Candace", "Danny", "Eli", "Farquaad"] {
+        game.add_player(name.into()).unwrap();
+    }
+    game.start().unwrap();
+    game
+}
+
+/// Runs `BENCH_THREADS` workers for `BENCH_DURATION`, each hammering its share of games through `op`.
+/// Returns the number of completed operations per second.
+fn measure<F>(op: F) -> f64
+where
+    F: Fn(usize) + Send + Sync + 'static,
+{
+    let op = Arc::new(op);
+    let start = Instant::now();
+
+    let handles = (0..BENCH_THREADS)
+        .map(|worker| {
+            let op = Arc::clone(&op);
+            thread::spawn(move || {
+                let mut done = 0usize;
+                while start.elapsed() < BENCH_DURATION {
+                    op((worker + done * BENCH_THREADS) % BENCH_GAMES);
+                    done += 1;
+                }
+                done
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let total: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    total as f64 / start.elapsed().as_secs_f64()
+}
+
+/// Throughput benchmark with many simultaneous games, comparing the repo against the former single global lock.
+/// Run with `cargo test --release bench_many_simultaneous_games -- --ignored --nocapture`.
+#[test]
+#[ignore]
+fn bench_many_simultaneous_games() {
+    let global: Arc<Mutex<Vec<Game>>> = Arc::new(Mutex::new(
+        (0..BENCH_GAMES).map(|_| new_bench_game()).collect(),
+    ));
+    let global_ops = measure(move |index| {
+        let games = global.lock().unwrap();
+        status_for_everyone(&games[index]);
+    });
+
+    let repo = Arc::new(InMemoryGameRepo::new(
+        WordFilter::default(),
+        Arc::new(KeyRing::generate()),
+    ));
+    let ids = (0..BENCH_GAMES)
+        .map(|_| {
+            let game = new_bench_game();
+            let id = game.id.clone();
+            repo.add_game(game);
+            id
+        })
+        .collect::<Vec<String>>();
+    let per_game_ops = measure(move |index| {
+        let session = repo.get_game_by_id(ids[index].clone()).unwrap();
+        let session = session.lock().unwrap();
+        status_for_everyone(session.game());
+    });
+
+    println!(
+        "{} games, {} threads: global lock {:.0} ops/s, per-game locks {:.0} ops/s ({:.2}x)",
+        BENCH_GAMES,
+        BENCH_THREADS,
+        global_ops,
+        per_game_ops,
+        per_game_ops / global_ops
+    );
+}
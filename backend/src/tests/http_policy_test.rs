@@ -0,0 +1,32 @@
+use crate::http_policy::HttpPolicy;
+use actix_web::http::Method;
+
+#[test]
+fn test_parse_policy() {
+    let policy = HttpPolicy::new("*", "*", false, 1024).unwrap();
+    assert!(policy.origins.is_empty());
+    assert!(policy.methods.is_empty());
+
+    let policy = HttpPolicy::new(
+        "https://uno.example.com, http://localhost:8080",
+        "get,POST",
+        true,
+        1024,
+    )
+    .unwrap();
+    assert_eq!(
+        policy.origins,
+        vec!["https://uno.example.com", "http://localhost:8080"]
+    );
+    assert_eq!(policy.methods, vec![Method::GET, Method::POST]);
+    assert!(policy.credentials);
+}
+
+#[test]
+fn test_invalid_policy() {
+    // browsers refuse credentials for a wildcard origin anyway
+    assert!(HttpPolicy::new("*", "*", true, 1024).is_err());
+    assert!(HttpPolicy::new("uno.example.com", "*", false, 1024).is_err());
+    assert!(HttpPolicy::new("*", "GET,NOT A METHOD", false, 1024).is_err());
+    assert!(HttpPolicy::new("*", "*", false, 0).is_err());
+}
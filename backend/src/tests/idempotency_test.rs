@@ -0,0 +1,126 @@
+use crate::handler::service::jwt_keys::KeyRing;
+use crate::handler::util::idempotency::{apply_once, IDEMPOTENCY_KEY_HEADER};
+use crate::session::chat::WordFilter;
+use crate::session::game_session::GameSession;
+use actix_web::http::StatusCode;
+use actix_web::test::TestRequest;
+use actix_web::HttpResponse;
+use std::sync::Arc;
+use uno_core::cards::card::{Card, CardColor, CardSymbol};
+use uno_core::gamestate::builder::GameBuilder;
+use uno_core::gamestate::command::Command;
+
+fn card(color: CardColor, symbol: CardSymbol) -> Card {
+    Card::new(color, symbol).unwrap()
+}
+
+/// A running game with Andy at turn, who holds only the given card against a red nine.
+fn running_session(andy_holds: Card) -> GameSession {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    let game = GameBuilder::new("Andy")
+        .human("Bob")
+        .hand("Andy", vec![andy_holds])
+        .hand("Bob", vec![card(Blue, Value(1)), card(Blue, Value(2))])
+        .draw_pile(vec![card(Yellow, Value(7)), card(Yellow, Value(8))])
+        .top_card(card(Red, Value(9)))
+        .build()
+        .unwrap();
+    GameSession::new(
+        game,
+        Arc::new(WordFilter::default()),
+        Arc::new(KeyRing::generate()),
+    )
+}
+
+fn hand_size(session: &GameSession) -> usize {
+    session
+        .game()
+        .find_player("Andy".into())
+        .unwrap()
+        .cards()
+        .len()
+}
+
+fn draw(session: &mut GameSession, key: Option<&str>, expected_turn: Option<u64>) -> HttpResponse {
+    let request = match key {
+        None => TestRequest::default(),
+        Some(key) => TestRequest::default().header(IDEMPOTENCY_KEY_HEADER, key),
+    };
+    let command = Command::DrawCards {
+        player: "Andy".into(),
+    };
+    match apply_once(
+        &request.to_http_request(),
+        session,
+        "Andy",
+        expected_turn,
+        command,
+    ) {
+        Ok(response) => response,
+        Err(response) => response,
+    }
+}
+
+#[test]
+fn test_replay() {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    // Andy cannot play the blue card, so he has to draw
+    let mut session = running_session(card(Blue, Value(3)));
+    let turn = session.game().turn();
+
+    let first = draw(&mut session, Some("draw-1"), None);
+    assert_eq!(first.status(), StatusCode::NO_CONTENT);
+    assert_eq!(hand_size(&session), 2);
+    assert_eq!(session.game().turn(), turn + 1);
+
+    // the retry is answered the same, without drawing again
+    let retry = draw(&mut session, Some("draw-1"), None);
+    assert_eq!(retry.status(), first.status());
+    assert_eq!(hand_size(&session), 2);
+    assert_eq!(session.game().turn(), turn + 1);
+    assert!(first.headers().get("idempotent-replayed").is_none());
+    assert_eq!(retry.headers().get("idempotent-replayed").unwrap(), "true");
+
+    assert_eq!(
+        draw(&mut session, Some("not a key"), None).status(),
+        StatusCode::BAD_REQUEST
+    );
+
+    // the same key with another body is a mistake of the client, not a retry
+    assert_eq!(
+        draw(&mut session, Some("draw-1"), Some(turn)).status(),
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+}
+
+#[test]
+fn test_replay_of_an_error() {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    // Andy can play the red card, so drawing is refused
+    let mut session = running_session(card(Red, Value(3)));
+    let turn = session.game().turn();
+
+    let first = draw(&mut session, Some("draw-1"), None);
+    assert_eq!(first.status(), StatusCode::CONFLICT);
+    let retry = draw(&mut session, Some("draw-1"), None);
+    assert_eq!(retry.status(), StatusCode::CONFLICT);
+    assert_eq!(retry.headers().get("idempotent-replayed").unwrap(), "true");
+    assert_eq!(hand_size(&session), 1);
+    assert_eq!(session.game().turn(), turn);
+}
+
+#[test]
+fn test_stale_state() {
+    let mut session = running_session(card(CardColor::Blue, CardSymbol::Value(3)));
+    let turn = session.game().turn();
+
+    let response = draw(&mut session, None, Some(turn + 1));
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    assert_eq!(session.game().turn(), turn);
+}
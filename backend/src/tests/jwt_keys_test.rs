@@ -0,0 +1,102 @@
+use crate::handler::service::jwt_keys::{KeyAlgorithm, KeyRing, KeyRingError};
+use jwt_simple::prelude::*;
+use serde_json::json;
+
+#[derive(Serialize, Deserialize)]
+struct Name {
+    name: String,
+}
+
+fn claims(name: &str) -> JWTClaims<Name> {
+    let name = Name {
+        name: name.to_string(),
+    };
+    Claims::with_custom_claims(name, Duration::from_hours(1))
+}
+
+#[test]
+fn test_rotation() {
+    let old = Ed25519KeyPair::generate();
+    let new = ES256KeyPair::generate();
+    let old_ring = KeyRing::from_json(
+        &json!([{"kid": "old", "alg": "Ed25519", "key": old.to_pem()}]).to_string(),
+    )
+    .unwrap();
+    let old_token = old_ring.sign(claims("Andy")).unwrap();
+
+    // the old key only verifies now, the new one signs
+    let ring = KeyRing::from_json(
+        &json!([
+            {"kid": "old", "alg": "EdDSA", "key": old.public_key().to_pem()},
+            {"kid": "new", "alg": "ES256", "key": new.to_pem().unwrap(), "sign": true},
+        ])
+        .to_string(),
+    )
+    .unwrap();
+    assert_eq!(ring.verify::<Name>(&old_token).unwrap().custom.name, "Andy");
+    let new_token = ring.sign(claims("Bob")).unwrap();
+    assert_eq!(
+        Token::decode_metadata(&new_token).unwrap().key_id(),
+        Some("new")
+    );
+    assert_eq!(ring.verify::<Name>(&new_token).unwrap().custom.name, "Bob");
+    assert!(old_ring.verify::<Name>(&new_token).is_err());
+
+    let public_keys = ring.public_keys();
+    assert_eq!(public_keys.len(), 2);
+    assert_eq!(public_keys[0].kid, "new");
+    assert_eq!(public_keys[0].alg, KeyAlgorithm::ES256);
+    assert!(!public_keys[0].pem.contains("PRIVATE"));
+}
+
+#[test]
+fn test_shared_secret() {
+    let keys = json!([{"kid": "a", "alg": "HS256", "key": "c2VjcmV0IHNoYXJlZCBieSBhbGwgdGhlIGdhbWUgc2VydmVycw=="}]);
+    let first = KeyRing::from_json(&keys.to_string()).unwrap();
+    let second = KeyRing::from_json(&keys.to_string()).unwrap();
+
+    let token = first.sign(claims("Andy")).unwrap();
+    assert_eq!(second.verify::<Name>(&token).unwrap().custom.name, "Andy");
+    assert!(second.public_keys().is_empty());
+    assert!(KeyRing::generate().verify::<Name>(&token).is_err());
+}
+
+#[test]
+fn test_invalid_key_files() {
+    let public = Ed25519KeyPair::generate().public_key().to_pem();
+    let secret = "c2VjcmV0IHNoYXJlZCBieSBhbGwgdGhlIGdhbWUgc2VydmVycw==";
+
+    assert!(matches!(
+        KeyRing::from_json(&json!([{"kid": "a", "alg": "EdDSA", "key": public}]).to_string()),
+        Err(KeyRingError::NoSigningKey)
+    ));
+    assert!(matches!(
+        KeyRing::from_json(
+            &json!([{"kid": "a", "alg": "EdDSA", "key": public, "sign": true}]).to_string()
+        ),
+        Err(KeyRingError::CannotSign(_))
+    ));
+    assert!(matches!(
+        KeyRing::from_json(
+            &json!([
+                {"kid": "a", "alg": "HS256", "key": secret},
+                {"kid": "a", "alg": "HS256", "key": secret},
+            ])
+            .to_string()
+        ),
+        Err(KeyRingError::DuplicateKeyId(_))
+    ));
+    // "secret" is far too short to sign with
+    assert!(matches!(
+        KeyRing::from_json(&json!([{"kid": "a", "alg": "HS256", "key": "c2VjcmV0"}]).to_string()),
+        Err(KeyRingError::InvalidKey(_, _))
+    ));
+    assert!(matches!(
+        KeyRing::from_json(&json!([{"kid": "a", "alg": "ES256", "key": "nope"}]).to_string()),
+        Err(KeyRingError::InvalidKey(_, _))
+    ));
+    assert!(matches!(
+        KeyRing::from_json("{}"),
+        Err(KeyRingError::Parse(_))
+    ));
+}
@@ -0,0 +1,111 @@
+use crate::handler::service::matchmaking::{
+    MatchmakingError, MatchmakingService, QueueStatus, AI_FILL_TIMEOUT, MATCH_SIZE, POLL_TIMEOUT,
+};
+use std::time::{Duration, Instant};
+use uno_core::gamestate::game::{Game, GameStatus};
+
+fn poll(service: &MatchmakingService, ticket: &str, now: Instant) -> (QueueStatus, Vec<Game>) {
+    let mut games = vec![];
+    let status = service.poll(ticket, now, |game| games.push(game)).unwrap();
+    (status, games)
+}
+
+#[test]
+fn test_full_group_is_matched() {
+    let service = MatchmakingService::new();
+    let now = Instant::now();
+
+    let tickets: Vec<String> = (0..MATCH_SIZE)
+        .map(|i| service.join(format!("player{}", i), false, now).unwrap())
+        .collect();
+    assert!(matches!(
+        service.join("player0".into(), false, now),
+        Err(MatchmakingError::AlreadyWaiting(_))
+    ));
+
+    let (status, games) = poll(&service, &tickets[1], now);
+    assert_eq!(games.len(), 1);
+    let game = &games[0];
+    assert_eq!(game.status(), GameStatus::Running);
+    assert_eq!(game.find_author().unwrap().name(), "player0");
+    assert!(game.players().iter().all(|player| player.is_human()));
+    assert_eq!(
+        status,
+        QueueStatus::Matched {
+            game_id: game.id.clone(),
+            player_name: "player1".into()
+        }
+    );
+
+    // a match is reported only once
+    assert!(service.poll(&tickets[1], now, |_| ()).is_err());
+    let (status, games) = poll(&service, &tickets[0], now);
+    assert!(games.is_empty());
+    assert!(matches!(status, QueueStatus::Matched { .. }));
+}
+
+#[test]
+fn test_ais_fill_seats_after_timeout() {
+    let service = MatchmakingService::new();
+    let start = Instant::now();
+
+    let andy = service.join("Andy".into(), true, start).unwrap();
+    let bob = service.join("Bob".into(), false, start).unwrap();
+
+    let (status, games) = poll(&service, &andy, start);
+    assert_eq!(status, QueueStatus::Waiting(2));
+    assert!(games.is_empty());
+
+    // both keep polling while they wait
+    let mut now = start + POLL_TIMEOUT / 2;
+    while now < start + AI_FILL_TIMEOUT {
+        assert_eq!(poll(&service, &andy, now).0, QueueStatus::Waiting(2));
+        assert_eq!(poll(&service, &bob, now).0, QueueStatus::Waiting(2));
+        now += POLL_TIMEOUT / 2;
+    }
+
+    let (status, games) = poll(&service, &andy, start + AI_FILL_TIMEOUT);
+    assert!(matches!(status, QueueStatus::Matched { .. }));
+    let players = games[0].players();
+    assert_eq!(players.len(), MATCH_SIZE);
+    assert_eq!(players.iter().filter(|p| p.is_human()).count(), 1);
+
+    // Bob did not want to play with AIs
+    let (status, _) = poll(&service, &bob, start + AI_FILL_TIMEOUT);
+    assert_eq!(status, QueueStatus::Waiting(1));
+    service.leave(&bob).unwrap();
+    assert!(service.leave(&bob).is_err());
+}
+
+#[test]
+fn test_tickets_not_polled_expire() {
+    let service = MatchmakingService::new();
+    let start = Instant::now();
+    let later = start + POLL_TIMEOUT + Duration::from_secs(1);
+
+    let gone = service.join("Gone".into(), false, start).unwrap();
+    let tickets: Vec<String> = (1..MATCH_SIZE)
+        .map(|i| service.join(format!("player{}", i), false, later).unwrap())
+        .collect();
+
+    // the player who stopped polling is not put into the game
+    let (status, games) = poll(&service, &tickets[0], later);
+    assert_eq!(status, QueueStatus::Waiting(MATCH_SIZE - 1));
+    assert!(games.is_empty());
+    assert!(matches!(
+        service.poll(&gone, later, |_| ()),
+        Err(MatchmakingError::NoSuchTicket(_))
+    ));
+
+    // a match nobody picks up is forgotten
+    let last = service.join("Last".into(), false, later).unwrap();
+    let (status, games) = poll(&service, &last, later);
+    assert!(matches!(status, QueueStatus::Matched { .. }));
+    assert_eq!(games.len(), 1);
+    let (status, _) = poll(&service, &tickets[0], later);
+    assert!(matches!(status, QueueStatus::Matched { .. }));
+    assert!(matches!(
+        service.poll(&tickets[1], later + POLL_TIMEOUT * 2, |_| ()),
+        Err(MatchmakingError::NoSuchTicket(_))
+    ));
+}
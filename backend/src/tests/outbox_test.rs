@@ -0,0 +1,49 @@
+use crate::session::outbox::{Outbox, BACKLOG_SIZE};
+use crate::ws::ws_message::WSMsg;
+
+fn seqs(msgs: Vec<WSMsg>) -> Vec<u64> {
+    msgs.iter().map(|msg| msg.seq.unwrap()).collect()
+}
+
+#[test]
+fn test_record_numbers_messages() {
+    let mut outbox = Outbox::new();
+
+    let first = outbox.record(WSMsg::finish("Andy".into()));
+    let second = outbox.record(WSMsg::finish("Bob".into()));
+
+    assert_eq!(first, WSMsg::finish("Andy".into()).with_seq(1));
+    assert_eq!(seqs(vec![second]), vec![2]);
+}
+
+#[test]
+fn test_since() {
+    let mut outbox = Outbox::new();
+    assert_eq!(outbox.since(0).map(seqs), Some(vec![]));
+    assert!(outbox.since(1).is_none()); // from the future
+
+    for _ in 0..3 {
+        outbox.record(WSMsg::finish("Andy".into()));
+    }
+
+    assert_eq!(outbox.since(0).map(seqs), Some(vec![1, 2, 3]));
+    assert_eq!(outbox.since(1).map(seqs), Some(vec![2, 3]));
+    assert_eq!(outbox.since(3).map(seqs), Some(vec![]));
+    assert!(outbox.since(4).is_none());
+}
+
+#[test]
+fn test_since_exceeding_backlog() {
+    let mut outbox = Outbox::new();
+    for _ in 0..BACKLOG_SIZE + 2 {
+        outbox.record(WSMsg::finish("Andy".into()));
+    }
+
+    assert!(outbox.since(0).is_none());
+    assert!(outbox.since(1).is_none());
+    assert_eq!(outbox.since(2).unwrap().len(), BACKLOG_SIZE);
+    assert_eq!(
+        outbox.since(BACKLOG_SIZE as u64).map(seqs),
+        Some(vec![BACKLOG_SIZE as u64 + 1, BACKLOG_SIZE as u64 + 2])
+    );
+}
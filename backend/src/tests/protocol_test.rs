@@ -0,0 +1,226 @@
+use crate::handler::util::response::ErrorResponse;
+use crate::session::chat::{ChatEntry, ChatError};
+use crate::ws::protocol::ProtocolVersion;
+use crate::ws::ws_message::WSMsg;
+use std::fs;
+use std::path::PathBuf;
+use uno_core::cards::card::{Card, CardColor, CardSymbol};
+use uno_core::err::call_uno::CallUnoError;
+use uno_core::gamestate::builder::GameBuilder;
+use uno_core::gamestate::command::Command;
+use uno_core::gamestate::event::Event;
+use uno_core::gamestate::game::Game;
+
+fn card(color: CardColor, symbol: CardSymbol) -> Card {
+    Card::new(color, symbol).unwrap()
+}
+
+fn running_game() -> Game {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    GameBuilder::new("Andy")
+        .human("Bob")
+        .hand("Andy", vec![card(Red, Value(1)), card(Black, Wild)])
+        .hand("Bob", vec![card(Blue, Skip)])
+        .top_card(card(Red, Value(9)))
+        .current_player("Bob")
+        .build()
+        .unwrap()
+}
+
+fn finished_game() -> Game {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    let mut game = GameBuilder::new("Andy")
+        .human("Bob")
+        .hand("Andy", vec![card(Red, Value(1))])
+        .hand("Bob", vec![card(Blue, Skip), card(Blue, Value(2))])
+        .top_card(card(Red, Value(9)))
+        .build()
+        .unwrap();
+    game.apply(Command::PlayCard {
+        player: "Andy".into(),
+        card: card(Red, Value(1)),
+        new_color: None,
+        said_uno: false,
+    })
+    .unwrap();
+    game
+}
+
+/// One message of every kind the server sends, named after their golden files.
+fn every_message() -> Vec<(&'static str, WSMsg)> {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    let mut lobby = Game::new("Andy".into());
+    lobby.add_player("Bob".into()).unwrap();
+    let mut rematch = finished_game();
+    rematch
+        .apply(Command::ProposeRematch {
+            author: "Andy".into(),
+        })
+        .unwrap();
+
+    vec![
+        ("status_lobby", WSMsg::status(&lobby, "Bob".into()).unwrap()),
+        (
+            "status_running",
+            WSMsg::status(&running_game(), "Andy".into()).unwrap(),
+        ),
+        (
+            "status_finished",
+            WSMsg::status(&finished_game(), "Bob".into()).unwrap(),
+        ),
+        (
+            "status_rematch",
+            WSMsg::status(&rematch, "Bob".into()).unwrap(),
+        ),
+        (
+            "spectator_status",
+            WSMsg::public_status(&running_game()).unwrap(),
+        ),
+        ("draw", WSMsg::draw("Andy".into(), "Bob".into(), 2)),
+        (
+            "draw_me",
+            WSMsg::draw_me("Bob".into(), vec![card(Green, Value(3))]),
+        ),
+        (
+            "play_card",
+            WSMsg::play_card("Andy".into(), "Bob".into(), card(Yellow, Draw2)),
+        ),
+        ("finish", WSMsg::finish("Andy".into())),
+        ("forfeit", WSMsg::forfeit("Andy".into(), Some("Bob".into()))),
+        (
+            "penalty",
+            WSMsg::penalty(
+                "Andy".into(),
+                vec![card(Red, Reverse), card(Blue, Value(0))],
+            ),
+        ),
+        ("gained_cards", WSMsg::gained_cards("Andy".into(), 2)),
+        ("chat", WSMsg::chat("Andy".into(), "good game".into())),
+        (
+            "spectator_chat",
+            WSMsg::spectator_chat("Eve".into(), "go Bob".into()),
+        ),
+        (
+            "status_with_chat",
+            WSMsg::status(&lobby, "Bob".into())
+                .unwrap()
+                .with_chat(vec![ChatEntry {
+                    who: "Andy".into(),
+                    text: "hi".into(),
+                }]),
+        ),
+        ("ack", WSMsg::ack(Some("1".into()))),
+        (
+            "notice",
+            WSMsg::notice("The server restarts in 5 minutes".into()),
+        ),
+        (
+            "error_typed",
+            WSMsg::command_error(Some("2".into()), ChatError::EmptyMessage.error_body()),
+        ),
+        (
+            "error_plain",
+            WSMsg::command_error(None, CallUnoError::CannotCallUno.error_body()),
+        ),
+        (
+            "sequenced",
+            WSMsg::play_card(
+                "Andy".into(),
+                "Bob".into(),
+                card(Black, Wild).morph_black_card(Red).unwrap(),
+            )
+            .with_seq(7),
+        ),
+    ]
+}
+
+fn golden_path(version: ProtocolVersion, name: &str) -> PathBuf {
+    let dir = match version {
+        ProtocolVersion::Legacy => "legacy",
+        ProtocolVersion::V2 => "v2",
+    };
+    [
+        env!("CARGO_MANIFEST_DIR"),
+        "src",
+        "tests",
+        "golden",
+        dir,
+        &format!("{}.json", name),
+    ]
+    .iter()
+    .collect()
+}
+
+/// Compares every message against its golden file. Run with `UPDATE_GOLDEN=1` to rewrite the files instead.
+fn check_golden(version: ProtocolVersion) {
+    let update = std::env::var("UPDATE_GOLDEN").is_ok();
+
+    for (name, msg) in every_message() {
+        let path = golden_path(version, name);
+        let json = msg.to_json(version);
+
+        if update {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, format!("{}\n", json)).unwrap();
+            continue;
+        }
+
+        let golden = fs::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("Missing golden file {}", path.display()));
+        assert_eq!(json, golden.trim_end(), "{:?} {} changed", version, name);
+    }
+}
+
+#[test]
+fn test_legacy_golden() {
+    check_golden(ProtocolVersion::Legacy);
+}
+
+#[test]
+fn test_v2_golden() {
+    check_golden(ProtocolVersion::V2);
+}
+
+#[test]
+fn test_negotiate() {
+    assert_eq!(
+        ProtocolVersion::negotiate(None).unwrap(),
+        ProtocolVersion::Legacy
+    );
+    assert_eq!(
+        ProtocolVersion::negotiate(Some(1)).unwrap(),
+        ProtocolVersion::Legacy
+    );
+    assert_eq!(
+        ProtocolVersion::negotiate(Some(2)).unwrap(),
+        ProtocolVersion::V2
+    );
+    assert_eq!(
+        ProtocolVersion::negotiate(Some(9)).unwrap(),
+        ProtocolVersion::LATEST
+    );
+    assert!(ProtocolVersion::negotiate(Some(0)).is_err());
+}
+
+#[test]
+fn test_spectators_do_not_see_cards() {
+    let game = running_game();
+    let drawn = Event::CardsDrawn {
+        who: "Andy".into(),
+        next: "Bob".into(),
+        cards: vec![card(CardColor::Green, CardSymbol::Value(3))],
+    };
+    assert_eq!(
+        WSMsg::for_spectator(&game, &drawn).unwrap(),
+        Some(WSMsg::draw("Andy".into(), "Bob".into(), 1))
+    );
+
+    let uno = Event::UnoCalled { who: "Andy".into() };
+    assert_eq!(WSMsg::for_spectator(&game, &uno).unwrap(), None);
+}
@@ -0,0 +1,56 @@
+use crate::rate_limit::{Quota, RateLimited, RateLimiter, RateLimits};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_quota_window() {
+    let limiter = RateLimiter::new(RateLimits::default(), false);
+    let quota = Quota::new(2, 10);
+    let start = Instant::now();
+
+    assert!(limiter.check("Andy".into(), quota, start).is_ok());
+    assert!(limiter.check("Andy".into(), quota, start).is_ok());
+    assert_eq!(
+        limiter.check("Andy".into(), quota, start + Duration::from_secs(4)),
+        Err(RateLimited {
+            retry_after: Duration::from_secs(6)
+        })
+    );
+    // every client has its own quota
+    assert!(limiter.check("Bob".into(), quota, start).is_ok());
+
+    assert!(limiter
+        .check("Andy".into(), quota, start + Duration::from_secs(10))
+        .is_ok());
+}
+
+#[test]
+fn test_overrides() {
+    let limits = RateLimits::default()
+        .with_overrides("ip=100/1, post /game=1/60,POST /game/{gameID}/hints=5/5")
+        .unwrap();
+    assert_eq!(limits.per_ip, Quota::new(100, 1));
+    assert_eq!(limits.routes["POST /game"], Quota::new(1, 60));
+    assert_eq!(limits.routes["POST /game/{gameID}/hints"], Quota::new(5, 5));
+    assert_eq!(
+        limits.routes["POST /game/{gameID}/playCard"],
+        RateLimits::default().routes["POST /game/{gameID}/playCard"]
+    );
+
+    for invalid in ["ip=100", "ip=1/0", "/game=1/1", "POST game=1/1", "ip=a/1"] {
+        assert!(RateLimits::default().with_overrides(invalid).is_err());
+    }
+}
+
+#[test]
+fn test_only_valid_tokens_get_their_own_quota() {
+    let limiter = RateLimiter::new(RateLimits::default(), false)
+        .with_identities(|token| (token == "valid").then(|| "game Andy".to_string()));
+
+    assert_eq!(limiter.client("1.2.3.4".into(), None), "1.2.3.4");
+    assert_eq!(
+        limiter.client("1.2.3.4".into(), Some("valid")),
+        "1.2.3.4 game Andy"
+    );
+    // made up tokens count towards the quota of the IP address
+    assert_eq!(limiter.client("1.2.3.4".into(), Some("random")), "1.2.3.4");
+}
@@ -0,0 +1,135 @@
+use crate::handler::results::{verify_certificate, CertificateError};
+use crate::handler::service::jwt_keys::KeyRing;
+use crate::session::results::{GameResult, Results};
+use jwt_simple::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uno_core::gamestate::command::Command;
+use uno_core::gamestate::game::Game;
+
+fn apply(game: &mut Game, results: &mut Results, command: Command) {
+    let events = game.apply(command).unwrap();
+    results.record(game, &events, &HashMap::from([("Bob".to_string(), 7)]));
+}
+
+fn finished_game(keys: Arc<KeyRing>) -> (Game, Results) {
+    let mut game = Game::new("Andy".into());
+    game.add_player("Bob".into()).unwrap();
+    game.add_player("Carl".into()).unwrap();
+    let mut results = Results::new(&game, keys);
+
+    apply(
+        &mut game,
+        &mut results,
+        Command::Start {
+            player: "Andy".into(),
+        },
+    );
+    apply(
+        &mut game,
+        &mut results,
+        Command::Forfeit {
+            player: "Carl".into(),
+        },
+    );
+    assert!(results.finished().is_empty());
+    apply(
+        &mut game,
+        &mut results,
+        Command::Forfeit {
+            player: "Andy".into(),
+        },
+    );
+
+    (game, results)
+}
+
+#[test]
+fn test_result_is_recorded_when_finished() {
+    let (mut game, mut results) = finished_game(Arc::new(KeyRing::generate()));
+    let result = results.finished()[0].result.clone();
+
+    assert_eq!(result.game_id, game.id);
+    assert_eq!(result.round, 1);
+    assert_eq!(result.finishing_order, vec!["Bob", "Andy", "Carl"]);
+    assert_eq!(result.forfeited, vec!["Carl", "Andy"]);
+    let account_of = |name: &str| {
+        let player = result.players.iter().find(|player| player.name == name);
+        player.unwrap().account_id
+    };
+    assert_eq!(account_of("Bob"), Some(7));
+    assert_eq!(account_of("Andy"), None);
+    assert!(result.started_at <= result.finished_at);
+    assert_eq!(result.event_log_hash.as_ref().map(String::len), Some(64));
+
+    // the rematch is another game with the same id
+    apply(
+        &mut game,
+        &mut results,
+        Command::Start {
+            player: "Andy".into(),
+        },
+    );
+    apply(
+        &mut game,
+        &mut results,
+        Command::Forfeit {
+            player: "Bob".into(),
+        },
+    );
+    apply(
+        &mut game,
+        &mut results,
+        Command::Forfeit {
+            player: "Carl".into(),
+        },
+    );
+    assert_eq!(results.finished().len(), 2);
+    assert_eq!(results.finished()[1].result.round, 2);
+    assert_ne!(
+        results.finished()[1].result.event_log_hash,
+        result.event_log_hash
+    );
+}
+
+#[test]
+fn test_certificate() {
+    let keys = Arc::new(KeyRing::generate());
+    let (_, results) = finished_game(Arc::clone(&keys));
+    let result = results.finished()[0].result.clone();
+
+    // the certificate is signed once when the game finishes
+    let certificate = results.finished()[0].certificate.clone().unwrap();
+    assert_eq!(verify_certificate(&keys, &certificate), Ok(result.clone()));
+
+    // tampering with the result breaks the signature
+    let (header, rest) = certificate.split_once('.').unwrap();
+    let signature = rest.rsplit_once('.').unwrap().1;
+    let forged = GameResult {
+        finishing_order: vec!["Carl".into(), "Bob".into(), "Andy".into()],
+        ..result.clone()
+    };
+    let forged_claims = Base64UrlSafeNoPadding::encode_to_string(
+        serde_json::to_vec(&serde_json::json!({"sub": "game-result", "gameId": forged.game_id}))
+            .unwrap(),
+    )
+    .unwrap();
+    let forged_certificate = format!("{}.{}.{}", header, forged_claims, signature);
+    assert_eq!(
+        verify_certificate(&keys, &forged_certificate),
+        Err(CertificateError::InvalidSignature)
+    );
+    assert_eq!(
+        verify_certificate(&KeyRing::generate(), &certificate),
+        Err(CertificateError::InvalidSignature)
+    );
+
+    // other tokens of the server are no certificates
+    let token = keys
+        .sign(Claims::with_custom_claims(result, Duration::from_hours(1)))
+        .unwrap();
+    assert_eq!(
+        verify_certificate(&keys, &token),
+        Err(CertificateError::NotACertificate)
+    );
+}
@@ -0,0 +1,30 @@
+use crate::handler::service::jwt_keys::KeyRing;
+use crate::session::chat::WordFilter;
+use crate::session::game_session::GameSession;
+use crate::session::spectators::SpectateError;
+use std::sync::Arc;
+use uno_core::gamestate::game::Game;
+
+#[test]
+fn test_names_are_taken_once() {
+    let mut game = Game::new("Andy".into());
+    game.add_player("Bob".into()).unwrap();
+    let mut session = GameSession::new(
+        game,
+        Arc::new(WordFilter::default()),
+        Arc::new(KeyRing::generate()),
+    );
+
+    assert!(session.add_spectator("Eve").is_ok());
+    assert!(matches!(
+        session.add_spectator("Eve"),
+        Err(SpectateError::NameTaken(name)) if name == "Eve"
+    ));
+    // nobody watches under the name of a player
+    assert!(matches!(
+        session.add_spectator("Bob"),
+        Err(SpectateError::NameTaken(_))
+    ));
+    assert!(session.spectators().has_name("Eve"));
+    assert!(!session.spectators().has_name("Bob"));
+}
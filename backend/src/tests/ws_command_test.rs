@@ -0,0 +1,81 @@
+use crate::handler::util::response::ErrorResponse;
+use crate::session::chat::ChatError;
+use crate::ws::protocol::ProtocolVersion;
+use crate::ws::ws_command::{WSCommand, WSRequest};
+use crate::ws::ws_message::WSMsg;
+use uno_core::cards::card::{CardColor, CardSymbol};
+
+#[test]
+fn test_parse_commands() {
+    let request: WSRequest = serde_json::from_str(
+        r#"{"id": "1", "type": "PLAY CARD", "card": {"color": "BLACK", "type": "WILD", "value": null}, "newColor": "RED"}"#,
+    )
+    .unwrap();
+    assert_eq!(request.id, Some("1".into()));
+    match request.command {
+        WSCommand::PlayCard {
+            card,
+            new_color,
+            said_uno,
+            expected_turn,
+        } => {
+            assert_eq!(card.symbol, CardSymbol::Wild);
+            assert_eq!(new_color, Some(CardColor::Red));
+            assert!(!said_uno);
+            assert_eq!(expected_turn, None);
+        }
+        other => panic!("Parsed as {:?}", other),
+    }
+
+    let request: WSRequest = serde_json::from_str(r#"{"type": "DRAW"}"#).unwrap();
+    assert_eq!(request.id, None);
+    assert!(matches!(
+        request.command,
+        WSCommand::Draw {
+            expected_turn: None
+        }
+    ));
+
+    let request: WSRequest =
+        serde_json::from_str(r#"{"type": "DRAW", "expectedTurn": 7}"#).unwrap();
+    assert!(matches!(
+        request.command,
+        WSCommand::Draw {
+            expected_turn: Some(7)
+        }
+    ));
+
+    let request: WSRequest = serde_json::from_str(r#"{"id": "3", "type": "UNO"}"#).unwrap();
+    assert!(matches!(request.command, WSCommand::CallUno));
+
+    let request: WSRequest =
+        serde_json::from_str(r#"{"id": "4", "type": "CHAT", "text": "gg"}"#).unwrap();
+    assert!(matches!(request.command, WSCommand::Chat { text } if text == "gg"));
+
+    let request: WSRequest = serde_json::from_str(r#"{"type": "FORFEIT"}"#).unwrap();
+    assert!(matches!(request.command, WSCommand::Forfeit));
+
+    let request: WSRequest = serde_json::from_str(r#"{"type": "READY"}"#).unwrap();
+    assert!(matches!(request.command, WSCommand::Ready));
+}
+
+#[test]
+fn test_id_of_invalid_command() {
+    let text = r#"{"id": "7", "type": "FLY"}"#;
+    assert!(serde_json::from_str::<WSRequest>(text).is_err());
+    assert_eq!(WSRequest::id_of(text), Some("7".into()));
+    assert_eq!(WSRequest::id_of("garbage"), None);
+}
+
+#[test]
+fn test_replies() {
+    assert_eq!(
+        WSMsg::ack(Some("1".into())).to_json(ProtocolVersion::Legacy),
+        r#"{"type":"ACK","id":"1"}"#
+    );
+    assert_eq!(
+        WSMsg::command_error(Some("2".into()), ChatError::EmptyMessage.error_body())
+            .to_json(ProtocolVersion::Legacy),
+        r#"{"type":"ERROR","id":"2","error":{"type":"INVALID_CHAT_MESSAGE","message":"Chat message cannot be empty"}}"#
+    );
+}
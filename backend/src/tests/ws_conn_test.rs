@@ -0,0 +1,63 @@
+use crate::handler::service::jwt_keys::KeyRing;
+use crate::repo::game_repo::SharedSession;
+use crate::session::chat::WordFilter;
+use crate::session::game_session::GameSession;
+use crate::ws::protocol::ProtocolVersion;
+use crate::ws::ws_conn::WSConn;
+use actix_web::rt::{time::delay_for, System};
+use actix_web::{test, web, App, Error, HttpRequest, HttpResponse};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uno_core::gamestate::game::Game;
+
+async fn connect_andy(
+    request: HttpRequest,
+    stream: web::Payload,
+    session: web::Data<SharedSession>,
+) -> Result<HttpResponse, Error> {
+    let session = session.get_ref();
+    let (connection, response) = WSConn::new(
+        &request,
+        stream,
+        Arc::clone(session),
+        "Andy".into(),
+        ProtocolVersion::V2,
+        false,
+    )?;
+    session.lock().unwrap().connect("Andy".into(), connection);
+    Ok(response)
+}
+
+#[test]
+fn test_closed_connections_are_forgotten() {
+    System::new("test").block_on(async {
+        let session: SharedSession = Arc::new(Mutex::new(GameSession::new(
+            Game::new("Andy".into()),
+            Arc::new(WordFilter::default()),
+            Arc::new(KeyRing::generate()),
+        )));
+        let data = web::Data::new(Arc::clone(&session));
+        let mut server = test::start(move || {
+            App::new()
+                .app_data(data.clone())
+                .route("/ws", web::get().to(connect_andy))
+        });
+        let connected = || session.lock().unwrap().connected_players();
+
+        let first = server.ws_at("/ws").await.unwrap();
+        delay_for(Duration::from_millis(100)).await;
+        assert_eq!(connected(), vec!["Andy"]);
+
+        // connecting again closes the first connection, which must not take the second one with it
+        let second = server.ws_at("/ws").await.unwrap();
+        delay_for(Duration::from_millis(100)).await;
+        drop(first);
+        delay_for(Duration::from_millis(100)).await;
+        assert_eq!(connected(), vec!["Andy"]);
+
+        // a client that goes away is no longer counted
+        drop(second);
+        delay_for(Duration::from_millis(100)).await;
+        assert!(connected().is_empty());
+    });
+}
@@ -0,0 +1,31 @@
+use crate::handler::service::ws_ticket::{TicketHolder, WsTicketService, TICKET_LIFETIME};
+use std::time::{Duration, Instant};
+
+fn andy() -> TicketHolder {
+    TicketHolder {
+        game_id: "game".into(),
+        name: "Andy".into(),
+        spectator: false,
+    }
+}
+
+#[test]
+fn test_tickets_are_single_use() {
+    let tickets = WsTicketService::new();
+    let now = Instant::now();
+
+    let ticket = tickets.issue(andy(), now);
+    assert_eq!(tickets.redeem(&ticket, now), Some(andy()));
+    assert_eq!(tickets.redeem(&ticket, now), None);
+    assert_eq!(tickets.redeem("made up", now), None);
+}
+
+#[test]
+fn test_tickets_expire() {
+    let tickets = WsTicketService::new();
+    let now = Instant::now();
+
+    let ticket = tickets.issue(andy(), now);
+    let later = now + TICKET_LIFETIME + Duration::from_secs(1);
+    assert_eq!(tickets.redeem(&ticket, later), None);
+}
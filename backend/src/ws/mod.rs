@@ -1,4 +1,6 @@
+pub mod protocol;
+pub mod server_event;
 mod ws_actor;
+pub mod ws_command;
 pub mod ws_conn;
 pub mod ws_message;
-mod ws_structs;
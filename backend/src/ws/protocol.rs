@@ -0,0 +1,104 @@
+use crate::ws::server_event::ServerEvent;
+use serde::Serialize;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use uno_core::cards::card::Card;
+
+#[cfg(test)]
+#[path = "../tests/protocol_test.rs"]
+mod tests;
+
+/// Version of the WebSocket protocol a client speaks, negotiated when connecting.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ProtocolVersion {
+    /// The original protocol, used by clients that do not ask for a version.
+    Legacy = 1,
+    /// `ServerEvent` as is, with types in SCREAMING_SNAKE_CASE.
+    V2 = 2,
+}
+
+#[derive(Debug)]
+pub enum ProtocolError {
+    UnsupportedVersion(u32),
+}
+
+impl Error for ProtocolError {}
+
+impl Display for ProtocolError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use ProtocolError::*;
+
+        match self {
+            UnsupportedVersion(version) => {
+                write!(f, "Protocol version {} is not supported", version)
+            }
+        }
+    }
+}
+
+impl ProtocolVersion {
+    pub const LATEST: ProtocolVersion = ProtocolVersion::V2;
+
+    /// Picks the newest version both sides understand, given the newest one the client does.
+    /// Clients that do not say anything are assumed to be legacy ones.
+    pub fn negotiate(client_version: Option<u32>) -> Result<ProtocolVersion, ProtocolError> {
+        match client_version {
+            None | Some(1) => Ok(ProtocolVersion::Legacy),
+            Some(2..) => Ok(ProtocolVersion::LATEST),
+            Some(version) => Err(ProtocolError::UnsupportedVersion(version)),
+        }
+    }
+
+    /// Serializes the event in the shape of this version, with the sequence number as the first field if there is one.
+    pub fn serialize(self, event: &ServerEvent, seq: Option<u64>) -> String {
+        match (self, event) {
+            (ProtocolVersion::Legacy, ServerEvent::DrawMe { next, cards }) => to_json(
+                seq,
+                LegacyEvent::DrawMe {
+                    next,
+                    cards: cards.as_slice(),
+                },
+            ),
+            (ProtocolVersion::Legacy, ServerEvent::PlayCard { who, next, card }) => {
+                to_json(seq, LegacyEvent::PlayCard { who, next, card })
+            }
+            (ProtocolVersion::Legacy, ServerEvent::GainedCards { who, cards }) => to_json(
+                seq,
+                LegacyEvent::GainedCard {
+                    who,
+                    number: *cards,
+                },
+            ),
+            // the rest of the events kept their original shape
+            _ => to_json(seq, event),
+        }
+    }
+}
+
+/// Events whose shape changed since the legacy protocol, as the legacy clients expect them.
+#[derive(Serialize)]
+#[serde(tag = "type")]
+enum LegacyEvent<'a> {
+    #[serde(rename = "DRAW ME")]
+    DrawMe { next: &'a str, cards: &'a [Card] },
+    #[serde(rename = "PLAY CARD")]
+    PlayCard {
+        who: &'a str,
+        next: &'a str,
+        card: &'a Card,
+    },
+    #[serde(rename = "GAINED CARD")]
+    GainedCard { who: &'a str, number: usize },
+}
+
+#[derive(Serialize)]
+struct Sequenced<T: Serialize> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seq: Option<u64>,
+    #[serde(flatten)]
+    event: T,
+}
+
+fn to_json<T: Serialize>(seq: Option<u64>, event: T) -> String {
+    serde_json::to_string(&Sequenced { seq, event }).unwrap() // safe since all events serialize into JSON objects
+}
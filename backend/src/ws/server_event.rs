@@ -0,0 +1,298 @@
+use crate::handler::util::response::ErrBody;
+use crate::session::chat::ChatEntry;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use uno_core::cards::card::Card;
+use uno_core::err::status::CreateStatusError;
+use uno_core::gamestate::game::{Game, GameStatus};
+
+/// Everything the server can tell a client over its WebSocket.
+/// This is the shape of the latest protocol version, older clients get it translated by `ProtocolVersion`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ServerEvent {
+    Status {
+        #[serde(flatten)]
+        view: StatusView,
+        /// The latest messages of the player chat.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        chat: Vec<ChatEntry>,
+    },
+    /// Status of the game as spectators see it.
+    #[serde(rename_all = "camelCase")]
+    SpectatorStatus {
+        #[serde(flatten)]
+        view: PublicStatus,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        chat: Vec<ChatEntry>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        spectator_chat: Vec<ChatEntry>,
+    },
+    Draw {
+        who: String,
+        next: String,
+        cards: usize,
+    },
+    DrawMe {
+        next: String,
+        cards: Vec<Card>,
+    },
+    PlayCard {
+        who: String,
+        next: String,
+        card: Card,
+    },
+    Finish {
+        who: String,
+    },
+    /// The player gave up the game. `next` is None if that finished the game.
+    Forfeit {
+        who: String,
+        next: Option<String>,
+    },
+    Penalty {
+        who: String,
+        cards: Vec<Card>,
+    },
+    GainedCards {
+        who: String,
+        cards: usize,
+    },
+    Chat {
+        who: String,
+        text: String,
+    },
+    /// Message of the spectator chat, which players never get.
+    SpectatorChat {
+        who: String,
+        text: String,
+    },
+    /// Reply to a command the client sent over its WebSocket, carrying the id the client chose for it.
+    Ack {
+        id: Option<String>,
+    },
+    Error {
+        id: Option<String>,
+        error: ErrBody,
+    },
+    /// Message of the server operator to everyone connected, e.g. that the server restarts soon.
+    Notice {
+        text: String,
+    },
+}
+
+/// The whole game as seen by one player.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "status", rename_all = "UPPERCASE")]
+pub enum StatusView {
+    Lobby(LobbyStatus),
+    Running(RunningStatus),
+    Finished(FinishedStatus),
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct LobbyStatus {
+    author: String,
+    you: String,
+    players: Vec<String>,
+    /// Games won by the players so far, across rematches.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    wins: BTreeMap<String, usize>,
+    /// Counter of the changes made to the game, actions can send it as `expectedTurn`.
+    turn: u64,
+    /// Seed commitment of a fair game.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fairness: Option<FairnessSummary>,
+}
+
+/// The commitment to the seed of a fair game, and the seed itself once the game finished.
+/// The shuffles to verify it against are at `GET /game/{gameID}/fairness`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FairnessSummary {
+    commitment: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<String>,
+}
+
+impl FairnessSummary {
+    fn new(game: &Game) -> Option<FairnessSummary> {
+        game.fairness().map(|fairness| FairnessSummary {
+            commitment: fairness.commitment(),
+            seed: game.fairness_proof().map(|proof| proof.seed),
+        })
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct RunningPlayer {
+    name: String,
+    cards: usize,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningStatus {
+    author: String,
+    you: String,
+    current_player: String,
+    players: Vec<RunningPlayer>,
+    finished_players: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    forfeited_players: Vec<String>,
+    cards: Vec<Card>,
+    top_card: Card,
+    is_clockwise: bool,
+    /// Games won by the players so far, across rematches.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    wins: BTreeMap<String, usize>,
+    /// Counter of the changes made to the game, actions can send it as `expectedTurn`.
+    turn: u64,
+    /// Seed commitment of a fair game.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fairness: Option<FairnessSummary>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FinishedStatus {
+    author: String,
+    you: String,
+    players: Vec<String>,
+    /// Games won by the players so far, across rematches.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    wins: BTreeMap<String, usize>,
+    /// Players who agreed to the rematch the author called.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rematch: Option<Vec<String>>,
+    /// Counter of the changes made to the game, actions can send it as `expectedTurn`.
+    turn: u64,
+    /// Seed commitment of a fair game.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fairness: Option<FairnessSummary>,
+}
+
+/// What everyone may know about the game: no hands, only their sizes.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicStatus {
+    status: GameStatus,
+    author: String,
+    players: Vec<RunningPlayer>,
+    current_player: Option<String>,
+    finished_players: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    forfeited_players: Vec<String>,
+    top_card: Option<Card>,
+    is_clockwise: bool,
+    /// Games won by the players so far, across rematches.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    wins: BTreeMap<String, usize>,
+    /// Players who agreed to the rematch the author called.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rematch: Option<Vec<String>>,
+    /// Counter of the changes made to the game, actions can send it as `expectedTurn`.
+    turn: u64,
+    /// Seed commitment of a fair game.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fairness: Option<FairnessSummary>,
+}
+
+impl PublicStatus {
+    pub fn new(game: &Game) -> Result<PublicStatus, CreateStatusError> {
+        let running = game.status() == GameStatus::Running;
+
+        Ok(PublicStatus {
+            status: game.status(),
+            author: find_author_name(game)?,
+            players: running_players(game),
+            current_player: match running {
+                true => Some(get_current_player_name(game)?),
+                false => None,
+            },
+            finished_players: get_finished_player_names(game),
+            forfeited_players: game.forfeited_players().clone(),
+            top_card: match running {
+                true => Some(game.deck().top_discard_card().clone()),
+                false => None,
+            },
+            is_clockwise: game.is_clockwise,
+            wins: game.wins().clone(),
+            rematch: game.rematch_confirmed().cloned(),
+            turn: game.turn(),
+            fairness: FairnessSummary::new(game),
+        })
+    }
+}
+
+impl StatusView {
+    pub fn new(game: &Game, target_player_name: String) -> Result<StatusView, CreateStatusError> {
+        let author = find_author_name(game)?;
+
+        Ok(match game.status() {
+            GameStatus::Lobby => StatusView::Lobby(LobbyStatus {
+                author,
+                you: target_player_name,
+                players: game.players().iter().map(|p| p.name()).collect(),
+                wins: game.wins().clone(),
+                turn: game.turn(),
+                fairness: FairnessSummary::new(game),
+            }),
+            GameStatus::Running => StatusView::Running(RunningStatus {
+                author,
+                you: target_player_name.clone(),
+                current_player: get_current_player_name(game)?,
+                players: running_players(game),
+                finished_players: get_finished_player_names(game),
+                forfeited_players: game.forfeited_players().clone(),
+                cards: match game.find_player(target_player_name) {
+                    None => vec![],
+                    Some(player) => player.cards(),
+                },
+                top_card: game.deck().top_discard_card().clone(),
+                is_clockwise: game.is_clockwise,
+                wins: game.wins().clone(),
+                turn: game.turn(),
+                fairness: FairnessSummary::new(game),
+            }),
+            GameStatus::Finished => StatusView::Finished(FinishedStatus {
+                author,
+                you: target_player_name,
+                players: get_finished_player_names(game),
+                wins: game.wins().clone(),
+                rematch: game.rematch_confirmed().cloned(),
+                turn: game.turn(),
+                fairness: FairnessSummary::new(game),
+            }),
+        })
+    }
+}
+
+fn running_players(game: &Game) -> Vec<RunningPlayer> {
+    game.players()
+        .iter()
+        .map(|player| RunningPlayer {
+            name: player.name(),
+            cards: player.get_card_count(),
+        })
+        .collect()
+}
+
+fn get_finished_player_names(game: &Game) -> Vec<String> {
+    game.get_finished_players()
+        .iter()
+        .map(|p| p.name())
+        .collect()
+}
+
+fn find_author_name(game: &Game) -> Result<String, CreateStatusError> {
+    match game.find_author() {
+        None => Err(CreateStatusError::AuthorNotFound),
+        Some(author) => Ok(author.name()),
+    }
+}
+
+fn get_current_player_name(game: &Game) -> Result<String, CreateStatusError> {
+    match game.get_current_player() {
+        None => Err(CreateStatusError::CurrentPlayerNotFound),
+        Some(player) => Ok(player.name()),
+    }
+}
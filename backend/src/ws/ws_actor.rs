@@ -1,25 +1,130 @@
 use std::time::{Duration, Instant};
 
-use actix::{Actor, ActorContext, AsyncContext, Handler, StreamHandler};
+use actix::{Actor, ActorContext, AsyncContext, Handler, Message, StreamHandler};
 use actix_web_actors::ws;
 
+use super::protocol::ProtocolVersion;
+use super::ws_command::{WSCommand, WSRequest};
+use super::ws_conn::WSConn;
 use super::ws_message::WSMsg;
+use crate::handler::util::idempotency::check_turn;
+use crate::handler::util::response::{ErrBody, ErrMsg, ErrorResponse, TypedErrMsg};
+use crate::repo::game_repo::SharedSession;
+use crate::session::ai_driver::drive_ai;
+use crate::session::spectators::SpectateError;
+use uno_core::gamestate::command::Command;
 
 /// How often heartbeat pings are sent
 const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
 /// How long before lack of client response causes a timeout
 const CLIENT_TIMEOUT: Duration = Duration::from_secs(10);
 
-/// Actor representing a WebSocket connection
-#[derive(Debug, Eq, PartialEq, Clone)]
+/// Actor representing a WebSocket connection of a player
 pub struct WSActor {
     /// Heartbeat to keep the connection alive
     hb: Instant,
+    /// Game the connection belongs to, commands from the client are applied to it
+    session: SharedSession,
+    player_name: String,
+    /// Shape of the messages the client understands
+    version: ProtocolVersion,
+    /// Spectators only watch, they cannot send any commands
+    is_spectator: bool,
 }
 
+/// Makes the actor close its connection
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Close;
+
 impl WSActor {
-    pub fn new() -> Self {
-        Self { hb: Instant::now() }
+    pub fn new(
+        session: SharedSession,
+        player_name: String,
+        version: ProtocolVersion,
+        is_spectator: bool,
+    ) -> Self {
+        Self {
+            hb: Instant::now(),
+            session,
+            player_name,
+            version,
+            is_spectator,
+        }
+    }
+
+    /// Applies a command sent by the client on behalf of the connected player, returns the reply to it.
+    fn handle_command(&self, text: &str) -> WSMsg {
+        let request: WSRequest = match serde_json::from_str(text) {
+            Err(error) => {
+                let error = ErrBody::Typed(TypedErrMsg::new("INVALID_COMMAND", error));
+                return WSMsg::command_error(WSRequest::id_of(text), error);
+            }
+            Ok(request) => request,
+        };
+
+        // spectators can only chat among themselves
+        if self.is_spectator && !matches!(request.command, WSCommand::Chat { .. }) {
+            return WSMsg::command_error(request.id, SpectateError::CannotPlay.error_body());
+        }
+
+        let result = match self.session.lock() {
+            Err(_) => Err(ErrBody::Plain(ErrMsg::new_from_scratch(
+                "Cannot obtain lock on game",
+            ))),
+            Ok(mut session) => {
+                let player = self.player_name.clone();
+                match request.command {
+                    WSCommand::PlayCard {
+                        card,
+                        new_color,
+                        said_uno,
+                        expected_turn,
+                    } => check_turn(&session, expected_turn)
+                        .map_err(|error| error.error_body())
+                        .and_then(|()| {
+                            session
+                                .apply(Command::PlayCard {
+                                    player,
+                                    card,
+                                    new_color,
+                                    said_uno,
+                                })
+                                .map_err(|error| error.error_body())
+                        }),
+                    WSCommand::Draw { expected_turn } => check_turn(&session, expected_turn)
+                        .map_err(|error| error.error_body())
+                        .and_then(|()| {
+                            session
+                                .apply(Command::DrawCards { player })
+                                .map_err(|error| error.error_body())
+                        }),
+                    WSCommand::CallUno => session
+                        .apply(Command::CallUno { player })
+                        .map_err(|error| error.error_body()),
+                    WSCommand::Forfeit => session
+                        .apply(Command::Forfeit { player })
+                        .map_err(|error| error.error_body()),
+                    WSCommand::Ready => session
+                        .apply(Command::ConfirmRematch { player })
+                        .map_err(|error| error.error_body()),
+                    WSCommand::Chat { text } if self.is_spectator => session
+                        .spectator_chat(&player, text)
+                        .map_err(|error| error.error_body()),
+                    WSCommand::Chat { text } => session
+                        .chat(&player, text)
+                        .map_err(|error| error.error_body()),
+                }
+            }
+        };
+
+        match result {
+            Err(error) => WSMsg::command_error(request.id, error),
+            Ok(()) => {
+                drive_ai(self.session.clone());
+                WSMsg::ack(request.id)
+            }
+        }
     }
 
     /// sends ping to client every HEARTBEAT_INTERVAL, checks heartbeats from client
@@ -44,7 +149,16 @@ impl Handler<WSMsg> for WSActor {
     type Result = ();
 
     fn handle(&mut self, msg: WSMsg, ctx: &mut Self::Context) -> Self::Result {
-        ctx.text(msg.msg)
+        ctx.text(msg.to_json(self.version))
+    }
+}
+
+impl Handler<Close> for WSActor {
+    type Result = ();
+
+    fn handle(&mut self, _: Close, ctx: &mut Self::Context) -> Self::Result {
+        ctx.close(None);
+        ctx.stop();
     }
 }
 
@@ -54,6 +168,19 @@ impl Actor for WSActor {
     fn started(&mut self, ctx: &mut Self::Context) {
         self.hb(ctx);
     }
+
+    /// Forgets the connection, so that a closed tab or a timed out heartbeat no longer counts as connected
+    fn stopped(&mut self, ctx: &mut Self::Context) {
+        let connection = WSConn::of(ctx.address());
+        if let Ok(mut session) = self.session.lock() {
+            match self.is_spectator {
+                true => session
+                    .spectators_mut()
+                    .disconnected(&self.player_name, &connection),
+                false => session.disconnected(&self.player_name, &connection),
+            }
+        }
+    }
 }
 
 /// Handler for messages coming from the client
@@ -67,7 +194,10 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WSActor {
             Ok(ws::Message::Pong(_)) => {
                 self.hb = Instant::now();
             }
-            Ok(ws::Message::Text(_)) => (),
+            Ok(ws::Message::Text(text)) => {
+                let reply = self.handle_command(&text);
+                ctx.text(reply.to_json(self.version));
+            }
             Ok(ws::Message::Binary(_)) => (),
             Ok(ws::Message::Close(reason)) => {
                 ctx.close(reason);
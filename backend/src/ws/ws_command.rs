@@ -0,0 +1,59 @@
+use serde::Deserialize;
+use uno_core::cards::card::{Card, CardColor};
+
+#[cfg(test)]
+#[path = "../tests/ws_command_test.rs"]
+mod tests;
+
+/// Action a client requests over its WebSocket. The sender is always the player the connection belongs to.
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum WSCommand {
+    #[serde(rename = "PLAY CARD")]
+    PlayCard {
+        card: Card,
+        #[serde(rename = "newColor", default)]
+        new_color: Option<CardColor>,
+        #[serde(rename = "saidUno", default)]
+        said_uno: bool,
+        /// Rejects the command if the game is no longer at this turn.
+        #[serde(rename = "expectedTurn", default)]
+        expected_turn: Option<u64>,
+    },
+    #[serde(rename = "DRAW")]
+    Draw {
+        #[serde(rename = "expectedTurn", default)]
+        expected_turn: Option<u64>,
+    },
+    #[serde(rename = "UNO")]
+    CallUno,
+    #[serde(rename = "CHAT")]
+    Chat { text: String },
+    #[serde(rename = "FORFEIT")]
+    Forfeit,
+    /// Agrees to the rematch the author called.
+    #[serde(rename = "READY")]
+    Ready,
+}
+
+/// A `WSCommand` together with an optional id, which is repeated in the reply to the command.
+#[derive(Deserialize, Debug)]
+pub struct WSRequest {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(flatten)]
+    pub command: WSCommand,
+}
+
+#[derive(Deserialize)]
+struct WSRequestId {
+    #[serde(default)]
+    id: Option<String>,
+}
+
+impl WSRequest {
+    /// Returns the id of a request even if the request itself is invalid, so that the error can be correlated.
+    pub fn id_of(text: &str) -> Option<String> {
+        serde_json::from_str::<WSRequestId>(text).ok()?.id
+    }
+}
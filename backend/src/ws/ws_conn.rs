@@ -1,24 +1,56 @@
 use actix::Addr;
 use actix_web::{web, Error, HttpRequest, HttpResponse};
-use actix_web_actors::ws;
+use actix_web_actors::ws::{self, WebsocketContext};
 
-use super::{ws_actor::WSActor, ws_message::WSMsg};
+use crate::repo::game_repo::SharedSession;
+
+use super::{
+    protocol::ProtocolVersion,
+    ws_actor::{Close, WSActor},
+    ws_message::WSMsg,
+};
+
+#[cfg(test)]
+#[path = "../tests/ws_conn_test.rs"]
+mod tests;
+
+/// Subprotocol clients offer next to their token in `Sec-WebSocket-Protocol`, the server accepts only this one.
+pub static WS_PROTOCOL: &str = "uno";
 
 /// WebSocket connection to which it is possible to send messages
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Eq, PartialEq, Clone)]
 pub struct WSConn {
     addr: Addr<WSActor>,
 }
 
 impl WSConn {
-    /// Create a new WebSocket connection from a request
-    pub fn new(request: &HttpRequest, stream: web::Payload) -> Result<(Self, HttpResponse), Error> {
-        let (addr, response) = ws::start_with_addr(WSActor::new(), request, stream)?;
-        Ok((Self { addr }, response))
+    /// Create a new WebSocket connection of the player from a request
+    pub fn new(
+        request: &HttpRequest,
+        stream: web::Payload,
+        session: SharedSession,
+        player_name: String,
+        version: ProtocolVersion,
+        is_spectator: bool,
+    ) -> Result<(Self, HttpResponse), Error> {
+        let actor = WSActor::new(session, player_name, version, is_spectator);
+        let mut response = ws::handshake_with_protocols(request, &[WS_PROTOCOL])?;
+        let (addr, out_stream) = WebsocketContext::create_with_addr(actor, stream);
+        Ok((Self { addr }, response.streaming(out_stream)))
+    }
+
+    /// The connection the actor serves, to tell it apart from a newer connection of the same player
+    pub(super) fn of(addr: Addr<WSActor>) -> Self {
+        Self { addr }
     }
 
     /// Send a WebSocket message
     pub fn send(&self, msg: WSMsg) {
         self.addr.do_send(msg);
     }
+
+    /// Close the connection from the server's side
+    pub fn close(&self) {
+        self.addr.do_send(Close);
+    }
 }
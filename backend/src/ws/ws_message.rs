@@ -1,53 +1,154 @@
-use crate::cards::card::Card;
-use crate::err::status::CreateStatusError;
-use crate::gamestate::game::{Game, GameStatus};
-use crate::ws::ws_structs::draw::{DrawMeWSMessage, DrawWSMessage};
-use crate::ws::ws_structs::finish::FinishWSMessage;
-use crate::ws::ws_structs::gained_cards::GainedCardsWSMessage;
-use crate::ws::ws_structs::penalty::PenaltyWSMessage;
-use crate::ws::ws_structs::play_card::PlayCardWSMessage;
-use crate::ws::ws_structs::status::{
-    FinishedStatusWSMessage, LobbyStatusWSMessage, RunningStatusWSMessage,
-};
-use crate::ws::ws_structs::WsMessageWrapper;
+use crate::handler::util::response::ErrBody;
+use crate::session::chat::ChatEntry;
+use crate::ws::protocol::ProtocolVersion;
+use crate::ws::server_event::{PublicStatus, ServerEvent, StatusView};
 use actix::Message;
+use uno_core::cards::card::Card;
+use uno_core::err::status::CreateStatusError;
+use uno_core::gamestate::event::Event;
+use uno_core::gamestate::game::Game;
 
 /// WebSocket message that can be sent to a WebSocket connection
-#[derive(Message, Clone)]
+#[derive(Message, Clone, Debug, PartialEq)]
 #[rtype(result = "()")]
 pub struct WSMsg {
-    pub msg: String,
+    pub seq: Option<u64>,
+    pub event: ServerEvent,
 }
 
 impl WSMsg {
-    fn new(msg: String) -> Self {
-        Self { msg }
+    fn new(event: ServerEvent) -> Self {
+        Self { seq: None, event }
+    }
+
+    pub fn with_seq(self, seq: u64) -> Self {
+        Self {
+            seq: Some(seq),
+            ..self
+        }
+    }
+
+    /// Serializes the message for a client speaking the given protocol version.
+    pub fn to_json(&self, version: ProtocolVersion) -> String {
+        version.serialize(&self.event, self.seq)
     }
 
     pub fn status(game: &Game, target_player_name: String) -> Result<Self, CreateStatusError> {
-        let msg = match game.status() {
-            GameStatus::Lobby => {
-                LobbyStatusWSMessage::new(game, target_player_name)?.ws_serialize()
+        let status = StatusView::new(game, target_player_name)?;
+        Ok(Self::new(ServerEvent::Status {
+            view: status,
+            chat: vec![],
+        }))
+    }
+    pub fn public_status(game: &Game) -> Result<Self, CreateStatusError> {
+        Ok(Self::new(ServerEvent::SpectatorStatus {
+            view: PublicStatus::new(game)?,
+            chat: vec![],
+            spectator_chat: vec![],
+        }))
+    }
+
+    /// Attaches the player chat history to a status, other messages are returned unchanged.
+    pub fn with_chat(mut self, history: Vec<ChatEntry>) -> Self {
+        match &mut self.event {
+            ServerEvent::Status { chat, .. } | ServerEvent::SpectatorStatus { chat, .. } => {
+                *chat = history
             }
-            GameStatus::Running => {
-                RunningStatusWSMessage::new(game, target_player_name)?.ws_serialize()
+            _ => (),
+        }
+        self
+    }
+
+    /// Attaches the spectator chat history to a spectator status, other messages are returned unchanged.
+    pub fn with_spectator_chat(mut self, history: Vec<ChatEntry>) -> Self {
+        if let ServerEvent::SpectatorStatus { spectator_chat, .. } = &mut self.event {
+            *spectator_chat = history;
+        }
+        self
+    }
+
+    /// Turns a game event into the message spectators should get, if any. Spectators never see any cards in hands.
+    /// Expects the game to already be in the state after the event.
+    pub fn for_spectator(game: &Game, event: &Event) -> Result<Option<Self>, CreateStatusError> {
+        use Event::*;
+
+        let msg = match event {
+            PlayerJoined { .. }
+            | PlayerLeft { .. }
+            | AuthorChanged { .. }
+            | GameStarted
+            | GameFinished
+            | RematchProposed { .. }
+            | RematchConfirmed { .. }
+            | ReturnedToLobby => Self::public_status(game)?,
+            CardPlayed { who, next, card } => {
+                Self::play_card(who.clone(), next.clone(), card.clone())
             }
-            GameStatus::Finished => {
-                FinishedStatusWSMessage::new(game, target_player_name)?.ws_serialize()
+            CardsDrawn { who, next, cards } => Self::draw(who.clone(), next.clone(), cards.len()),
+            Penalized { who, cards } => Self::gained_cards(who.clone(), cards.len()),
+            PlayerFinished { who } => Self::finish(who.clone()),
+            PlayerForfeited { who, next } => Self::forfeit(who.clone(), next.clone()),
+            UnoCalled { .. } => return Ok(None),
+        };
+
+        Ok(Some(msg))
+    }
+
+    /// Turns a game event into the message the recipient should get, if any.
+    /// Expects the game to already be in the state after the event.
+    pub fn for_event(
+        game: &Game,
+        event: &Event,
+        recipient_name: &str,
+    ) -> Result<Option<Self>, CreateStatusError> {
+        use Event::*;
+
+        let msg = match event {
+            PlayerJoined { .. }
+            | PlayerLeft { .. }
+            | AuthorChanged { .. }
+            | GameStarted
+            | GameFinished
+            | RematchProposed { .. }
+            | RematchConfirmed { .. }
+            | ReturnedToLobby => Self::status(game, recipient_name.to_string())?,
+            CardPlayed { who, next, card } => {
+                Self::play_card(who.clone(), next.clone(), card.clone())
+            }
+            CardsDrawn { who, next, cards } if who == recipient_name => {
+                Self::draw_me(next.clone(), cards.clone())
             }
+            CardsDrawn { who, next, cards } => Self::draw(who.clone(), next.clone(), cards.len()),
+            Penalized { who, cards } if who == recipient_name => {
+                Self::penalty(who.clone(), cards.clone())
+            }
+            Penalized { who, cards } => Self::gained_cards(who.clone(), cards.len()),
+            PlayerFinished { who } => Self::finish(who.clone()),
+            PlayerForfeited { who, next } => Self::forfeit(who.clone(), next.clone()),
+            // not part of the protocol the clients understand
+            UnoCalled { .. } => return Ok(None),
         };
 
-        Ok(Self::new(msg))
+        Ok(Some(msg))
+    }
+
+    pub fn notice(text: String) -> Self {
+        Self::new(ServerEvent::Notice { text })
     }
 
     pub fn draw(drawing_player_name: String, next_player_name: String, cards_drawn: usize) -> Self {
-        let msg = DrawWSMessage::new(drawing_player_name, next_player_name, cards_drawn);
-        Self::new(msg.ws_serialize())
+        Self::new(ServerEvent::Draw {
+            who: drawing_player_name,
+            next: next_player_name,
+            cards: cards_drawn,
+        })
     }
 
     pub fn draw_me(next_player_name: String, cards_drawn: Vec<Card>) -> Self {
-        let msg = DrawMeWSMessage::new(next_player_name, cards_drawn);
-        Self::new(msg.ws_serialize())
+        Self::new(ServerEvent::DrawMe {
+            next: next_player_name,
+            cards: cards_drawn,
+        })
     }
 
     pub fn play_card(
@@ -55,22 +156,62 @@ impl WSMsg {
         next_player_name: String,
         card_drawn: Card,
     ) -> Self {
-        let msg = PlayCardWSMessage::new(playing_player_name, next_player_name, card_drawn);
-        Self::new(msg.ws_serialize())
+        Self::new(ServerEvent::PlayCard {
+            who: playing_player_name,
+            next: next_player_name,
+            card: card_drawn,
+        })
     }
 
     pub fn finish(finished_player_name: String) -> Self {
-        let msg = FinishWSMessage::new(finished_player_name);
-        Self::new(msg.ws_serialize())
+        Self::new(ServerEvent::Finish {
+            who: finished_player_name,
+        })
+    }
+
+    pub fn forfeit(forfeited_player_name: String, next_player_name: Option<String>) -> Self {
+        Self::new(ServerEvent::Forfeit {
+            who: forfeited_player_name,
+            next: next_player_name,
+        })
     }
 
     pub fn penalty(penalized_player_name: String, gained_cards: Vec<Card>) -> Self {
-        let msg = PenaltyWSMessage::new(penalized_player_name, gained_cards);
-        Self::new(msg.ws_serialize())
+        Self::new(ServerEvent::Penalty {
+            who: penalized_player_name,
+            cards: gained_cards,
+        })
+    }
+
+    pub fn chat(author_name: String, text: String) -> Self {
+        Self::new(ServerEvent::Chat {
+            who: author_name,
+            text,
+        })
+    }
+
+    pub fn spectator_chat(author_name: String, text: String) -> Self {
+        Self::new(ServerEvent::SpectatorChat {
+            who: author_name,
+            text,
+        })
+    }
+
+    pub fn ack(command_id: Option<String>) -> Self {
+        Self::new(ServerEvent::Ack { id: command_id })
+    }
+
+    pub fn command_error(command_id: Option<String>, error: ErrBody) -> Self {
+        Self::new(ServerEvent::Error {
+            id: command_id,
+            error,
+        })
     }
 
     pub fn gained_cards(penalized_player_name: String, gained_cards_count: usize) -> Self {
-        let msg = GainedCardsWSMessage::new(penalized_player_name, gained_cards_count);
-        Self::new(msg.ws_serialize())
+        Self::new(ServerEvent::GainedCards {
+            who: penalized_player_name,
+            cards: gained_cards_count,
+        })
     }
 }
@@ -0,0 +1,47 @@
+//! Verifies the shuffles of a fair game, from the proof the server revealed when it finished.
+//!
+//! Usage: `verify_shuffle [proof.json]`, the proof is read from standard input without a file.
+
+use std::io::Read;
+use std::process::ExitCode;
+use uno_core::gamestate::fairness::FairnessProof;
+
+fn main() -> ExitCode {
+    let json = match std::env::args().nth(1) {
+        Some(path) => std::fs::read_to_string(path),
+        None => {
+            let mut json = String::new();
+            std::io::stdin().read_to_string(&mut json).map(|_| json)
+        }
+    };
+    let json = match json {
+        Ok(json) => json,
+        Err(error) => {
+            eprintln!("Cannot read the proof: {}", error);
+            return ExitCode::from(2);
+        }
+    };
+
+    let proof: FairnessProof = match serde_json::from_str(&json) {
+        Ok(proof) => proof,
+        Err(error) => {
+            eprintln!("Not a fairness proof: {}", error);
+            return ExitCode::from(2);
+        }
+    };
+
+    match proof.verify() {
+        Ok(()) => {
+            println!(
+                "Valid: all {} shuffles derive from the committed seed {}",
+                proof.shuffles.len(),
+                proof.commitment
+            );
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            println!("Invalid: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
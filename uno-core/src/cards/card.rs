@@ -4,7 +4,9 @@ use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Eq, PartialEq, Clone)]
+/// A card of the deck. Wild cards have the black color until they are played and get the chosen one.
+/// Cards are ordered only so that a shuffle can start from the same order every time.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
 pub struct Card {
     pub color: CardColor,
     pub symbol: CardSymbol,
@@ -199,7 +201,7 @@ impl Display for Card {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum CardColor {
     Red,
@@ -223,7 +225,7 @@ impl Display for CardColor {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Clone)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum CardSymbol {
     Value(#[serde(skip)] i8),
@@ -1,24 +1,41 @@
 use crate::cards::card::{Card, CardColor, CardSymbol};
 use crate::cards::random_color;
-use rand::seq::SliceRandom;
+use crate::gamestate::fairness::shuffle;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha20Rng;
 
+/// The draw pile together with the discard pile, whose top card is the one players play against.
 #[derive(Debug, Clone)]
 pub struct Deck {
     draw_pile: Vec<Card>,
     discard_pile: Vec<Card>,
+    rng: ChaCha20Rng,
+    /// The draw pile after every shuffle in the order of drawing, kept only in fair games.
+    shuffles: Option<Vec<Vec<Card>>>,
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Deck {
     pub fn new() -> Deck {
-        let mut draw_pile = Vec::new();
+        Self::shuffled(ChaCha20Rng::from_entropy(), None)
+    }
 
-        insert_number_cards(&mut draw_pile);
-        insert_colored_symbol_cards(&mut draw_pile);
-        insert_black_symbol_cards(&mut draw_pile);
+    /// A deck shuffled only by the given generator, which remembers every shuffle so that it can be verified.
+    pub fn new_fair(rng: ChaCha20Rng) -> Deck {
+        Self::shuffled(rng, Some(Vec::new()))
+    }
 
+    fn shuffled(rng: ChaCha20Rng, shuffles: Option<Vec<Vec<Card>>>) -> Deck {
         let mut deck = Deck {
-            draw_pile,
+            draw_pile: full_deck(),
             discard_pile: Vec::new(),
+            rng,
+            shuffles,
         };
 
         deck.shuffle_draw_pile();
@@ -34,8 +51,28 @@ impl Deck {
         deck
     }
 
+    /// Creates a deck with the draw pile in the given order, the first card being drawn first.
+    pub fn with_piles(mut draw_pile: Vec<Card>, top_card: Card) -> Deck {
+        draw_pile.reverse();
+        Deck {
+            draw_pile,
+            discard_pile: vec![top_card],
+            rng: ChaCha20Rng::from_entropy(),
+            shuffles: None,
+        }
+    }
+
     fn shuffle_draw_pile(&mut self) {
-        self.draw_pile.shuffle(&mut rand::thread_rng());
+        shuffle(&mut self.draw_pile, &mut self.rng);
+
+        if let Some(shuffles) = self.shuffles.as_mut() {
+            shuffles.push(self.draw_pile.iter().rev().cloned().collect());
+        }
+    }
+
+    /// The draw pile after every shuffle, the card drawn first being the first one. None unless the deck is fair.
+    pub fn shuffles(&self) -> Option<&Vec<Vec<Card>>> {
+        self.shuffles.as_ref()
     }
 
     fn switching_piles_wont_move_card(&self) -> bool {
@@ -76,6 +113,12 @@ impl Deck {
         self.discard_pile.push(last_discarded_card);
     }
 
+    /// Shuffles the cards back into the draw pile, e.g. the hand of a player who gave up.
+    pub fn return_cards(&mut self, mut cards: Vec<Card>) {
+        self.draw_pile.append(&mut cards);
+        self.shuffle_draw_pile();
+    }
+
     pub fn play(&mut self, card: Card) {
         self.discard_pile.push(card);
     }
@@ -84,19 +127,26 @@ impl Deck {
         self.discard_pile.last().unwrap() // safe since draw pile should always have at least one card
     }
 
-    // Used in a test
-    #[allow(dead_code)]
     pub fn draw_pile_size(&self) -> usize {
         self.draw_pile.len()
     }
 
-    // Used in a test
-    #[allow(dead_code)]
     pub fn discard_pile_size(&self) -> usize {
         self.discard_pile.len()
     }
 }
 
+/// All 108 cards of the deck, unshuffled.
+pub(crate) fn full_deck() -> Vec<Card> {
+    let mut cards = Vec::new();
+
+    insert_number_cards(&mut cards);
+    insert_colored_symbol_cards(&mut cards);
+    insert_black_symbol_cards(&mut cards);
+
+    cards
+}
+
 // use of Card::new(...).unwrap() in this function is safe at the time of this commit
 fn insert_number_cards(card_stack: &mut Vec<Card>) {
     for color in CardColor::non_black_iter() {
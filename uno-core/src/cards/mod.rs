@@ -1,3 +1,5 @@
+//! Cards of the UNO deck and the deck itself, made of a draw pile and a discard pile.
+
 use crate::cards::card::CardColor;
 use rand::Rng;
 
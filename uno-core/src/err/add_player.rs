@@ -1,11 +1,13 @@
-use crate::err::status::CreateStatusError;
+use crate::gamestate::MAX_PLAYERS;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum AddPlayerError {
     AlreadyExists(String),
-    CreateStatusError(CreateStatusError),
+    NotAcceptingPlayers,
+    Kicked(String),
+    LobbyFull,
 }
 
 impl Error for AddPlayerError {}
@@ -18,13 +20,13 @@ impl Display for AddPlayerError {
             AlreadyExists(name) => {
                 write!(f, "Player of name {} already exists in this game.", name)
             }
-            CreateStatusError(err) => write!(f, "{}", err),
+            NotAcceptingPlayers => write!(f, "Game does not accept any new players."),
+            Kicked(name) => write!(f, "Player {} was kicked from this game.", name),
+            LobbyFull => write!(
+                f,
+                "Game is full, it can have at most {} players.",
+                MAX_PLAYERS
+            ),
         }
     }
 }
-
-impl From<CreateStatusError> for AddPlayerError {
-    fn from(e: CreateStatusError) -> Self {
-        AddPlayerError::CreateStatusError(e)
-    }
-}
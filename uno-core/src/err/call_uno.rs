@@ -0,0 +1,39 @@
+use crate::err::player_exist::PlayerExistError;
+use crate::err::player_turn::PlayerTurnError;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+pub enum CallUnoError {
+    PlayerTurnError(PlayerTurnError),
+    PlayerExistError(PlayerExistError),
+    GameNotRunning(String),
+    CannotCallUno,
+}
+
+impl Error for CallUnoError {}
+
+impl Display for CallUnoError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use CallUnoError::*;
+
+        match self {
+            PlayerTurnError(err) => write!(f, "{}", err),
+            PlayerExistError(err) => write!(f, "{}", err),
+            GameNotRunning(id) => write!(f, "The game with id '{}' is not running", id),
+            CannotCallUno => write!(f, "UNO! can only be said with two cards in hand"),
+        }
+    }
+}
+
+impl From<PlayerTurnError> for CallUnoError {
+    fn from(e: PlayerTurnError) -> Self {
+        CallUnoError::PlayerTurnError(e)
+    }
+}
+
+impl From<PlayerExistError> for CallUnoError {
+    fn from(e: PlayerExistError) -> Self {
+        CallUnoError::PlayerExistError(e)
+    }
+}
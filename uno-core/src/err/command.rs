@@ -0,0 +1,117 @@
+use crate::err::add_player::AddPlayerError;
+use crate::err::call_uno::CallUnoError;
+use crate::err::draw_cards::PlayerDrawError;
+use crate::err::fairness::FairnessError;
+use crate::err::force_finish::ForceFinishError;
+use crate::err::forfeit::ForfeitError;
+use crate::err::game_start::GameStartError;
+use crate::err::lobby::LobbyError;
+use crate::err::play_card::PlayCardError;
+use crate::err::rematch::RematchError;
+use crate::err::status::CreateStatusError;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Reason why a `Command` was rejected.
+#[derive(Debug)]
+pub enum CommandError {
+    AddPlayer(AddPlayerError),
+    Start(GameStartError),
+    Lobby(LobbyError),
+    PlayCard(PlayCardError),
+    DrawCards(PlayerDrawError),
+    CallUno(CallUnoError),
+    Forfeit(ForfeitError),
+    Rematch(RematchError),
+    Fairness(FairnessError),
+    ForceFinish(ForceFinishError),
+    CreateStatusError(CreateStatusError),
+}
+
+impl Error for CommandError {}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use CommandError::*;
+
+        match self {
+            AddPlayer(err) => write!(f, "{}", err),
+            Start(err) => write!(f, "{}", err),
+            Lobby(err) => write!(f, "{}", err),
+            PlayCard(err) => write!(f, "{}", err),
+            DrawCards(err) => write!(f, "{}", err),
+            CallUno(err) => write!(f, "{}", err),
+            Forfeit(err) => write!(f, "{}", err),
+            Rematch(err) => write!(f, "{}", err),
+            Fairness(err) => write!(f, "{}", err),
+            ForceFinish(err) => write!(f, "{}", err),
+            CreateStatusError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<AddPlayerError> for CommandError {
+    fn from(e: AddPlayerError) -> Self {
+        CommandError::AddPlayer(e)
+    }
+}
+
+impl From<GameStartError> for CommandError {
+    fn from(e: GameStartError) -> Self {
+        CommandError::Start(e)
+    }
+}
+
+impl From<LobbyError> for CommandError {
+    fn from(e: LobbyError) -> Self {
+        CommandError::Lobby(e)
+    }
+}
+
+impl From<PlayCardError> for CommandError {
+    fn from(e: PlayCardError) -> Self {
+        CommandError::PlayCard(e)
+    }
+}
+
+impl From<PlayerDrawError> for CommandError {
+    fn from(e: PlayerDrawError) -> Self {
+        CommandError::DrawCards(e)
+    }
+}
+
+impl From<CallUnoError> for CommandError {
+    fn from(e: CallUnoError) -> Self {
+        CommandError::CallUno(e)
+    }
+}
+
+impl From<ForfeitError> for CommandError {
+    fn from(e: ForfeitError) -> Self {
+        CommandError::Forfeit(e)
+    }
+}
+
+impl From<RematchError> for CommandError {
+    fn from(e: RematchError) -> Self {
+        CommandError::Rematch(e)
+    }
+}
+
+impl From<CreateStatusError> for CommandError {
+    fn from(e: CreateStatusError) -> Self {
+        CommandError::CreateStatusError(e)
+    }
+}
+
+impl From<FairnessError> for CommandError {
+    fn from(e: FairnessError) -> Self {
+        CommandError::Fairness(e)
+    }
+}
+
+impl From<ForceFinishError> for CommandError {
+    fn from(e: ForceFinishError) -> Self {
+        CommandError::ForceFinish(e)
+    }
+}
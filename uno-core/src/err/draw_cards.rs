@@ -1,4 +1,3 @@
-use crate::err::ai::AiError;
 use crate::err::player_exist::PlayerExistError;
 use crate::err::player_turn::PlayerTurnError;
 use crate::err::status::CreateStatusError;
@@ -9,7 +8,7 @@ use std::fmt::{Display, Formatter};
 pub enum PlayerDrawError {
     TurnError(PlayerTurnError),
     PlayerExistError(PlayerExistError),
-    ChainedAiError,
+    GameNotRunning(String),
     CanPlayInstead,
     CreateStatusError(CreateStatusError),
 }
@@ -24,8 +23,8 @@ impl Display for PlayerDrawError {
             TurnError(err) => write!(f, "{}", err),
             PlayerExistError(err) => write!(f, "{}", err),
             CreateStatusError(err) => write!(f, "{}", err),
+            GameNotRunning(id) => write!(f, "The game with id '{}' is not running", id),
             CanPlayInstead => write!(f, "No need to draw, playing a card is possible"),
-            ChainedAiError => write!(f, "Error occurred when AI played"),
         }
     }
 }
@@ -42,12 +41,6 @@ impl From<PlayerExistError> for PlayerDrawError {
     }
 }
 
-impl From<AiError> for PlayerDrawError {
-    fn from(_e: AiError) -> Self {
-        PlayerDrawError::ChainedAiError
-    }
-}
-
 impl From<CreateStatusError> for PlayerDrawError {
     fn from(e: CreateStatusError) -> Self {
         PlayerDrawError::CreateStatusError(e)
@@ -0,0 +1,30 @@
+use crate::gamestate::fairness::MAX_ENTROPY_LENGTH;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum FairnessError {
+    NotFair,
+    NotInLobby,
+    NoSuchPlayer(String),
+    InvalidEntropy,
+}
+
+impl Error for FairnessError {}
+
+impl Display for FairnessError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use FairnessError::*;
+
+        match self {
+            NotFair => write!(f, "The game was not created in the fair mode"),
+            NotInLobby => write!(f, "Entropy can only be added before the game starts"),
+            NoSuchPlayer(name) => write!(f, "Player of name {} does not exist!", name),
+            InvalidEntropy => write!(
+                f,
+                "Entropy must have 1 to {} characters",
+                MAX_ENTROPY_LENGTH
+            ),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ForceFinishError {
+    GameNotRunning(String),
+}
+
+impl Error for ForceFinishError {}
+
+impl Display for ForceFinishError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use ForceFinishError::*;
+
+        match self {
+            GameNotRunning(id) => write!(f, "The game with id '{}' is not running", id),
+        }
+    }
+}
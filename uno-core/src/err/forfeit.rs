@@ -0,0 +1,30 @@
+use crate::err::player_exist::PlayerExistError;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+pub enum ForfeitError {
+    PlayerExistError(PlayerExistError),
+    GameNotRunning(String),
+    AlreadyOut(String),
+}
+
+impl Error for ForfeitError {}
+
+impl Display for ForfeitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use ForfeitError::*;
+
+        match self {
+            PlayerExistError(err) => write!(f, "{}", err),
+            GameNotRunning(id) => write!(f, "The game with id '{}' is not running", id),
+            AlreadyOut(name) => write!(f, "Player {} has already finished or forfeited", name),
+        }
+    }
+}
+
+impl From<PlayerExistError> for ForfeitError {
+    fn from(e: PlayerExistError) -> Self {
+        ForfeitError::PlayerExistError(e)
+    }
+}
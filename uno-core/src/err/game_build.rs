@@ -0,0 +1,28 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum GameBuildError {
+    NoPlayers,
+    DuplicatePlayer(String),
+    NoSuchPlayer(String),
+    InvalidActiveCards(String),
+    /// The set cards leave too few in the deck for the players without a hand.
+    NotEnoughCards,
+}
+
+impl Error for GameBuildError {}
+
+impl Display for GameBuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use GameBuildError::*;
+
+        match self {
+            NoPlayers => write!(f, "The game needs at least one player."),
+            DuplicatePlayer(name) => write!(f, "Player of name {} is listed twice.", name),
+            NoSuchPlayer(name) => write!(f, "Player of name {} is not part of the game.", name),
+            InvalidActiveCards(reason) => write!(f, "Invalid active cards: {}", reason),
+            NotEnoughCards => write!(f, "Not enough cards are left to deal to the players."),
+        }
+    }
+}
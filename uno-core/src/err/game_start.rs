@@ -1,5 +1,3 @@
-use crate::err::ai::AiError;
-use crate::err::status::CreateStatusError;
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
 
@@ -7,8 +5,8 @@ use std::fmt::{Debug, Display, Formatter};
 pub enum GameStartError {
     DeckEmptyWhenStartingGame,
     GameAlreadyStarted,
-    CreateStatusError(CreateStatusError),
-    ChainedAiError,
+    NotAuthor(String),
+    FairGameNotInLobby,
 }
 
 impl Error for GameStartError {}
@@ -22,20 +20,11 @@ impl Display for GameStartError {
                 write!(f, "Impossible: deck empty when starting game")
             }
             GameAlreadyStarted => write!(f, "Cannot start an already running game"),
-            CreateStatusError(err) => write!(f, "{}", err),
-            ChainedAiError => write!(f, "Error occurred when AI played"),
+            NotAuthor(_) => write!(f, "This action can be done only by the author of the game"),
+            FairGameNotInLobby => write!(
+                f,
+                "A fair game can only start from the lobby, call a rematch to get back to it"
+            ),
         }
     }
 }
-
-impl From<CreateStatusError> for GameStartError {
-    fn from(e: CreateStatusError) -> Self {
-        GameStartError::CreateStatusError(e)
-    }
-}
-
-impl From<AiError> for GameStartError {
-    fn from(_: AiError) -> Self {
-        GameStartError::ChainedAiError
-    }
-}
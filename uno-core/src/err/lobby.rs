@@ -0,0 +1,40 @@
+use crate::gamestate::MAX_PLAYERS;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum LobbyError {
+    NotAuthor(String),
+    NotInLobby,
+    NoSuchPlayer(String),
+    CannotKickSelf,
+    NotAnAi(String),
+    NotHuman(String),
+    LobbyFull,
+}
+
+impl Error for LobbyError {}
+
+impl Display for LobbyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use LobbyError::*;
+
+        match self {
+            NotAuthor(name) => write!(
+                f,
+                "Player {} is not the author, only the author can manage the lobby",
+                name
+            ),
+            NotInLobby => write!(f, "The lobby can only be changed before the game starts"),
+            NoSuchPlayer(name) => write!(f, "Player of name {} does not exist!", name),
+            CannotKickSelf => write!(f, "The author cannot kick themselves, they can leave"),
+            NotAnAi(name) => write!(f, "Player {} is not an AI", name),
+            NotHuman(name) => write!(f, "Player {} is an AI", name),
+            LobbyFull => write!(
+                f,
+                "The lobby is full, a game can have at most {} players",
+                MAX_PLAYERS
+            ),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! Errors returned by the engine, one type per kind of action.
+//! [`CommandError`](command::CommandError) wraps all of them for [`Game::apply`](crate::gamestate::game::Game::apply).
+
+pub mod add_player;
+pub mod ai;
+pub mod call_uno;
+pub mod command;
+pub mod draw_cards;
+pub mod fairness;
+pub mod force_finish;
+pub mod forfeit;
+pub mod game_build;
+pub mod game_start;
+pub mod lobby;
+pub mod play_card;
+pub mod player_exist;
+pub mod player_turn;
+pub mod rematch;
+pub mod status;
+pub mod verification;
@@ -1,5 +1,4 @@
 use crate::cards::card::Card;
-use crate::err::ai::AiError;
 use crate::err::player_exist::PlayerExistError;
 use crate::err::player_turn::PlayerTurnError;
 use crate::err::status::CreateStatusError;
@@ -11,10 +10,10 @@ pub enum PlayCardError {
     PlayerTurnError(PlayerTurnError),
     PlayerExistError(PlayerExistError),
     CreateStatusError(CreateStatusError),
+    GameNotRunning(String),
     PlayerHasNoSuchCard(Card),
     CardCannotBePlayed(Card, Card),
     SaidUnoWhenShouldNotHave,
-    ChainedAiError,
 }
 
 impl Error for PlayCardError {}
@@ -27,6 +26,7 @@ impl Display for PlayCardError {
             PlayerTurnError(err) => write!(f, "{}", err),
             PlayerExistError(err) => write!(f, "{}", err),
             CreateStatusError(err) => write!(f, "{}", err),
+            GameNotRunning(id) => write!(f, "The game with id '{}' is not running", id),
             PlayerHasNoSuchCard(card) => write!(f, "Player does not have a {}", card),
             CardCannotBePlayed(played, top) => {
                 write!(f, "Cannot play a {} after a {}.", played, top)
@@ -34,7 +34,6 @@ impl Display for PlayCardError {
             SaidUnoWhenShouldNotHave => {
                 write!(f, "UNO! was said when it shouldn't have been possible")
             }
-            ChainedAiError => write!(f, "Error occurred when AI played"),
         }
     }
 }
@@ -56,9 +55,3 @@ impl From<CreateStatusError> for PlayCardError {
         PlayCardError::CreateStatusError(e)
     }
 }
-
-impl From<AiError> for PlayCardError {
-    fn from(_e: AiError) -> Self {
-        PlayCardError::ChainedAiError
-    }
-}
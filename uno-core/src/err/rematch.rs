@@ -0,0 +1,33 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum RematchError {
+    NotAuthor(String),
+    GameNotFinished(String),
+    NoReadyCheck,
+    ReadyCheckRunning,
+    NotHuman(String),
+    NoSuchPlayer(String),
+}
+
+impl Error for RematchError {}
+
+impl Display for RematchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use RematchError::*;
+
+        match self {
+            NotAuthor(name) => write!(
+                f,
+                "Player {} is not the author, only the author can call a rematch",
+                name
+            ),
+            GameNotFinished(id) => write!(f, "The game with id '{}' is not finished", id),
+            NoReadyCheck => write!(f, "Nobody called a rematch yet"),
+            ReadyCheckRunning => write!(f, "A rematch was already called"),
+            NotHuman(name) => write!(f, "Player {} is an AI", name),
+            NoSuchPlayer(name) => write!(f, "Player of name {} does not exist!", name),
+        }
+    }
+}
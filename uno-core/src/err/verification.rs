@@ -0,0 +1,30 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Reason why the shuffles of a fair game could not be verified.
+#[derive(Debug, Eq, PartialEq)]
+pub enum VerificationError {
+    InvalidSeed,
+    CommitmentMismatch,
+    NotAFullDeck,
+    ShuffleMismatch(usize),
+}
+
+impl Error for VerificationError {}
+
+impl Display for VerificationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use VerificationError::*;
+
+        match self {
+            InvalidSeed => write!(f, "The seed is not 32 hex encoded bytes"),
+            CommitmentMismatch => write!(f, "The seed does not match the commitment"),
+            NotAFullDeck => write!(f, "The first shuffle is not the full deck of 108 cards"),
+            ShuffleMismatch(index) => write!(
+                f,
+                "Shuffle {} is not the one the seed and the entropy give",
+                index
+            ),
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use crate::cards::card::{Card, CardColor};
+use crate::cards::deck::{full_deck, Deck};
+use crate::cards::random_color;
+use crate::err::game_build::GameBuildError;
+use crate::gamestate::active_cards::ActiveCards;
+use crate::gamestate::fairness::shuffle;
+use crate::gamestate::game::Game;
+use crate::gamestate::players::player::Player;
+use crate::gamestate::CARDS_DEALT_TO_PLAYERS;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use serde::Deserialize;
+
+#[cfg(test)]
+#[path = "../tests/game_builder_test.rs"]
+mod tests;
+
+/// A seat of a game set up by the `GameBuilder`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerSetup {
+    pub name: String,
+    #[serde(default)]
+    pub is_ai: bool,
+    /// None deals the player the usual number of cards.
+    #[serde(default)]
+    pub hand: Option<Vec<Card>>,
+}
+
+/// Sets up a running game in an exact state, without any randomness, e.g. for tests or puzzles.
+/// The first player is the author. Everything left unset is taken from a freshly shuffled deck
+/// the set cards were removed from: players without a hand are dealt the usual number of cards,
+/// and without a draw pile, the deck's remaining cards are drawn.
+///
+/// ```
+/// use uno_core::cards::card::{Card, CardColor, CardSymbol};
+/// use uno_core::gamestate::builder::GameBuilder;
+///
+/// let red_five = Card::new(CardColor::Red, CardSymbol::Value(5)).unwrap();
+/// let game = GameBuilder::new("Andy")
+///     .human("Bob")
+///     .hand("Bob", vec![red_five.clone()])
+///     .top_card(red_five)
+///     .current_player("Bob")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(game.get_current_player().unwrap().name(), "Bob");
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameBuilder {
+    players: Vec<PlayerSetup>,
+    /// Cards in the order they will be drawn.
+    #[serde(default)]
+    draw_pile: Option<Vec<Card>>,
+    #[serde(default)]
+    top_card: Option<Card>,
+    #[serde(default)]
+    current_player: Option<String>,
+    #[serde(default = "clockwise_by_default")]
+    clockwise: bool,
+    #[serde(default)]
+    active_cards: Vec<Card>,
+    /// Names passed to `hand` that did not belong to any player.
+    #[serde(skip)]
+    unknown_players: Vec<String>,
+}
+
+fn clockwise_by_default() -> bool {
+    true
+}
+
+impl GameBuilder {
+    pub fn new(author_name: &str) -> GameBuilder {
+        GameBuilder {
+            players: vec![],
+            draw_pile: None,
+            top_card: None,
+            current_player: None,
+            clockwise: true,
+            active_cards: vec![],
+            unknown_players: vec![],
+        }
+        .human(author_name)
+    }
+
+    pub fn human(self, name: &str) -> GameBuilder {
+        self.seat(name, false)
+    }
+
+    pub fn ai(self, name: &str) -> GameBuilder {
+        self.seat(name, true)
+    }
+
+    fn seat(mut self, name: &str, is_ai: bool) -> GameBuilder {
+        self.players.push(PlayerSetup {
+            name: name.into(),
+            is_ai,
+            hand: None,
+        });
+        self
+    }
+
+    pub fn hand(mut self, player_name: &str, cards: Vec<Card>) -> GameBuilder {
+        match self.players.iter_mut().find(|p| p.name == player_name) {
+            Some(player) => player.hand = Some(cards),
+            None => self.unknown_players.push(player_name.into()),
+        }
+        self
+    }
+
+    /// Cards in the order they will be drawn.
+    pub fn draw_pile(mut self, cards: Vec<Card>) -> GameBuilder {
+        self.draw_pile = Some(cards);
+        self
+    }
+
+    pub fn top_card(mut self, card: Card) -> GameBuilder {
+        self.top_card = Some(card);
+        self
+    }
+
+    pub fn current_player(mut self, player_name: &str) -> GameBuilder {
+        self.current_player = Some(player_name.into());
+        self
+    }
+
+    pub fn clockwise(mut self, clockwise: bool) -> GameBuilder {
+        self.clockwise = clockwise;
+        self
+    }
+
+    /// Cards the current player has to respond to, all of the same symbol: Skip, Draw2 or Draw4.
+    pub fn active_cards(mut self, cards: Vec<Card>) -> GameBuilder {
+        self.active_cards = cards;
+        self
+    }
+
+    /// Returns the game in the Running state.
+    pub fn build(self) -> Result<Game, GameBuildError> {
+        if let Some(name) = self.unknown_players.into_iter().next() {
+            return Err(GameBuildError::NoSuchPlayer(name));
+        }
+        if self.players.is_empty() {
+            return Err(GameBuildError::NoPlayers);
+        }
+
+        // the cards nobody set come from a shuffled deck without the set ones
+        let mut remaining_cards = full_deck();
+        shuffle(&mut remaining_cards, &mut ChaCha20Rng::from_entropy());
+        let set_cards = self
+            .players
+            .iter()
+            .flat_map(|setup| setup.hand.iter().flatten())
+            .chain(self.top_card.iter())
+            .chain(self.draw_pile.iter().flatten());
+        for card in set_cards {
+            remove_card(&mut remaining_cards, card);
+        }
+
+        let mut players: Vec<Player> = vec![];
+        for (index, setup) in self.players.into_iter().enumerate() {
+            if players.iter().any(|player| player.name() == setup.name) {
+                return Err(GameBuildError::DuplicatePlayer(setup.name));
+            }
+
+            let hand = match setup.hand {
+                Some(hand) => hand,
+                None => deal(&mut remaining_cards, CARDS_DEALT_TO_PLAYERS)?,
+            };
+            let mut player = Player::new(setup.name, index == 0, !setup.is_ai);
+            hand.into_iter().for_each(|card| player.give_card(card));
+            players.push(player);
+        }
+
+        let current_player = match self.current_player {
+            None => 0,
+            Some(name) => players
+                .iter()
+                .position(|player| player.name() == name)
+                .ok_or(GameBuildError::NoSuchPlayer(name))?,
+        };
+
+        let mut active_cards = ActiveCards::new();
+        for card in self.active_cards {
+            active_cards
+                .push(card)
+                .map_err(|err| GameBuildError::InvalidActiveCards(err.to_string()))?;
+        }
+
+        let top_card = match self.top_card {
+            Some(top_card) => top_card,
+            None => {
+                let top_card = deal(&mut remaining_cards, 1)?.remove(0);
+                if top_card.should_be_black() {
+                    // safe since explicit should_be_black() test
+                    top_card.morph_black_card(random_color()).unwrap()
+                } else {
+                    top_card
+                }
+            }
+        };
+        // the deck draws from the end of the remaining cards, but takes the first of a given pile first
+        remaining_cards.reverse();
+        let deck = Deck::with_piles(self.draw_pile.unwrap_or(remaining_cards), top_card);
+
+        Ok(Game::from_parts(
+            players,
+            deck,
+            current_player,
+            active_cards,
+            self.clockwise,
+        ))
+    }
+}
+
+/// Removes one copy of the card, wild cards are found whatever color they were given.
+fn remove_card(cards: &mut Vec<Card>, card: &Card) {
+    let position = cards.iter().position(|candidate| {
+        candidate.symbol == card.symbol
+            && (candidate.color == card.color || candidate.color == CardColor::Black)
+    });
+    if let Some(position) = position {
+        cards.remove(position);
+    }
+}
+
+fn deal(cards: &mut Vec<Card>, count: usize) -> Result<Vec<Card>, GameBuildError> {
+    if cards.len() < count {
+        return Err(GameBuildError::NotEnoughCards);
+    }
+    Ok(cards.split_off(cards.len() - count))
+}
@@ -0,0 +1,46 @@
+use crate::cards::card::{Card, CardColor};
+use serde::{Deserialize, Serialize};
+
+/// An action somebody wants to take in a game. Commands are validated and applied by `Game::apply`.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Command {
+    /// A new player takes a seat in the lobby.
+    Join { name: String },
+    /// The player leaves the lobby. If they were the author, the next human player becomes the author.
+    Leave { player: String },
+    /// The author removes a human player from the lobby, who cannot join again.
+    Kick { author: String, player: String },
+    /// The author adds an AI to the lobby.
+    AddAi { author: String },
+    /// The author removes an AI from the lobby.
+    RemoveAi { author: String, player: String },
+    /// The author hands authorship over to another human player.
+    TransferAuthor { author: String, to: String },
+    /// The author starts (or restarts) the game.
+    Start { player: String },
+    /// The player at turn plays a card from their hand.
+    #[serde(rename_all = "camelCase")]
+    PlayCard {
+        player: String,
+        card: Card,
+        new_color: Option<CardColor>,
+        said_uno: bool,
+    },
+    /// The player at turn draws (or takes the penalty of the active cards) instead of playing.
+    DrawCards { player: String },
+    /// The player at turn says UNO! before playing their second to last card.
+    CallUno { player: String },
+    /// The player gives up the running game, their cards go back to the draw pile.
+    Forfeit { player: String },
+    /// The author of a finished game asks everyone whether they want to play again.
+    ProposeRematch { author: String },
+    /// The player wants to play the rematch. The game goes back to the lobby once every human agreed.
+    ConfirmRematch { player: String },
+    /// The author takes the game back to the lobby without waiting, dropping everyone who did not agree.
+    ReturnToLobby { author: String },
+    /// The player mixes their own randomness into the shuffles of a fair game, before it starts.
+    AddEntropy { player: String, entropy: String },
+    /// The server operator ends the running game, the players still in it are placed by how few cards they hold.
+    ForceFinish,
+}
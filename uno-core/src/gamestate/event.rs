@@ -0,0 +1,58 @@
+use crate::cards::card::Card;
+use serde::{Deserialize, Serialize};
+
+/// Something that happened in a game as the result of a `Command`.
+/// Events are plain data: turning them into messages for the players is up to the caller.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Event {
+    PlayerJoined {
+        name: String,
+    },
+    /// The player left the lobby, or was removed from it by the author.
+    PlayerLeft {
+        name: String,
+        kicked: bool,
+    },
+    AuthorChanged {
+        name: String,
+    },
+    GameStarted,
+    CardPlayed {
+        who: String,
+        next: String,
+        card: Card,
+    },
+    /// `cards` is empty when the player only accepted being skipped.
+    CardsDrawn {
+        who: String,
+        next: String,
+        cards: Vec<Card>,
+    },
+    UnoCalled {
+        who: String,
+    },
+    /// The player did not say UNO! and had to take penalty cards.
+    Penalized {
+        who: String,
+        cards: Vec<Card>,
+    },
+    PlayerFinished {
+        who: String,
+    },
+    /// The player gave up and will be placed after everyone else. `next` is None if that finished the game.
+    PlayerForfeited {
+        who: String,
+        next: Option<String>,
+    },
+    GameFinished,
+    /// The author of the finished game called a rematch, they agreed to it already.
+    RematchProposed {
+        by: String,
+    },
+    RematchConfirmed {
+        who: String,
+    },
+    /// The game is in the lobby again, preceded by a `PlayerLeft` event for everyone who did not agree to the rematch.
+    ReturnedToLobby,
+}
@@ -0,0 +1,151 @@
+use crate::cards::card::Card;
+use crate::cards::deck::full_deck;
+use crate::err::verification::VerificationError;
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+#[cfg(test)]
+#[path = "../tests/fairness_test.rs"]
+mod tests;
+
+/// Most characters of entropy a player can contribute.
+pub static MAX_ENTROPY_LENGTH: usize = 64;
+
+/// Commit-reveal of the seed every shuffle of a fair game is derived from.
+/// The commitment is known before the players add their entropy, so the server cannot pick a seed that suits it,
+/// and the seed is revealed once the game finishes, so anyone can re-run the shuffles.
+#[derive(Debug, Clone)]
+pub struct Fairness {
+    seed: [u8; 32],
+    /// Entropy the players mixed into the seed, by their names.
+    entropy: BTreeMap<String, String>,
+}
+
+impl Default for Fairness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fairness {
+    pub fn new() -> Self {
+        let mut seed = [0; 32];
+        rand::rngs::OsRng.fill_bytes(&mut seed);
+
+        Self {
+            seed,
+            entropy: BTreeMap::new(),
+        }
+    }
+
+    /// Hex encoded SHA-256 of the seed.
+    pub fn commitment(&self) -> String {
+        to_hex(&Sha256::digest(self.seed))
+    }
+
+    pub fn entropy(&self) -> &BTreeMap<String, String> {
+        &self.entropy
+    }
+
+    pub(crate) fn add_entropy(&mut self, player_name: String, entropy: String) {
+        self.entropy.insert(player_name, entropy);
+    }
+
+    /// Generator of the shuffles, seeded with the seed and the entropy of the players.
+    pub(crate) fn rng(&self) -> ChaCha20Rng {
+        ChaCha20Rng::from_seed(mixed_seed(&self.seed, &self.entropy))
+    }
+
+    /// Reveals the seed together with the shuffles of the deck.
+    pub(crate) fn proof(&self, shuffles: Vec<Vec<Card>>) -> FairnessProof {
+        FairnessProof {
+            commitment: self.commitment(),
+            seed: to_hex(&self.seed),
+            entropy: self.entropy.clone(),
+            shuffles,
+        }
+    }
+}
+
+/// Everything needed to check the shuffles of a fair game, revealed when it finishes.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FairnessProof {
+    pub commitment: String,
+    /// Hex encoded seed.
+    pub seed: String,
+    pub entropy: BTreeMap<String, String>,
+    /// The draw pile after every shuffle, the card drawn first being the first one.
+    /// The first shuffle is the whole deck, whose first card became the top card of the discard pile.
+    pub shuffles: Vec<Vec<Card>>,
+}
+
+impl FairnessProof {
+    /// Re-runs every shuffle from the seed and the entropy, Ok if the game used exactly those.
+    pub fn verify(&self) -> Result<(), VerificationError> {
+        let seed: [u8; 32] = from_hex(&self.seed)
+            .and_then(|seed| seed.try_into().ok())
+            .ok_or(VerificationError::InvalidSeed)?;
+        if to_hex(&Sha256::digest(seed)) != self.commitment.to_lowercase() {
+            return Err(VerificationError::CommitmentMismatch);
+        }
+
+        let mut full_deck = full_deck();
+        full_deck.sort();
+        let mut first_shuffle = self.shuffles.first().cloned().unwrap_or_default();
+        first_shuffle.sort();
+        if first_shuffle != full_deck {
+            return Err(VerificationError::NotAFullDeck);
+        }
+
+        let mut rng = ChaCha20Rng::from_seed(mixed_seed(&seed, &self.entropy));
+        for (index, recorded) in self.shuffles.iter().enumerate() {
+            let mut cards = recorded.clone();
+            shuffle(&mut cards, &mut rng);
+            cards.reverse();
+            if &cards != recorded {
+                return Err(VerificationError::ShuffleMismatch(index));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Shuffles the cards so that the result depends only on the generator and on which cards there are, not on their order.
+/// The card drawn first is the last one.
+pub(crate) fn shuffle(cards: &mut [Card], rng: &mut ChaCha20Rng) {
+    cards.sort();
+    cards.shuffle(rng);
+}
+
+fn mixed_seed(seed: &[u8; 32], entropy: &BTreeMap<String, String>) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(seed);
+    // lengths first, so that no two different sets of entropy hash the same
+    for (player_name, player_entropy) in entropy {
+        hasher.update((player_name.len() as u64).to_be_bytes());
+        hasher.update(player_name);
+        hasher.update((player_entropy.len() as u64).to_be_bytes());
+        hasher.update(player_entropy);
+    }
+    hasher.finalize().into()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
@@ -0,0 +1,1138 @@
+use crate::cards::card::{Card, CardColor, CardSymbol};
+use crate::cards::deck::Deck;
+use crate::err::add_player::AddPlayerError;
+use crate::err::ai::AiError;
+use crate::err::call_uno::CallUnoError;
+use crate::err::command::CommandError;
+use crate::err::draw_cards::PlayerDrawError;
+use crate::err::fairness::FairnessError;
+use crate::err::force_finish::ForceFinishError;
+use crate::err::forfeit::ForfeitError;
+use crate::err::game_start::GameStartError;
+use crate::err::lobby::LobbyError;
+use crate::err::play_card::PlayCardError;
+use crate::err::player_exist::PlayerExistError;
+use crate::err::player_turn::PlayerTurnError;
+use crate::err::rematch::RematchError;
+use crate::err::status::CreateStatusError;
+use crate::gamestate::active_cards::ActiveCards;
+use crate::gamestate::command::Command;
+use crate::gamestate::event::Event;
+use crate::gamestate::fairness::{Fairness, FairnessProof, MAX_ENTROPY_LENGTH};
+use crate::gamestate::legal_moves::LegalMoves;
+use crate::gamestate::players::ai::decide_command;
+use crate::gamestate::players::player::Player;
+use crate::gamestate::{CARDS_DEALT_TO_PLAYERS, MAX_PLAYERS, PENALTY_CARDS};
+use nanoid::nanoid;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[cfg(test)]
+#[path = "../tests/game_test.rs"]
+mod tests;
+
+/// Phase of the game; players can only join in the lobby and only play while the game is running.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum GameStatus {
+    Lobby,
+    Running,
+    Finished,
+}
+
+/// A single game of UNO, with its players, deck and the cards currently in effect.
+#[derive(Debug, Clone)]
+pub struct Game {
+    pub id: String,
+    status: GameStatus,
+    players: Vec<Player>,
+    deck: Deck,
+    current_player: usize,
+    /// An active card means that the current player must respond to that card, i.e. by being skipped or by drawing.
+    active_cards: ActiveCards,
+    pub is_clockwise: bool,
+    /// Names of the players the author kicked, they cannot join again.
+    kicked: Vec<String>,
+    /// Names of the players who gave up the running game, in the order they did so.
+    forfeited: Vec<String>,
+    /// Number of games every player won, kept across rematches.
+    wins: BTreeMap<String, usize>,
+    /// Names of the humans who agreed to the rematch, None unless the author called one.
+    rematch: Option<Vec<String>>,
+    /// Number of changes made to the game so far, it never goes back, not even in a rematch.
+    turn: u64,
+    /// Seed of the shuffles of a fair game, None if the server is simply trusted to shuffle.
+    fairness: Option<Fairness>,
+    /// Whether the server operator ended the game before it was played out.
+    force_finished: bool,
+}
+
+impl Game {
+    pub fn new(author_name: String) -> Game {
+        Game {
+            id: nanoid!(10),
+            status: GameStatus::Lobby,
+            players: vec![Player::new(author_name, true, true)],
+            deck: Deck::new(),
+            current_player: 0,
+            active_cards: ActiveCards::new(),
+            is_clockwise: true,
+            kicked: vec![],
+            forfeited: vec![],
+            wins: BTreeMap::new(),
+            rematch: None,
+            turn: 0,
+            fairness: None,
+            force_finished: false,
+        }
+    }
+
+    pub fn new_with_ai(author_name: String, ai_count: usize) -> Game {
+        let mut game = Game::new(author_name);
+        (0..ai_count).for_each(|_| game.add_ai());
+        game
+    }
+
+    /// Assembles a running game, used by the `GameBuilder`.
+    pub(super) fn from_parts(
+        players: Vec<Player>,
+        deck: Deck,
+        current_player: usize,
+        active_cards: ActiveCards,
+        is_clockwise: bool,
+    ) -> Game {
+        Game {
+            id: nanoid!(10),
+            status: GameStatus::Running,
+            players,
+            deck,
+            current_player,
+            active_cards,
+            is_clockwise,
+            kicked: vec![],
+            forfeited: vec![],
+            wins: BTreeMap::new(),
+            rematch: None,
+            turn: 0,
+            fairness: None,
+            force_finished: false,
+        }
+    }
+
+    /// Validates the command and applies it to the game.
+    /// Returns everything that happened as a consequence, in order. Performs no I/O and never plays for the AIs.
+    pub fn apply(&mut self, command: Command) -> Result<Vec<Event>, CommandError> {
+        let events = match command {
+            Command::Join { name } => self.add_player(name)?,
+            Command::Leave { player } => self.leave(player)?,
+            Command::Kick { author, player } => self.kick(author, player)?,
+            Command::AddAi { author } => self.add_ai_by(author)?,
+            Command::RemoveAi { author, player } => self.remove_ai(author, player)?,
+            Command::TransferAuthor { author, to } => self.transfer_author(author, to)?,
+            Command::Start { player } => self.start_by(player)?,
+            Command::PlayCard {
+                player,
+                card,
+                new_color,
+                said_uno,
+            } => self.play_card(player, card, new_color, said_uno)?,
+            Command::DrawCards { player } => self.draw_cards(player)?,
+            Command::CallUno { player } => self.call_uno(player)?,
+            Command::Forfeit { player } => self.forfeit(player)?,
+            Command::ProposeRematch { author } => self.propose_rematch(author)?,
+            Command::ConfirmRematch { player } => self.confirm_rematch(player)?,
+            Command::ReturnToLobby { author } => self.return_to_lobby(author)?,
+            Command::AddEntropy { player, entropy } => self.add_entropy(player, entropy)?,
+            Command::ForceFinish => self.force_finish()?,
+        };
+
+        // a command that changed nothing must not make the turn the other clients saw stale
+        if !events.is_empty() {
+            self.turn += 1;
+        }
+        Ok(events)
+    }
+
+    /// Starts the game on behalf of a player, returns Err if they are not the author.
+    fn start_by(&mut self, player_name: String) -> Result<Vec<Event>, GameStartError> {
+        match self.find_player(player_name.clone()) {
+            Some(player) if player.is_author() => self.start(),
+            _ => Err(GameStartError::NotAuthor(player_name)),
+        }
+    }
+
+    /// Randomizes player order and start, clears positions from previous games, resets the deck and deals cards to players.
+    /// Returns Err is the game is already Running, or if it is a finished fair game.
+    pub fn start(&mut self) -> Result<Vec<Event>, GameStartError> {
+        if self.status == GameStatus::Running {
+            return Err(GameStartError::GameAlreadyStarted);
+        }
+        // the seed of the finished game is revealed, the next one has to be committed to in the lobby,
+        // where the players can see the commitment and add their entropy before it is used
+        if self.status == GameStatus::Finished && self.fairness.is_some() {
+            return Err(GameStartError::FairGameNotInLobby);
+        }
+
+        self.randomize_player_order();
+        self.randomize_starting_player();
+        self.clear_player_positions();
+        self.forfeited.clear();
+        self.rematch = None;
+        self.active_cards.clear();
+        self.force_finished = false;
+
+        self.status = GameStatus::Running;
+        self.deal_starting_cards()?;
+
+        Ok(vec![Event::GameStarted])
+    }
+
+    fn randomize_player_order(&mut self) {
+        self.players.shuffle(&mut rand::thread_rng())
+    }
+
+    /// Imitates a random starting player by pretending that some rounds have already been played.
+    fn randomize_starting_player(&mut self) {
+        self.current_player = rand::thread_rng().gen_range(0..self.players.len());
+    }
+
+    fn clear_player_positions(&mut self) {
+        for player in self.players.iter_mut() {
+            player.clear_position();
+        }
+    }
+
+    /// Clears all players' hands and gives them new cards from a new Deck.
+    fn deal_starting_cards(&mut self) -> Result<(), GameStartError> {
+        self.deck = match &self.fairness {
+            None => Deck::new(),
+            Some(fairness) => Deck::new_fair(fairness.rng()),
+        };
+
+        for player in self.players.iter_mut() {
+            player.drop_all_cards();
+            player.forget_uno_call();
+
+            for _ in 0..CARDS_DEALT_TO_PLAYERS {
+                match self.deck.draw() {
+                    None => return Err(GameStartError::DeckEmptyWhenStartingGame),
+                    Some(card) => player.give_card(card),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn find_player(&self, name: String) -> Option<&Player> {
+        self.players.iter().find(|player| player.name() == name)
+    }
+
+    pub fn find_player_mut(&mut self, name: &str) -> Option<&mut Player> {
+        self.players.iter_mut().find(|player| player.name() == name)
+    }
+
+    pub fn find_author(&self) -> Option<&Player> {
+        self.players.iter().find(|player| player.is_author())
+    }
+
+    /// Convenience method for accessing the reference to the game's Players.
+    pub fn players(&self) -> &Vec<Player> {
+        &self.players
+    }
+
+    /// Convenience method for accessing the reference to the game's Deck.
+    pub fn deck(&self) -> &Deck {
+        &self.deck
+    }
+
+    pub fn add_player(&mut self, name: String) -> Result<Vec<Event>, AddPlayerError> {
+        if self.status != GameStatus::Lobby {
+            return Err(AddPlayerError::NotAcceptingPlayers);
+        }
+
+        if self.kicked.contains(&name) {
+            return Err(AddPlayerError::Kicked(name));
+        }
+
+        if self.find_player(name.clone()).is_some() {
+            return Err(AddPlayerError::AlreadyExists(name));
+        }
+
+        if self.players.len() >= MAX_PLAYERS {
+            return Err(AddPlayerError::LobbyFull);
+        }
+
+        self.players.push(Player::new(name.clone(), false, true));
+
+        Ok(vec![Event::PlayerJoined { name }])
+    }
+
+    pub fn add_ai(&mut self) {
+        self.players.push(Player::new_ai())
+    }
+
+    /// Removes the player from the lobby. If they were the author, the first remaining human becomes the author.
+    fn leave(&mut self, player_name: String) -> Result<Vec<Event>, LobbyError> {
+        if self.status != GameStatus::Lobby {
+            return Err(LobbyError::NotInLobby);
+        }
+
+        let player = self.remove_player(&player_name)?;
+        let mut events = vec![Event::PlayerLeft {
+            name: player_name,
+            kicked: false,
+        }];
+
+        if player.is_author() {
+            if let Some(next) = self.players.iter_mut().find(|p| p.is_human()) {
+                next.set_author(true);
+                events.push(Event::AuthorChanged { name: next.name() });
+            }
+        }
+
+        Ok(events)
+    }
+
+    fn kick(&mut self, author_name: String, player_name: String) -> Result<Vec<Event>, LobbyError> {
+        self.check_lobby_author(&author_name)?;
+        if author_name == player_name {
+            return Err(LobbyError::CannotKickSelf);
+        }
+        match self.find_player(player_name.clone()) {
+            None => return Err(LobbyError::NoSuchPlayer(player_name)),
+            Some(player) if !player.is_human() => return Err(LobbyError::NotHuman(player_name)),
+            Some(_) => (),
+        }
+
+        self.remove_player(&player_name)?;
+        self.kicked.push(player_name.clone());
+
+        Ok(vec![Event::PlayerLeft {
+            name: player_name,
+            kicked: true,
+        }])
+    }
+
+    fn add_ai_by(&mut self, author_name: String) -> Result<Vec<Event>, LobbyError> {
+        self.check_lobby_author(&author_name)?;
+
+        if self.players.len() >= MAX_PLAYERS {
+            return Err(LobbyError::LobbyFull);
+        }
+
+        let ai = Player::new_ai();
+        let name = ai.name();
+        self.players.push(ai);
+
+        Ok(vec![Event::PlayerJoined { name }])
+    }
+
+    fn remove_ai(
+        &mut self,
+        author_name: String,
+        ai_name: String,
+    ) -> Result<Vec<Event>, LobbyError> {
+        self.check_lobby_author(&author_name)?;
+        match self.find_player(ai_name.clone()) {
+            None => return Err(LobbyError::NoSuchPlayer(ai_name)),
+            Some(player) if player.is_human() => return Err(LobbyError::NotAnAi(ai_name)),
+            Some(_) => (),
+        }
+
+        self.remove_player(&ai_name)?;
+
+        Ok(vec![Event::PlayerLeft {
+            name: ai_name,
+            kicked: false,
+        }])
+    }
+
+    fn transfer_author(
+        &mut self,
+        author_name: String,
+        new_author_name: String,
+    ) -> Result<Vec<Event>, LobbyError> {
+        self.check_lobby_author(&author_name)?;
+        match self.find_player(new_author_name.clone()) {
+            None => return Err(LobbyError::NoSuchPlayer(new_author_name)),
+            Some(player) if !player.is_human() => {
+                return Err(LobbyError::NotHuman(new_author_name))
+            }
+            Some(_) => (),
+        }
+
+        for player in self.players.iter_mut() {
+            player.set_author(player.name() == new_author_name);
+        }
+
+        Ok(vec![Event::AuthorChanged {
+            name: new_author_name,
+        }])
+    }
+
+    /// Returns Err unless the game is in the lobby and the player is its author.
+    fn check_lobby_author(&self, author_name: &str) -> Result<(), LobbyError> {
+        if self.status != GameStatus::Lobby {
+            return Err(LobbyError::NotInLobby);
+        }
+
+        match self.find_player(author_name.to_string()) {
+            Some(player) if player.is_author() => Ok(()),
+            _ => Err(LobbyError::NotAuthor(author_name.to_string())),
+        }
+    }
+
+    fn remove_player(&mut self, player_name: &str) -> Result<Player, LobbyError> {
+        match self.players.iter().position(|p| p.name() == player_name) {
+            None => Err(LobbyError::NoSuchPlayer(player_name.to_string())),
+            Some(index) => Ok(self.players.remove(index)),
+        }
+    }
+
+    pub fn get_finished_players(&self) -> Vec<&Player> {
+        let mut result = self
+            .players
+            .iter()
+            .filter(|p| p.is_finished())
+            .collect::<Vec<&Player>>();
+        result.sort_by_key(|player| player.position().unwrap()); // safe due to p.is_finished() filter above
+        result
+    }
+
+    pub fn get_current_player(&self) -> Option<&Player> {
+        self.players.get(self.current_player)
+    }
+
+    fn next_turn(&mut self) {
+        self.current_player = if self.is_clockwise {
+            self.current_player + 1
+        } else {
+            match self.current_player.checked_sub(1) {
+                None => self.players.len() - 1,
+                Some(number) => number,
+            }
+        }
+        .rem_euclid(self.players.len());
+    }
+
+    /// Attempts to find the next player in line. Returns true if found, false otherwise.
+    pub fn end_turn(&mut self) -> bool {
+        if self.players.iter().all(|player| self.is_out(player)) {
+            return false;
+        }
+
+        loop {
+            self.next_turn();
+
+            if let Some(player) = self.get_current_player() {
+                if !self.is_out(player) {
+                    break;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Whether the player no longer takes turns, either because they finished or because they gave up.
+    fn is_out(&self, player: &Player) -> bool {
+        player.is_finished() || self.forfeited.contains(&player.name())
+    }
+
+    /// Names of the players who gave up the running game, in the order they did so.
+    pub fn forfeited_players(&self) -> &Vec<String> {
+        &self.forfeited
+    }
+
+    /// Takes the player out of the turn rotation and shuffles their cards back into the draw pile.
+    /// They end up last in the finishing order, behind everyone who keeps playing.
+    pub fn forfeit(&mut self, player_name: String) -> Result<Vec<Event>, ForfeitError> {
+        if self.status != GameStatus::Running {
+            return Err(ForfeitError::GameNotRunning(self.id.clone()));
+        }
+
+        let player = self.does_player_exist(player_name.clone())?;
+        if self.is_out(player) {
+            return Err(ForfeitError::AlreadyOut(player_name));
+        }
+        let was_at_turn = self.is_player_at_turn(player).is_ok();
+
+        // safe since does_player_exist() check above
+        let player = self.find_player_mut(&player_name).unwrap();
+        let cards = player.cards();
+        player.drop_all_cards();
+        player.forget_uno_call();
+        self.deck.return_cards(cards);
+        self.forfeited.push(player_name.clone());
+
+        if was_at_turn {
+            // whatever was active was meant for the player who gave up
+            self.active_cards.clear();
+            self.end_turn();
+        }
+
+        let mut finish_events = self.maybe_finish_game();
+        let next = match self.status {
+            GameStatus::Running => self.get_current_player().map(|player| player.name()),
+            _ => None,
+        };
+
+        let mut events = vec![Event::PlayerForfeited {
+            who: player_name,
+            next,
+        }];
+        events.append(&mut finish_events);
+
+        Ok(events)
+    }
+
+    pub fn reverse(&mut self) {
+        self.is_clockwise = !self.is_clockwise
+    }
+
+    pub fn status(&self) -> GameStatus {
+        self.status
+    }
+
+    /// Symbol of the active cards the current player has to respond to, if there are any.
+    pub fn active_symbol(&self) -> Option<CardSymbol> {
+        self.active_cards.active_symbol()
+    }
+
+    /// If there are any active cards, returns true only if the played_card's symbol matches:
+    /// e.g. playing a Blue Skip on a Red Skip.
+    /// If there are no active cards, returns true if the played_card's symbol OR color matches, or it is a Black card.
+    pub fn can_play_card(&self, played_card: &Card) -> bool {
+        let top_card = self.deck.top_discard_card();
+
+        if self.active_cards.are_cards_active() {
+            played_card.symbol == self.active_cards.active_symbol().unwrap() // save due to are_cards_active() check above
+        } else {
+            played_card.color == CardColor::Black
+                || played_card.color == top_card.color
+                || played_card.symbol == top_card.symbol
+        }
+    }
+
+    /// Returns the moves the named player can make right now.
+    /// The moves are empty if the game is not running or it is not the player's turn.
+    pub fn legal_moves(&self, player_name: &str) -> Result<LegalMoves, PlayerExistError> {
+        let player = self.does_player_exist(player_name.to_string())?;
+
+        if self.status != GameStatus::Running || self.is_player_at_turn(player).is_err() {
+            return Ok(LegalMoves::default());
+        }
+
+        Ok(self.moves_of(player))
+    }
+
+    /// Computes the moves of the player as if it was their turn.
+    fn moves_of(&self, player: &Player) -> LegalMoves {
+        let playable_cards: Vec<Card> = player
+            .cards()
+            .iter()
+            .filter(|card| self.can_play_card(card))
+            .cloned()
+            .collect();
+        let must_draw_or_pass = playable_cards.is_empty();
+        let skip_active = self.active_cards.active_symbol() == Some(CardSymbol::Skip);
+
+        LegalMoves {
+            playable_cards,
+            can_draw: must_draw_or_pass && !skip_active,
+            can_pass: must_draw_or_pass && skip_active,
+        }
+    }
+
+    /// Returns true if the current player is an AI that is expected to take its turn.
+    pub fn is_ai_turn(&self) -> bool {
+        self.status == GameStatus::Running
+            && matches!(self.get_current_player(), Some(player) if !player.is_human() && !self.is_out(player))
+    }
+
+    /// Returns reference to a player matching the provided name, Err if they do not exist.
+    fn does_player_exist(&self, player_name: String) -> Result<&Player, PlayerExistError> {
+        let maybe_player = self.find_player(player_name.clone());
+
+        if let Some(player) = maybe_player {
+            Ok(player)
+        } else {
+            Err(PlayerExistError::NoSuchPlayer(player_name))
+        }
+    }
+
+    /// Returns Err if the passed player is not the current player, or if there is somehow no player playing.
+    fn is_player_at_turn(&self, player: &Player) -> Result<(), PlayerTurnError> {
+        match self.get_current_player() {
+            None => Err(PlayerTurnError::NoOneIsPlaying),
+            Some(current_player) => {
+                if player != current_player {
+                    Err(PlayerTurnError::PlayerOutOfTurn(player.name()))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Performs immutable checks whether the player is eligible to draw a card.
+    fn can_player_draw(&self, player_name: String) -> Result<(), PlayerDrawError> {
+        if self.status != GameStatus::Running {
+            return Err(PlayerDrawError::GameNotRunning(self.id.clone()));
+        }
+
+        let player = self.does_player_exist(player_name)?;
+        self.is_player_at_turn(player)?;
+
+        let moves = self.moves_of(player);
+        if !(moves.can_draw || moves.can_pass) {
+            return Err(PlayerDrawError::CanPlayInstead);
+        }
+
+        Ok(())
+    }
+
+    fn end_drawing(
+        &mut self,
+        drawing_player: String,
+        cards_drawn: Vec<Card>,
+    ) -> Result<Vec<Event>, PlayerDrawError> {
+        if let Some(player) = self.find_player_mut(&drawing_player) {
+            player.forget_uno_call();
+        }
+        self.end_turn();
+
+        // player name after end_turn == next player
+        let next_player_name = match self.get_current_player() {
+            None => {
+                return Err(PlayerDrawError::from(
+                    CreateStatusError::CurrentPlayerNotFound,
+                ))
+            }
+            Some(player) => player.name(),
+        };
+
+        Ok(vec![Event::CardsDrawn {
+            who: drawing_player,
+            next: next_player_name,
+            cards: cards_drawn,
+        }])
+    }
+
+    /// Returns a CardsDrawn event containing what the player received as drawn cards.
+    /// Returns an error if the player does not exist, is not the current player, or has a valid card to play.
+    /// Should get called whenever a player clicks the draw card pile.
+    pub fn draw_cards(&mut self, player_name: String) -> Result<Vec<Event>, PlayerDrawError> {
+        self.can_player_draw(player_name.clone())?;
+
+        // Skip turn, unwrap() is safe since are_cards_active() check above
+        if self.active_cards.are_cards_active()
+            && self.active_cards.active_symbol().unwrap() == CardSymbol::Skip
+        {
+            self.active_cards.clear();
+            return self.end_drawing(player_name, vec![]);
+        }
+
+        let draw_count = if self.active_cards.are_cards_active() {
+            let count = self.active_cards.sum_active_draw_cards().expect(
+                "Impossible: player can draw, but there are active cards that are not Draw",
+            );
+            self.active_cards.clear();
+            count
+        } else {
+            1
+        };
+        let drawn_cards = self.draw_n_cards(player_name.clone(), draw_count);
+
+        debug_assert_eq!(draw_count, drawn_cards.len());
+        self.end_drawing(player_name, drawn_cards)
+    }
+
+    /// Draws n cards from the deck and gives them to the named player.
+    /// Returns a clone of the cards drawn.
+    fn draw_n_cards(&mut self, player_name: String, n: usize) -> Vec<Card> {
+        let player = self
+            .players
+            .iter_mut()
+            .find(|player| player.name() == player_name)
+            .unwrap(); // safe because of can_player_draw() in draw_cards()
+        let mut drawn_cards = vec![];
+
+        for _ in 0..n {
+            let drawn_card = self.deck.draw();
+            if drawn_card.is_none() {
+                // there are no cards on the table at all
+                break;
+            }
+            let drawn_card = drawn_card.unwrap(); // safe since is_none() check + early break above
+
+            drawn_cards.push(drawn_card.clone());
+            player.give_card(drawn_card);
+        }
+
+        drawn_cards
+    }
+
+    /// Performs immutable checks whether the player is eligible to play a card.
+    fn can_player_play(
+        &self,
+        player_name: String,
+        card: &Card,
+        said_uno: bool,
+    ) -> Result<(), PlayCardError> {
+        if self.status != GameStatus::Running {
+            return Err(PlayCardError::GameNotRunning(self.id.clone()));
+        }
+
+        let player = self.does_player_exist(player_name)?;
+
+        self.is_player_at_turn(player)?;
+
+        if !player.should_say_uno() && said_uno {
+            return Err(PlayCardError::SaidUnoWhenShouldNotHave);
+        }
+
+        if !player.cards().contains(card) {
+            return Err(PlayCardError::PlayerHasNoSuchCard(card.clone()));
+        }
+
+        if !self.moves_of(player).playable_cards.contains(card) {
+            return Err(PlayCardError::CardCannotBePlayed(
+                card.clone(),
+                self.deck.top_discard_card().clone(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn play_card(
+        &mut self,
+        player_name: String,
+        card: Card,
+        maybe_new_color: Option<CardColor>,
+        said_uno: bool,
+    ) -> Result<Vec<Event>, PlayCardError> {
+        self.can_player_play(player_name.clone(), &card, said_uno)?;
+
+        // required to be borrowed before mutable section
+        let possible_position = self.get_finished_players().len();
+        let (played_card, player_finished, should_say_uno, called_uno) =
+            self.mutate_player(&player_name, card, maybe_new_color, possible_position)?;
+
+        self.handle_played_card(&played_card);
+        self.deck.play(played_card.clone());
+        self.end_turn();
+        self.play_card_events(
+            player_finished,
+            player_name,
+            played_card,
+            should_say_uno && !(said_uno || called_uno),
+        )
+        .map_err(PlayCardError::from)
+    }
+
+    /// Remembers that the player at turn said UNO! ahead of playing their second to last card.
+    pub fn call_uno(&mut self, player_name: String) -> Result<Vec<Event>, CallUnoError> {
+        if self.status != GameStatus::Running {
+            return Err(CallUnoError::GameNotRunning(self.id.clone()));
+        }
+
+        let player = self.does_player_exist(player_name.clone())?;
+        self.is_player_at_turn(player)?;
+
+        if !player.should_say_uno() {
+            return Err(CallUnoError::CannotCallUno);
+        }
+
+        self.find_player_mut(&player_name)
+            .unwrap() // safe because of does_player_exist() above
+            .call_uno();
+
+        Ok(vec![Event::UnoCalled { who: player_name }])
+    }
+
+    fn mutate_player(
+        &mut self,
+        player_name: &str,
+        wanted_card: Card,
+        maybe_new_color: Option<CardColor>,
+        possible_position: usize,
+    ) -> Result<(Card, bool, bool, bool), PlayCardError> {
+        let player = self
+            .players
+            .iter_mut()
+            .find(|player| player.name() == *player_name)
+            .unwrap(); // safe because of can_player_play() in play_card()
+
+        let should_have_said_uno = player.should_say_uno(); // acquired before removing a card from players' hands
+        let mut played_card = player.play_card(wanted_card)?;
+        let called_uno = player.forget_uno_call();
+
+        if played_card.should_be_black() {
+            if let Some(color) = maybe_new_color {
+                played_card = played_card.morph_black_card(color).unwrap(); // safe because of should_be_black() check above
+            }
+        }
+
+        let player_finished = player.cards().is_empty();
+        if player_finished {
+            player.set_position(possible_position);
+        }
+
+        Ok((
+            played_card,
+            player_finished,
+            should_have_said_uno,
+            called_uno,
+        ))
+    }
+
+    fn handle_played_card(&mut self, played_card: &Card) {
+        match played_card.symbol {
+            CardSymbol::Value(_) | CardSymbol::Wild => self.active_cards.clear(),
+            CardSymbol::Reverse => {
+                self.reverse();
+                self.active_cards.clear();
+            }
+            CardSymbol::Draw2 | CardSymbol::Draw4 | CardSymbol::Skip => {
+                self.active_cards.push(played_card.clone()).unwrap(); // match guard corresponds with ALLOWED_ACTIVE_CARDS in ActiveCards
+            }
+        }
+    }
+
+    /// Assumes player_name is a valid player name, meaning that such a player exists.
+    fn play_card_events(
+        &mut self,
+        player_finished: bool,
+        player_name: String,
+        played_card: Card,
+        player_penalized: bool,
+    ) -> Result<Vec<Event>, CreateStatusError> {
+        let next_player_name = match self.get_current_player() {
+            None => return Err(CreateStatusError::CurrentPlayerNotFound),
+            Some(player) => player.name(),
+        };
+        let mut events = vec![Event::CardPlayed {
+            who: player_name.clone(),
+            next: next_player_name,
+            card: played_card,
+        }];
+
+        if player_penalized {
+            let gained_cards = self.draw_n_cards(player_name.clone(), PENALTY_CARDS);
+            events.push(Event::Penalized {
+                who: player_name.clone(),
+                cards: gained_cards,
+            });
+        }
+
+        if player_finished {
+            events.push(Event::PlayerFinished { who: player_name });
+        }
+
+        events.append(&mut self.maybe_finish_game());
+
+        Ok(events)
+    }
+
+    /// Finishes the game when at most one player is left playing, or when only AIs are left playing.
+    fn maybe_finish_game(&mut self) -> Vec<Event> {
+        let only_one_left = self
+            .players
+            .iter()
+            .filter(|player| !self.is_out(player))
+            .count()
+            <= 1;
+        let only_ai_left = self.human_iter().all(|player| self.is_out(player));
+
+        if !only_one_left && !only_ai_left {
+            return vec![];
+        }
+
+        let mut events = self.finish_all_unfinished_players();
+        self.status = GameStatus::Finished;
+        if let Some(winner) = self.players.iter().find(|p| p.position() == Some(0)) {
+            *self.wins.entry(winner.name()).or_insert(0) += 1;
+        }
+        events.push(Event::GameFinished);
+
+        events
+    }
+
+    fn force_finish(&mut self) -> Result<Vec<Event>, ForceFinishError> {
+        if self.status != GameStatus::Running {
+            return Err(ForceFinishError::GameNotRunning(self.id.clone()));
+        }
+
+        let mut remaining: Vec<&Player> = self
+            .players
+            .iter()
+            .filter(|player| !self.is_out(player))
+            .collect();
+        remaining.sort_by_key(|player| player.get_card_count());
+        let mut order: Vec<String> = remaining.iter().map(|player| player.name()).collect();
+        order.extend(self.forfeited.iter().rev().cloned());
+
+        let first_position = self.get_finished_players().len();
+        for (index, name) in order.iter().enumerate() {
+            if let Some(player) = self.find_player_mut(name) {
+                player.set_position(first_position + index);
+            }
+        }
+
+        // nobody won a game that was not played out
+        self.status = GameStatus::Finished;
+        self.force_finished = true;
+
+        let mut events: Vec<Event> = order
+            .into_iter()
+            .map(|name| Event::PlayerFinished { who: name })
+            .collect();
+        events.push(Event::GameFinished);
+        Ok(events)
+    }
+
+    /// Whether the server operator ended the finished game before it was played out.
+    pub fn was_force_finished(&self) -> bool {
+        self.force_finished
+    }
+
+    /// Counter of the changes made to the game, clients send it back to act only on the state they saw.
+    pub fn turn(&self) -> u64 {
+        self.turn
+    }
+
+    /// Number of games won by every player who won at least one.
+    pub fn wins(&self) -> &BTreeMap<String, usize> {
+        &self.wins
+    }
+
+    /// Names of the humans who agreed to the rematch, None unless the author called one.
+    pub fn rematch_confirmed(&self) -> Option<&Vec<String>> {
+        self.rematch.as_ref()
+    }
+
+    /// Makes every following game of this one provably fair, see `Fairness`.
+    pub fn enable_fairness(&mut self) {
+        if self.fairness.is_none() {
+            self.fairness = Some(Fairness::new());
+        }
+    }
+
+    /// Commitment and entropy of a fair game, None if the game is not fair.
+    pub fn fairness(&self) -> Option<&Fairness> {
+        self.fairness.as_ref()
+    }
+
+    /// The revealed seed and every shuffle of a fair game, only once it finished.
+    pub fn fairness_proof(&self) -> Option<FairnessProof> {
+        match (&self.fairness, self.deck.shuffles()) {
+            (Some(fairness), Some(shuffles)) if self.status == GameStatus::Finished => {
+                Some(fairness.proof(shuffles.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    fn renew_fairness(&mut self) {
+        if self.fairness.is_some() {
+            self.fairness = Some(Fairness::new());
+        }
+    }
+
+    fn add_entropy(
+        &mut self,
+        player_name: String,
+        entropy: String,
+    ) -> Result<Vec<Event>, FairnessError> {
+        if self.fairness.is_none() {
+            return Err(FairnessError::NotFair);
+        }
+        if self.status != GameStatus::Lobby {
+            return Err(FairnessError::NotInLobby);
+        }
+        if self.find_player(player_name.clone()).is_none() {
+            return Err(FairnessError::NoSuchPlayer(player_name));
+        }
+        if entropy.is_empty() || entropy.chars().count() > MAX_ENTROPY_LENGTH {
+            return Err(FairnessError::InvalidEntropy);
+        }
+
+        if let Some(fairness) = self.fairness.as_mut() {
+            fairness.add_entropy(player_name, entropy);
+        }
+        Ok(vec![])
+    }
+
+    fn propose_rematch(&mut self, author_name: String) -> Result<Vec<Event>, RematchError> {
+        self.check_finished()?;
+        match self.find_player(author_name.clone()) {
+            Some(player) if player.is_author() => (),
+            _ => return Err(RematchError::NotAuthor(author_name)),
+        }
+        if self.rematch.is_some() {
+            return Err(RematchError::ReadyCheckRunning);
+        }
+
+        self.rematch = Some(vec![author_name.clone()]);
+        let mut events = vec![Event::RematchProposed { by: author_name }];
+        if self.everyone_confirmed_rematch() {
+            events.extend(self.back_to_lobby());
+        }
+
+        Ok(events)
+    }
+
+    fn confirm_rematch(&mut self, player_name: String) -> Result<Vec<Event>, RematchError> {
+        self.check_finished()?;
+        match self.find_player(player_name.clone()) {
+            None => return Err(RematchError::NoSuchPlayer(player_name)),
+            Some(player) if !player.is_human() => return Err(RematchError::NotHuman(player_name)),
+            Some(_) => (),
+        }
+
+        let confirmed = self.rematch.as_mut().ok_or(RematchError::NoReadyCheck)?;
+        if confirmed.contains(&player_name) {
+            return Ok(vec![]);
+        }
+        confirmed.push(player_name.clone());
+
+        let mut events = vec![Event::RematchConfirmed { who: player_name }];
+        if self.everyone_confirmed_rematch() {
+            events.extend(self.back_to_lobby());
+        }
+
+        Ok(events)
+    }
+
+    fn return_to_lobby(&mut self, author_name: String) -> Result<Vec<Event>, RematchError> {
+        self.check_finished()?;
+        match self.find_player(author_name.clone()) {
+            Some(player) if player.is_author() => (),
+            _ => return Err(RematchError::NotAuthor(author_name)),
+        }
+        if self.rematch.is_none() {
+            return Err(RematchError::NoReadyCheck);
+        }
+
+        Ok(self.back_to_lobby())
+    }
+
+    fn check_finished(&self) -> Result<(), RematchError> {
+        match self.status {
+            GameStatus::Finished => Ok(()),
+            _ => Err(RematchError::GameNotFinished(self.id.clone())),
+        }
+    }
+
+    fn everyone_confirmed_rematch(&self) -> bool {
+        let confirmed = self.rematch.as_deref().unwrap_or_default();
+        self.human_iter()
+            .all(|player| confirmed.contains(&player.name()))
+    }
+
+    /// Drops the humans who did not agree to the rematch and opens the lobby, keeping the AIs and the wins.
+    fn back_to_lobby(&mut self) -> Vec<Event> {
+        let confirmed = self.rematch.take().unwrap_or_default();
+        let (stay, dropped): (Vec<_>, Vec<_>) = std::mem::take(&mut self.players)
+            .into_iter()
+            .partition(|player| !player.is_human() || confirmed.contains(&player.name()));
+        self.players = stay;
+
+        for player in self.players.iter_mut() {
+            player.clear_position();
+            player.drop_all_cards();
+            player.forget_uno_call();
+        }
+        self.forfeited.clear();
+        self.active_cards.clear();
+        self.status = GameStatus::Lobby;
+        self.renew_fairness();
+
+        let mut events: Vec<Event> = dropped
+            .into_iter()
+            .map(|player| Event::PlayerLeft {
+                name: player.name(),
+                kicked: false,
+            })
+            .collect();
+        events.push(Event::ReturnedToLobby);
+
+        events
+    }
+
+    fn human_iter(&self) -> impl Iterator<Item = &Player> {
+        self.players.iter().filter(|player| player.is_human())
+    }
+
+    /// Finishes all players that are not yet finished, those who gave up last, the first of them at the very end.
+    /// Returns a PlayerFinished event for each of them.
+    fn finish_all_unfinished_players(&mut self) -> Vec<Event> {
+        if self.get_finished_players().is_empty() && self.forfeited.is_empty() {
+            return vec![];
+        }
+
+        let mut order: Vec<String> = self
+            .players
+            .iter()
+            .filter(|player| !self.is_out(player))
+            .map(|player| player.name())
+            .collect();
+        order.extend(self.forfeited.iter().rev().cloned());
+
+        let first_position = self.get_finished_players().len();
+        for (index, name) in order.iter().enumerate() {
+            if let Some(player) = self.find_player_mut(name) {
+                player.set_position(first_position + index);
+            }
+        }
+
+        order
+            .into_iter()
+            .map(|name| Event::PlayerFinished { who: name })
+            .collect()
+    }
+
+    /// Plays the turn of the AI player at turn, does nothing if it is not an AI's turn.
+    /// Deciding is instant, pausing to make the AI look human is up to the caller.
+    pub fn play_ai_turn(&mut self) -> Result<Vec<Event>, AiError> {
+        if !self.is_ai_turn() {
+            return Ok(vec![]);
+        }
+
+        let command = match decide_command(self) {
+            None => return Err(AiError::from(CreateStatusError::CurrentPlayerNotFound)),
+            Some(command) => command,
+        };
+
+        let events = match command {
+            Command::PlayCard {
+                player,
+                card,
+                new_color,
+                said_uno,
+            } => self.play_card(player, card, new_color, said_uno)?,
+            Command::DrawCards { player } => self.draw_cards(player)?,
+            _ => unreachable!("Impossible: AI only ever plays or draws cards"),
+        };
+
+        if !events.is_empty() {
+            self.turn += 1;
+        }
+        Ok(events)
+    }
+
+    /// Plays AI turns until it is a human's turn or the game ends.
+    pub fn play_ai_turns(&mut self) -> Result<Vec<Event>, AiError> {
+        let mut events = vec![];
+        while self.is_ai_turn() {
+            events.append(&mut self.play_ai_turn()?);
+        }
+
+        Ok(events)
+    }
+}
@@ -0,0 +1,14 @@
+use crate::cards::card::Card;
+use serde::Serialize;
+
+/// Moves a player is allowed to make right now. Players out of turn have no legal moves.
+#[derive(Debug, Serialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LegalMoves {
+    /// Cards from the player's hand that can be played, in the order they are held.
+    pub playable_cards: Vec<Card>,
+    /// True if the player has to draw, because there is no card they could play.
+    pub can_draw: bool,
+    /// True if the player cannot respond to an active Skip and their turn passes without drawing.
+    pub can_pass: bool,
+}
@@ -0,0 +1,15 @@
+//! State of a single game, together with the commands that change it and the events describing the changes.
+
+mod active_cards;
+pub mod builder;
+pub mod command;
+pub mod event;
+pub mod fairness;
+pub mod game;
+pub mod legal_moves;
+pub mod players;
+
+pub static CARDS_DEALT_TO_PLAYERS: usize = 7;
+pub static PENALTY_CARDS: usize = 2;
+/// Most players a game can have, AIs included, so that there are always enough cards to deal.
+pub static MAX_PLAYERS: usize = 10;
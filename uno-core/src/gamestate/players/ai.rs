@@ -0,0 +1,42 @@
+use crate::cards::card::{Card, CardColor};
+use crate::cards::random_color;
+use crate::gamestate::command::Command;
+use crate::gamestate::game::Game;
+use rand::Rng;
+use std::time::Duration;
+
+pub fn decide_new_color(card: &Card) -> Option<CardColor> {
+    if card.should_be_black() {
+        Some(random_color())
+    } else {
+        None
+    }
+}
+
+pub fn decide_sleep_time() -> Duration {
+    Duration::from_secs(rand::thread_rng().gen_range(1..=2))
+}
+
+/// Decides what the current player of the game should do, returns None if there is no current player.
+/// The AI simply plays the first card it legally can and draws otherwise.
+pub fn decide_command(game: &Game) -> Option<Command> {
+    let current_player = game.get_current_player()?;
+    let maybe_card = game
+        .legal_moves(&current_player.name())
+        .ok()?
+        .playable_cards
+        .into_iter()
+        .next();
+
+    Some(match maybe_card {
+        Some(card) => Command::PlayCard {
+            player: current_player.name(),
+            new_color: decide_new_color(&card),
+            said_uno: current_player.should_say_uno(),
+            card,
+        },
+        None => Command::DrawCards {
+            player: current_player.name(),
+        },
+    })
+}
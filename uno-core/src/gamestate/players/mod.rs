@@ -1,3 +1,5 @@
+//! Players of a game, humans and AIs alike, and the decision making of the AI.
+
 pub mod ai;
 pub mod player;
 
@@ -8,7 +10,7 @@ pub(self) mod name_generation {
     const NAMES_FILE: &str = "names.txt";
 
     #[derive(RustEmbed)]
-    #[folder = "static/resources"]
+    #[folder = "resources"]
     #[include = "*.txt"]
     struct Resource;
 
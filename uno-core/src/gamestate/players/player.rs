@@ -1,9 +1,8 @@
 use crate::cards::card::Card;
 use crate::err::play_card::PlayCardError;
 use crate::gamestate::players::name_generation::get_random_name;
-use crate::ws::ws_conn::WSConn;
-use crate::ws::ws_message::WSMsg;
 
+/// A seat in the game, holding the player's hand and their progress towards finishing.
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Player {
     name: String,
@@ -11,7 +10,7 @@ pub struct Player {
     is_human: bool,
     cards: Vec<Card>,
     position: Option<usize>,
-    connection: Option<WSConn>,
+    called_uno: bool,
 }
 
 impl Player {
@@ -22,7 +21,7 @@ impl Player {
             is_human,
             cards: vec![],
             position: None,
-            connection: None,
+            called_uno: false,
         }
     }
 
@@ -99,18 +98,20 @@ impl Player {
         self.is_author
     }
 
+    pub fn set_author(&mut self, is_author: bool) {
+        self.is_author = is_author
+    }
+
     pub fn is_human(&self) -> bool {
         self.is_human
     }
 
-    pub fn message(&self, msg: WSMsg) {
-        match &self.connection {
-            Some(conn) => conn.send(msg),
-            None => (),
-        }
+    pub fn call_uno(&mut self) {
+        self.called_uno = true
     }
 
-    pub fn set_connection(&mut self, connection: WSConn) {
-        self.connection = Option::Some(connection)
+    /// Clears a previous UNO! call, returns whether there was one.
+    pub fn forget_uno_call(&mut self) -> bool {
+        std::mem::replace(&mut self.called_uno, false)
     }
 }
@@ -0,0 +1,22 @@
+//! Rules engine of the card game UNO.
+//!
+//! The engine is transport agnostic: a [`Game`](gamestate::game::Game) is driven by
+//! [`Command`](gamestate::command::Command)s and describes everything that happened in return as a list of
+//! [`Event`](gamestate::event::Event)s, without performing any I/O. Delivering the events to the players,
+//! as well as pacing the turns of AI players, is left to the caller.
+//!
+//! ```
+//! use uno_core::gamestate::command::Command;
+//! use uno_core::gamestate::event::Event;
+//! use uno_core::gamestate::game::Game;
+//!
+//! let mut game = Game::new("Andy".into());
+//! game.apply(Command::Join { name: "Bob".into() }).unwrap();
+//!
+//! let events = game.apply(Command::Start { player: "Andy".into() }).unwrap();
+//! assert_eq!(events, vec![Event::GameStarted]);
+//! ```
+
+pub mod cards;
+pub mod err;
+pub mod gamestate;
@@ -0,0 +1,163 @@
+use crate::err::command::CommandError;
+use crate::err::fairness::FairnessError;
+use crate::err::game_start::GameStartError;
+use crate::err::verification::VerificationError;
+use crate::gamestate::command::Command;
+use crate::gamestate::fairness::{FairnessProof, MAX_ENTROPY_LENGTH};
+use crate::gamestate::game::{Game, GameStatus};
+
+fn add_entropy(game: &mut Game, player: &str, entropy: &str) -> Result<(), FairnessError> {
+    match game.apply(Command::AddEntropy {
+        player: player.into(),
+        entropy: entropy.into(),
+    }) {
+        Ok(_) => Ok(()),
+        Err(CommandError::Fairness(error)) => Err(error),
+        Err(error) => panic!("unexpected error {}", error),
+    }
+}
+
+fn forfeit(game: &mut Game, player: &str) {
+    game.apply(Command::Forfeit {
+        player: player.into(),
+    })
+    .unwrap();
+}
+
+fn finished_fair_game() -> Game {
+    let mut game = Game::new("Andy".into());
+    game.add_player("Bob".into()).unwrap();
+    game.add_player("Carl".into()).unwrap();
+    game.enable_fairness();
+    add_entropy(&mut game, "Bob", "correct horse battery staple").unwrap();
+    game.start().unwrap();
+
+    // the cards of those who forfeit are shuffled back into the draw pile
+    forfeit(&mut game, "Carl");
+    forfeit(&mut game, "Bob");
+    assert_eq!(game.status(), GameStatus::Finished);
+
+    game
+}
+
+#[test]
+fn test_proof_of_finished_game_verifies() {
+    let game = finished_fair_game();
+    let proof = game.fairness_proof().unwrap();
+
+    assert_eq!(proof.commitment, game.fairness().unwrap().commitment());
+    assert_eq!(proof.entropy.len(), 1);
+    // the first deal and the two forfeits
+    assert_eq!(proof.shuffles.len(), 3);
+    assert_eq!(proof.verify(), Ok(()));
+
+    let json = serde_json::to_string(&proof).unwrap();
+    let parsed: FairnessProof = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.verify(), Ok(()));
+}
+
+#[test]
+fn test_tampered_proof_fails() {
+    let proof = finished_fair_game().fairness_proof().unwrap();
+
+    let mut other_seed = proof.clone();
+    other_seed.seed = "00".repeat(32);
+    assert_eq!(
+        other_seed.verify(),
+        Err(VerificationError::CommitmentMismatch)
+    );
+
+    let mut invalid_seed = proof.clone();
+    invalid_seed.seed = "not hex".into();
+    assert_eq!(invalid_seed.verify(), Err(VerificationError::InvalidSeed));
+
+    let mut other_entropy = proof.clone();
+    other_entropy
+        .entropy
+        .insert("Andy".into(), "chosen after the fact".into());
+    assert_eq!(
+        other_entropy.verify(),
+        Err(VerificationError::ShuffleMismatch(0))
+    );
+
+    let mut swapped = proof.clone();
+    let last = swapped.shuffles[1].len() - 1;
+    swapped.shuffles[1].swap(0, last);
+    if swapped.shuffles[1] != proof.shuffles[1] {
+        assert_eq!(swapped.verify(), Err(VerificationError::ShuffleMismatch(1)));
+    }
+
+    let mut missing_card = proof;
+    missing_card.shuffles[0].pop();
+    assert_eq!(missing_card.verify(), Err(VerificationError::NotAFullDeck));
+}
+
+#[test]
+fn test_proof_is_revealed_only_when_finished() {
+    let mut game = Game::new("Andy".into());
+    game.add_player("Bob".into()).unwrap();
+    assert!(game.fairness().is_none());
+
+    game.enable_fairness();
+    let commitment = game.fairness().unwrap().commitment();
+    game.start().unwrap();
+    assert!(game.fairness_proof().is_none());
+    assert_eq!(game.fairness().unwrap().commitment(), commitment);
+
+    forfeit(&mut game, "Bob");
+    assert!(game.fairness_proof().is_some());
+
+    // the revealed seed must not be used again, the next one is committed to in the lobby
+    assert!(matches!(
+        game.start(),
+        Err(GameStartError::FairGameNotInLobby)
+    ));
+    game.apply(Command::ProposeRematch {
+        author: "Andy".into(),
+    })
+    .unwrap();
+    game.apply(Command::ConfirmRematch {
+        player: "Bob".into(),
+    })
+    .unwrap();
+    assert_eq!(game.status(), GameStatus::Lobby);
+    let next_commitment = game.fairness().unwrap().commitment();
+    assert_ne!(next_commitment, commitment);
+    assert_eq!(add_entropy(&mut game, "Bob", "42"), Ok(()));
+    game.start().unwrap();
+    assert_eq!(game.fairness().unwrap().commitment(), next_commitment);
+}
+
+#[test]
+fn test_add_entropy() {
+    let mut game = Game::new("Andy".into());
+    game.add_player("Bob".into()).unwrap();
+
+    assert_eq!(
+        add_entropy(&mut game, "Bob", "42"),
+        Err(FairnessError::NotFair)
+    );
+
+    game.enable_fairness();
+    assert_eq!(
+        add_entropy(&mut game, "Carl", "42"),
+        Err(FairnessError::NoSuchPlayer("Carl".into()))
+    );
+    assert_eq!(
+        add_entropy(&mut game, "Bob", ""),
+        Err(FairnessError::InvalidEntropy)
+    );
+    assert_eq!(
+        add_entropy(&mut game, "Bob", &"x".repeat(MAX_ENTROPY_LENGTH + 1)),
+        Err(FairnessError::InvalidEntropy)
+    );
+    assert_eq!(add_entropy(&mut game, "Bob", "42"), Ok(()));
+    assert_eq!(add_entropy(&mut game, "Bob", "43"), Ok(()));
+    assert_eq!(game.fairness().unwrap().entropy()["Bob"], "43");
+
+    game.start().unwrap();
+    assert_eq!(
+        add_entropy(&mut game, "Bob", "42"),
+        Err(FairnessError::NotInLobby)
+    );
+}
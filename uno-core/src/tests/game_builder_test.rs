@@ -0,0 +1,238 @@
+use crate::cards::card::{Card, CardColor, CardSymbol};
+use crate::err::game_build::GameBuildError;
+use crate::gamestate::builder::GameBuilder;
+use crate::gamestate::command::Command;
+use crate::gamestate::event::Event;
+use crate::gamestate::game::{Game, GameStatus};
+use crate::gamestate::CARDS_DEALT_TO_PLAYERS;
+
+fn card(color: CardColor, symbol: CardSymbol) -> Card {
+    Card::new(color, symbol).unwrap()
+}
+
+fn filler() -> Vec<Card> {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    vec![card(Green, Value(1)), card(Green, Value(2))]
+}
+
+#[test]
+fn test_build_exact_state() {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    let game = GameBuilder::new("Andy")
+        .human("Bob")
+        .ai("Robot")
+        .hand("Bob", vec![card(Red, Value(1)), card(Blue, Skip)])
+        .draw_pile(vec![card(Yellow, Value(7)), card(Yellow, Value(8))])
+        .top_card(card(Red, Value(9)))
+        .current_player("Robot")
+        .clockwise(false)
+        .build()
+        .unwrap();
+
+    assert_eq!(game.status(), GameStatus::Running);
+    assert!(game.find_author().unwrap().name() == "Andy");
+    assert!(!game.find_player("Robot".into()).unwrap().is_human());
+    assert_eq!(
+        game.find_player("Bob".into()).unwrap().cards(),
+        vec![card(Red, Value(1)), card(Blue, Skip)]
+    );
+    assert_eq!(game.get_current_player().unwrap().name(), "Robot");
+    assert!(!game.is_clockwise);
+    assert_eq!(game.deck().top_discard_card(), &card(Red, Value(9)));
+    let mut deck = game.deck().clone();
+    assert_eq!(deck.draw(), Some(card(Yellow, Value(7))));
+    assert_eq!(deck.draw(), Some(card(Yellow, Value(8))));
+}
+
+#[test]
+fn test_unset_cards_come_from_one_deck() {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    let total_cards = |game: &Game| {
+        let hands: usize = game
+            .players()
+            .iter()
+            .map(|player| player.cards().len())
+            .sum();
+        hands + game.deck().draw_pile_size() + game.deck().discard_pile_size()
+    };
+
+    let game = GameBuilder::new("Andy").human("Bob").build().unwrap();
+    assert_eq!(total_cards(&game), 108);
+    for player in game.players() {
+        assert_eq!(player.cards().len(), CARDS_DEALT_TO_PLAYERS);
+    }
+
+    // the only red zero is in Andy's hand, not a second time in the deck
+    let red_zero = card(Red, Value(0));
+    let game = GameBuilder::new("Andy")
+        .human("Bob")
+        .hand("Andy", vec![red_zero.clone()])
+        .build()
+        .unwrap();
+    assert_eq!(total_cards(&game), 108);
+    assert_eq!(
+        game.find_player("Bob".into()).unwrap().cards().len(),
+        CARDS_DEALT_TO_PLAYERS
+    );
+    let mut deck = game.deck().clone();
+    assert_ne!(deck.top_discard_card(), &red_zero);
+    while let Some(card) = deck.draw() {
+        assert_ne!(card, red_zero);
+    }
+}
+
+#[test]
+fn test_build_errors() {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    assert_eq!(
+        GameBuilder::new("Andy")
+            .hand("Bob", vec![])
+            .build()
+            .unwrap_err(),
+        GameBuildError::NoSuchPlayer("Bob".into())
+    );
+    assert_eq!(
+        GameBuilder::new("Andy")
+            .current_player("Bob")
+            .build()
+            .unwrap_err(),
+        GameBuildError::NoSuchPlayer("Bob".into())
+    );
+    assert_eq!(
+        GameBuilder::new("Andy").human("Andy").build().unwrap_err(),
+        GameBuildError::DuplicatePlayer("Andy".into())
+    );
+    assert!(matches!(
+        GameBuilder::new("Andy")
+            .active_cards(vec![card(Red, Draw2), card(Red, Skip)])
+            .build(),
+        Err(GameBuildError::InvalidActiveCards(_))
+    ));
+}
+
+#[test]
+fn test_stacked_draw2_across_reversed_direction() {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    let drawn = vec![
+        card(Yellow, Value(1)),
+        card(Yellow, Value(2)),
+        card(Yellow, Value(3)),
+        card(Yellow, Value(4)),
+    ];
+    let mut game = GameBuilder::new("Andy")
+        .human("Bob")
+        .human("Candace")
+        .human("Danny")
+        .hand("Andy", [vec![card(Red, Draw2)], filler()].concat())
+        .hand("Bob", filler())
+        .hand("Candace", vec![card(Red, Value(5)), card(Blue, Value(6))])
+        .hand("Danny", [vec![card(Blue, Draw2)], filler()].concat())
+        .draw_pile([drawn.clone(), filler()].concat())
+        .top_card(card(Red, Value(9)))
+        .clockwise(false)
+        .build()
+        .unwrap();
+
+    game.apply(Command::PlayCard {
+        player: "Andy".into(),
+        card: card(Red, Draw2),
+        new_color: None,
+        said_uno: false,
+    })
+    .unwrap();
+    assert_eq!(game.get_current_player().unwrap().name(), "Danny");
+
+    game.apply(Command::PlayCard {
+        player: "Danny".into(),
+        card: card(Blue, Draw2),
+        new_color: None,
+        said_uno: false,
+    })
+    .unwrap();
+    assert_eq!(game.get_current_player().unwrap().name(), "Candace");
+
+    let moves = game.legal_moves("Candace").unwrap();
+    assert!(moves.playable_cards.is_empty());
+    assert!(moves.can_draw);
+
+    let events = game
+        .apply(Command::DrawCards {
+            player: "Candace".into(),
+        })
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![Event::CardsDrawn {
+            who: "Candace".into(),
+            next: "Bob".into(),
+            cards: drawn,
+        }]
+    );
+    assert_eq!(game.find_player("Candace".into()).unwrap().cards().len(), 6);
+    assert!(game.active_symbol().is_none());
+}
+
+#[test]
+fn test_active_draw4_stack_is_drawn_at_once() {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    let plus_4 = card(Black, Draw4).morph_black_card(Blue).unwrap();
+    let mut game = GameBuilder::new("Andy")
+        .human("Bob")
+        .hand("Andy", filler())
+        .hand("Bob", filler())
+        .top_card(plus_4.clone())
+        .active_cards(vec![plus_4.clone(), plus_4])
+        .build()
+        .unwrap();
+
+    game.apply(Command::DrawCards {
+        player: "Andy".into(),
+    })
+    .unwrap();
+    assert_eq!(
+        game.find_player("Andy".into()).unwrap().cards().len(),
+        2 + 8
+    );
+    assert_eq!(game.get_current_player().unwrap().name(), "Bob");
+}
+
+#[test]
+fn test_build_from_json() {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    let builder: GameBuilder = serde_json::from_str(
+        r#"{
+            "players": [
+                {"name": "Andy", "hand": [{"color": "RED", "type": "VALUE", "value": 1}]},
+                {"name": "Robot", "isAi": true}
+            ],
+            "topCard": {"color": "RED", "type": "SKIP", "value": null},
+            "activeCards": [{"color": "RED", "type": "SKIP", "value": null}],
+            "currentPlayer": "Andy"
+        }"#,
+    )
+    .unwrap();
+    let game = builder.build().unwrap();
+
+    assert_eq!(game.players().len(), 2);
+    assert!(game.is_clockwise);
+    assert_eq!(game.active_symbol(), Some(Skip));
+    assert!(game.legal_moves("Andy").unwrap().can_pass);
+    assert_eq!(
+        game.find_player("Andy".into()).unwrap().cards(),
+        vec![card(Red, Value(1))]
+    );
+}
@@ -1,8 +1,21 @@
 use crate::cards::card::{Card, CardColor, CardSymbol};
 use crate::err::add_player::AddPlayerError;
+use crate::err::call_uno::CallUnoError;
+use crate::err::command::CommandError;
+use crate::err::draw_cards::PlayerDrawError;
+use crate::err::force_finish::ForceFinishError;
+use crate::err::forfeit::ForfeitError;
+use crate::err::game_start::GameStartError;
+use crate::err::lobby::LobbyError;
+use crate::err::play_card::PlayCardError;
+use crate::err::rematch::RematchError;
+use crate::gamestate::builder::GameBuilder;
+use crate::gamestate::command::Command;
+use crate::gamestate::event::Event;
 use crate::gamestate::game::{Game, GameStatus};
+use crate::gamestate::legal_moves::LegalMoves;
 use crate::gamestate::players::player::Player;
-use crate::gamestate::CARDS_DEALT_TO_PLAYERS;
+use crate::gamestate::{CARDS_DEALT_TO_PLAYERS, MAX_PLAYERS, PENALTY_CARDS};
 
 static CARDS_TOTAL_IN_GAME: usize = 108;
 
@@ -81,10 +94,10 @@ fn test_author_is_first_before_start() {
 #[test]
 fn test_draw_cards_errors() {
     let mut game = Game::new("Andy".into());
-
-    assert!(game.draw_cards("Bobby".into()).is_err()); // nonexistent player
-
     game.add_player("Bobby".into()).unwrap();
+    game.status = GameStatus::Running;
+
+    assert!(game.draw_cards("Bobbie".into()).is_err()); // nonexistent player
     assert!(game.draw_cards("Bobby".into()).is_err()); // not the current player
 
     let top_card = game.deck.top_discard_card().clone();
@@ -107,6 +120,7 @@ fn test_draw_cards_errors() {
 #[test]
 fn test_draw_cards_draws() {
     let mut game = Game::new("Andy".into());
+    game.status = GameStatus::Running;
     game.deck
         .play(Card::new(CardColor::Blue, CardSymbol::Draw2).unwrap());
     assert!(game
@@ -292,6 +306,7 @@ fn test_active_cards() {
     use CardSymbol::*;
 
     let mut game = Game::new("Andy".into());
+    game.status = GameStatus::Running;
     assert_eq!(game.active_cards.active_symbol(), None);
     assert_eq!(game.active_cards.sum_active_draw_cards(), None);
 
@@ -338,6 +353,7 @@ fn test_active_cards() {
     assert!(game.active_cards.push(eight.clone()).is_err());
     assert!(!game.active_cards.are_cards_active());
 
+    game.status = GameStatus::Running; // a single player game finishes with every played card
     assert!(game
         .play_card("Andy".into(), blu_skip.clone(), None, true)
         .is_ok());
@@ -362,6 +378,7 @@ fn test_active_cards() {
     assert_eq!(game.active_cards.active_symbol(), Some(Skip));
     assert_eq!(game.active_cards.sum_active_draw_cards(), None);
 
+    game.status = GameStatus::Running;
     assert!(game
         .play_card("Andy".into(), green_skip.clone(), None, false)
         .is_ok());
@@ -480,6 +497,7 @@ fn test_say_uno() {
     game.add_player("Bob".into()).unwrap();
     game.add_player("Candace".into()).unwrap();
     game.add_player("Danny".into()).unwrap();
+    game.status = GameStatus::Running;
     let eight = Card::new(Blue, Value(8)).unwrap();
     game.deck.play(eight.clone());
 
@@ -528,6 +546,7 @@ fn test_skip() {
     let mut game = Game::new("Andy".into());
     game.add_player("Bob".into()).unwrap();
     game.add_player("Candace".into()).unwrap();
+    game.status = GameStatus::Running;
 
     // give some starting cards to players to not trigger endgame
     for player in game.players.iter_mut() {
@@ -581,6 +600,155 @@ fn test_add_player() {
     );
 }
 
+#[test]
+fn test_turn_counts_changes() {
+    let mut game = Game::new_with_ai("Andy".into(), 1);
+    assert_eq!(game.turn(), 0);
+
+    game.apply(Command::Join { name: "Bob".into() }).unwrap();
+    assert_eq!(game.turn(), 1);
+    // rejected commands change nothing
+    assert!(game.apply(Command::Join { name: "Bob".into() }).is_err());
+    assert_eq!(game.turn(), 1);
+    // neither do commands without any event
+    game.enable_fairness();
+    assert_eq!(
+        game.apply(Command::AddEntropy {
+            player: "Bob".into(),
+            entropy: "42".into(),
+        })
+        .unwrap(),
+        vec![]
+    );
+    assert_eq!(game.turn(), 1);
+
+    game.apply(Command::Start {
+        player: "Andy".into(),
+    })
+    .unwrap();
+    let turn = game.turn();
+    let ai_turns = game.play_ai_turns().unwrap();
+    assert_eq!(game.turn() > turn, !ai_turns.is_empty());
+}
+
+#[test]
+fn test_lobby_size_is_capped() {
+    let mut game = Game::new_with_ai("Andy".into(), MAX_PLAYERS - 2);
+    game.add_player("Bob".into()).unwrap();
+
+    assert_eq!(
+        game.add_player("Carl".into()),
+        Err(AddPlayerError::LobbyFull)
+    );
+    assert!(matches!(
+        game.apply(Command::AddAi {
+            author: "Andy".into()
+        }),
+        Err(CommandError::Lobby(LobbyError::LobbyFull))
+    ));
+    assert_eq!(game.players().len(), MAX_PLAYERS);
+}
+
+#[test]
+fn test_kick_and_leave() {
+    let mut game = Game::new("Andy".into());
+    game.add_player("Bob".into()).unwrap();
+    game.add_player("Carl".into()).unwrap();
+
+    let kick = |author: &str, player: &str| Command::Kick {
+        author: author.into(),
+        player: player.into(),
+    };
+    assert!(matches!(
+        game.apply(kick("Bob", "Carl")),
+        Err(CommandError::Lobby(LobbyError::NotAuthor(_)))
+    ));
+    assert!(matches!(
+        game.apply(kick("Andy", "Andy")),
+        Err(CommandError::Lobby(LobbyError::CannotKickSelf))
+    ));
+
+    assert_eq!(
+        game.apply(kick("Andy", "Carl")).unwrap(),
+        vec![Event::PlayerLeft {
+            name: "Carl".into(),
+            kicked: true
+        }]
+    );
+    assert!(game.find_player("Carl".into()).is_none());
+    assert_eq!(
+        game.add_player("Carl".into()).unwrap_err(),
+        AddPlayerError::Kicked("Carl".into())
+    );
+
+    // the author leaving hands the game over to the next human
+    assert_eq!(
+        game.apply(Command::Leave {
+            player: "Andy".into()
+        })
+        .unwrap(),
+        vec![
+            Event::PlayerLeft {
+                name: "Andy".into(),
+                kicked: false
+            },
+            Event::AuthorChanged { name: "Bob".into() }
+        ]
+    );
+    assert!(game.find_player("Bob".into()).unwrap().is_author());
+}
+
+#[test]
+fn test_manage_ais_and_author() {
+    let mut game = Game::new("Andy".into());
+    game.add_player("Bob".into()).unwrap();
+
+    game.apply(Command::AddAi {
+        author: "Andy".into(),
+    })
+    .unwrap();
+    let ai_name = game.players().last().unwrap().name();
+    assert!(!game.players().last().unwrap().is_human());
+
+    assert!(matches!(
+        game.apply(Command::RemoveAi {
+            author: "Andy".into(),
+            player: "Bob".into()
+        }),
+        Err(CommandError::Lobby(LobbyError::NotAnAi(_)))
+    ));
+    assert!(matches!(
+        game.apply(Command::TransferAuthor {
+            author: "Andy".into(),
+            to: ai_name.clone()
+        }),
+        Err(CommandError::Lobby(LobbyError::NotHuman(_)))
+    ));
+
+    game.apply(Command::RemoveAi {
+        author: "Andy".into(),
+        player: ai_name,
+    })
+    .unwrap();
+    assert_eq!(game.players().len(), 2);
+
+    game.apply(Command::TransferAuthor {
+        author: "Andy".into(),
+        to: "Bob".into(),
+    })
+    .unwrap();
+    assert_eq!(game.find_author().unwrap().name(), "Bob");
+    assert!(!game.find_player("Andy".into()).unwrap().is_author());
+
+    game.start().unwrap();
+    assert!(matches!(
+        game.apply(Command::AddAi {
+            author: "Bob".into()
+        }),
+        Err(CommandError::Lobby(LobbyError::NotInLobby))
+    ));
+}
+
 #[test]
 fn test_game_end() {
     use CardColor::*;
@@ -660,6 +828,7 @@ fn test_ai_does_not_hold() {
 
     // simulate game start without the random order
     assert!(game.deal_starting_cards().is_ok());
+    game.status = GameStatus::Running;
 
     // let Andy play
     let skip = Card::new(Blue, Skip).unwrap();
@@ -667,10 +836,11 @@ fn test_ai_does_not_hold() {
     game.players.get_mut(0).unwrap().give_card(skip.clone());
 
     let skip = Card::new(Blue, Skip).unwrap();
-    // this will cause all the other AI players to play too
     assert!(game
         .play_card("Andy".into(), skip.clone(), None, false)
         .is_ok());
+    // all the other AI players play too
+    assert!(game.play_ai_turns().is_ok());
 
     // we should be back at Andy
     assert!(game.get_current_player().unwrap().is_human());
@@ -686,6 +856,7 @@ fn test_only_ai_finishes_game() {
     use CardSymbol::*;
 
     let mut game = Game::new_with_ai("Andy".into(), 6);
+    game.status = GameStatus::Running;
 
     // let Andy play his only one card
     let skip = Card::new(Blue, Skip).unwrap();
@@ -693,7 +864,7 @@ fn test_only_ai_finishes_game() {
     game.players.get_mut(0).unwrap().give_card(skip.clone());
 
     let skip = Card::new(Blue, Skip).unwrap();
-    // this will cause all the other AI players to play too, but th
+    // the AI players do not get to play at all
     assert!(game
         .play_card("Andy".into(), skip.clone(), None, false)
         .is_ok());
@@ -708,6 +879,7 @@ fn test_game_finish_with_only_ai_left_playing() {
     use CardSymbol::*;
 
     let mut game = Game::new_with_ai("Andy".into(), 2);
+    game.status = GameStatus::Running;
     // let Andy play his only one card
     let value_2 = Card::new(Blue, Value(2)).unwrap();
     game.deck.play(value_2.clone()); // ensure playability
@@ -738,6 +910,7 @@ fn test_game_finish_with_only_ai_left_playing() {
     assert!(game
         .play_card("Andy".into(), value_2.clone(), None, true)
         .is_ok());
+    assert!(game.play_ai_turns().is_ok());
     assert!(!game.players.get(0).unwrap().is_finished());
     assert!(game.players.get(1).unwrap().is_finished());
     assert!(!game.players.get(2).unwrap().is_finished());
@@ -752,7 +925,7 @@ fn test_game_finish_with_only_ai_left_playing() {
     assert_eq!(game.status, GameStatus::Finished);
     assert!(game.players.get(0).unwrap().is_finished());
     assert!(game.players.get(1).unwrap().is_finished());
-    assert!(game.players.get(2).unwrap().is_finished()); // set in maybe_ai_turn()
+    assert!(game.players.get(2).unwrap().is_finished()); // set in maybe_finish_game()
 }
 
 #[test]
@@ -763,6 +936,7 @@ fn test_all_players_are_finished_when_game_is_finished() {
     let mut game = Game::new("Andy".into());
     game.add_player("Bob".into()).unwrap();
     game.add_player("Candace".into()).unwrap();
+    game.status = GameStatus::Running;
 
     let value_2 = Card::new(Blue, Value(2)).unwrap();
     game.deck.play(value_2.clone()); // ensure playability
@@ -800,7 +974,7 @@ fn test_all_players_are_finished_when_game_is_finished() {
     assert_eq!(game.status, GameStatus::Finished);
     assert!(game.players.get(0).unwrap().is_finished());
     assert!(game.players.get(1).unwrap().is_finished());
-    assert!(game.players.get(2).unwrap().is_finished()); // set in play_card_messages
+    assert!(game.players.get(2).unwrap().is_finished()); // set in maybe_finish_game()
     assert_eq!(
         game.get_finished_players()
             .into_iter()
@@ -809,3 +983,497 @@ fn test_all_players_are_finished_when_game_is_finished() {
         vec!["Andy".to_string(), "Bob".to_string(), "Candace".to_string()]
     );
 }
+
+#[test]
+fn test_apply_join_and_start() {
+    let mut game = Game::new("Andy".into());
+
+    assert_eq!(
+        game.apply(Command::Join { name: "Bob".into() }).unwrap(),
+        vec![Event::PlayerJoined { name: "Bob".into() }]
+    );
+    assert!(matches!(
+        game.apply(Command::Start {
+            player: "Bob".into()
+        }),
+        Err(CommandError::Start(GameStartError::NotAuthor(_)))
+    ));
+    assert_eq!(
+        game.apply(Command::Start {
+            player: "Andy".into()
+        })
+        .unwrap(),
+        vec![Event::GameStarted]
+    );
+    assert!(matches!(
+        game.apply(Command::Join {
+            name: "Candace".into()
+        }),
+        Err(CommandError::AddPlayer(AddPlayerError::NotAcceptingPlayers))
+    ));
+}
+
+#[test]
+fn test_apply_requires_running_game() {
+    let mut game = Game::new("Andy".into());
+    let card = game.deck.top_discard_card().clone();
+    game.players.get_mut(0).unwrap().give_card(card.clone());
+
+    assert!(matches!(
+        game.apply(Command::PlayCard {
+            player: "Andy".into(),
+            card,
+            new_color: None,
+            said_uno: false,
+        }),
+        Err(CommandError::PlayCard(PlayCardError::GameNotRunning(_)))
+    ));
+    assert!(matches!(
+        game.apply(Command::DrawCards {
+            player: "Andy".into()
+        }),
+        Err(CommandError::DrawCards(PlayerDrawError::GameNotRunning(_)))
+    ));
+}
+
+#[test]
+fn test_apply_play_card_events() {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    let mut game = Game::new("Andy".into());
+    game.add_player("Bob".into()).unwrap();
+    game.status = GameStatus::Running;
+
+    let eight = Card::new(Blue, Value(8)).unwrap();
+    game.deck.play(eight.clone());
+    game.players.get_mut(0).unwrap().give_card(eight.clone());
+    game.players.get_mut(0).unwrap().give_card(eight.clone());
+    game.players.get_mut(1).unwrap().give_card(eight.clone());
+
+    let events = game
+        .apply(Command::PlayCard {
+            player: "Andy".into(),
+            card: eight.clone(),
+            new_color: None,
+            said_uno: false,
+        })
+        .unwrap();
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(
+        events[0],
+        Event::CardPlayed {
+            who: "Andy".into(),
+            next: "Bob".into(),
+            card: eight.clone(),
+        }
+    );
+    match &events[1] {
+        Event::Penalized { who, cards } => {
+            assert_eq!(who, "Andy");
+            assert_eq!(cards.len(), PENALTY_CARDS);
+        }
+        other => panic!("Expected a penalty, got {:?}", other),
+    }
+
+    let events = game
+        .apply(Command::PlayCard {
+            player: "Bob".into(),
+            card: eight.clone(),
+            new_color: None,
+            said_uno: false,
+        })
+        .unwrap();
+
+    assert_eq!(
+        events,
+        vec![
+            Event::CardPlayed {
+                who: "Bob".into(),
+                next: "Andy".into(),
+                card: eight,
+            },
+            Event::PlayerFinished { who: "Bob".into() },
+            Event::PlayerFinished { who: "Andy".into() },
+            Event::GameFinished,
+        ]
+    );
+    assert_eq!(game.status, GameStatus::Finished);
+}
+
+#[test]
+fn test_apply_draw_cards_events() {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    let mut game = Game::new("Andy".into());
+    game.add_player("Bob".into()).unwrap();
+    game.status = GameStatus::Running;
+
+    game.deck.play(Card::new(Blue, Value(8)).unwrap());
+    game.players
+        .get_mut(0)
+        .unwrap()
+        .give_card(Card::new(Red, Value(2)).unwrap());
+
+    let events = game
+        .apply(Command::DrawCards {
+            player: "Andy".into(),
+        })
+        .unwrap();
+
+    match events.as_slice() {
+        [Event::CardsDrawn { who, next, cards }] => {
+            assert_eq!(who, "Andy");
+            assert_eq!(next, "Bob");
+            assert_eq!(cards.len(), 1);
+        }
+        other => panic!("Expected a single draw, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_call_uno() {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    let mut game = Game::new("Andy".into());
+    game.add_player("Bob".into()).unwrap();
+    game.status = GameStatus::Running;
+
+    let eight = Card::new(Blue, Value(8)).unwrap();
+    game.deck.play(eight.clone());
+    for _ in 0..3 {
+        game.players.get_mut(0).unwrap().give_card(eight.clone());
+    }
+    game.players.get_mut(1).unwrap().give_card(eight.clone());
+
+    // three cards in hand
+    assert!(matches!(
+        game.call_uno("Andy".into()),
+        Err(CallUnoError::CannotCallUno)
+    ));
+    // not at turn
+    assert!(matches!(
+        game.call_uno("Bob".into()),
+        Err(CallUnoError::PlayerTurnError(_))
+    ));
+
+    game.players
+        .get_mut(0)
+        .unwrap()
+        .play_card(eight.clone())
+        .unwrap();
+    assert_eq!(
+        game.apply(Command::CallUno {
+            player: "Andy".into()
+        })
+        .unwrap(),
+        vec![Event::UnoCalled { who: "Andy".into() }]
+    );
+
+    // UNO! was called ahead, so playing without saying it is not penalized
+    let events = game
+        .play_card("Andy".into(), eight.clone(), None, false)
+        .unwrap();
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event, Event::Penalized { .. })));
+    assert_eq!(game.players.first().unwrap().get_card_count(), 1);
+}
+
+#[test]
+fn test_ai_turns_produce_events() {
+    let mut game = Game::new_with_ai("Andy".into(), 2);
+    game.start().unwrap();
+    game.current_player = game
+        .players
+        .iter()
+        .position(|player| !player.is_human())
+        .unwrap();
+
+    let events = game.play_ai_turns().unwrap();
+
+    assert!(!events.is_empty());
+    assert!(!game.is_ai_turn());
+    assert!(game.get_current_player().unwrap().is_human() || game.status == GameStatus::Finished);
+}
+
+#[test]
+fn test_legal_moves() {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    let mut game = Game::new("Andy".into());
+    game.add_player("Bob".into()).unwrap();
+    assert_eq!(game.legal_moves("Andy").unwrap(), LegalMoves::default()); // lobby
+
+    game.status = GameStatus::Running;
+    game.deck.play(Card::new(Red, Value(5)).unwrap());
+    let red_six = Card::new(Red, Value(6)).unwrap();
+    let wild = Card::new(Black, Wild).unwrap();
+    for card in [
+        red_six.clone(),
+        Card::new(Blue, Value(1)).unwrap(),
+        wild.clone(),
+    ] {
+        game.players.get_mut(0).unwrap().give_card(card);
+    }
+    game.players
+        .get_mut(1)
+        .unwrap()
+        .give_card(Card::new(Blue, Value(2)).unwrap());
+
+    let moves = game.legal_moves("Andy").unwrap();
+    assert_eq!(moves.playable_cards, vec![red_six, wild]);
+    assert!(!moves.can_draw);
+    assert!(!moves.can_pass);
+
+    assert_eq!(game.legal_moves("Bob").unwrap(), LegalMoves::default()); // out of turn
+    assert!(game.legal_moves("Alice").is_err());
+
+    game.current_player = 1;
+    let moves = game.legal_moves("Bob").unwrap();
+    assert!(moves.playable_cards.is_empty());
+    assert!(moves.can_draw);
+    assert!(!moves.can_pass);
+
+    let skip = Card::new(Blue, Skip).unwrap();
+    game.deck.play(skip.clone());
+    game.active_cards.push(skip).unwrap();
+    let moves = game.legal_moves("Bob").unwrap();
+    assert!(moves.playable_cards.is_empty());
+    assert!(!moves.can_draw);
+    assert!(moves.can_pass);
+}
+
+#[test]
+fn test_play_card_validates_with_legal_moves() {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    let mut game = Game::new("Andy".into());
+    game.add_player("Bob".into()).unwrap();
+    game.status = GameStatus::Running;
+    game.deck.play(Card::new(Red, Value(5)).unwrap());
+    for card in [
+        Card::new(Blue, Value(1)).unwrap(),
+        Card::new(Red, Value(1)).unwrap(),
+        Card::new(Red, Value(2)).unwrap(),
+    ] {
+        game.players.get_mut(0).unwrap().give_card(card);
+    }
+
+    assert!(matches!(
+        game.play_card(
+            "Andy".into(),
+            Card::new(Green, Value(7)).unwrap(),
+            None,
+            false
+        ),
+        Err(PlayCardError::PlayerHasNoSuchCard(_))
+    ));
+    assert!(matches!(
+        game.play_card(
+            "Andy".into(),
+            Card::new(Blue, Value(1)).unwrap(),
+            None,
+            false
+        ),
+        Err(PlayCardError::CardCannotBePlayed(_, _))
+    ));
+    assert!(matches!(
+        game.draw_cards("Andy".into()),
+        Err(PlayerDrawError::CanPlayInstead)
+    ));
+    assert!(game
+        .play_card(
+            "Andy".into(),
+            Card::new(Red, Value(1)).unwrap(),
+            None,
+            false
+        )
+        .is_ok());
+}
+
+#[test]
+fn test_forfeit() {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    let card = |color, symbol| Card::new(color, symbol).unwrap();
+    let mut game = GameBuilder::new("Andy")
+        .human("Bob")
+        .human("Carl")
+        .hand("Andy", vec![card(Red, Value(1)), card(Blue, Value(2))])
+        .hand("Bob", vec![card(Green, Value(3))])
+        .hand("Carl", vec![card(Yellow, Value(4))])
+        .draw_pile(vec![card(Yellow, Value(7))])
+        .top_card(card(Red, Value(9)))
+        .active_cards(vec![card(Red, Draw2)])
+        .build()
+        .unwrap();
+
+    let forfeit = |player: &str| Command::Forfeit {
+        player: player.into(),
+    };
+    assert_eq!(
+        game.apply(forfeit("Andy")).unwrap(),
+        vec![Event::PlayerForfeited {
+            who: "Andy".into(),
+            next: Some("Bob".into())
+        }]
+    );
+    assert!(game.find_player("Andy".into()).unwrap().cards().is_empty());
+    assert_eq!(game.deck().draw_pile_size(), 3);
+    // the +2 was meant for Andy
+    assert!(game.legal_moves("Bob").unwrap().can_draw);
+    assert!(matches!(
+        game.apply(forfeit("Andy")),
+        Err(CommandError::Forfeit(ForfeitError::AlreadyOut(_)))
+    ));
+
+    // Carl forfeiting out of turn leaves Bob alone, who then wins before both of them
+    let events = game.apply(forfeit("Carl")).unwrap();
+    assert_eq!(
+        events,
+        vec![
+            Event::PlayerForfeited {
+                who: "Carl".into(),
+                next: None
+            },
+            Event::PlayerFinished { who: "Bob".into() },
+            Event::PlayerFinished { who: "Carl".into() },
+            Event::PlayerFinished { who: "Andy".into() },
+            Event::GameFinished,
+        ]
+    );
+    assert_eq!(game.status(), GameStatus::Finished);
+    let finished: Vec<String> = game
+        .get_finished_players()
+        .iter()
+        .map(|player| player.name())
+        .collect();
+    assert_eq!(finished, vec!["Bob", "Carl", "Andy"]);
+}
+
+#[test]
+fn test_force_finish() {
+    use CardColor::*;
+    use CardSymbol::*;
+
+    let card = |color, symbol| Card::new(color, symbol).unwrap();
+    let mut game = GameBuilder::new("Andy")
+        .human("Bob")
+        .human("Carl")
+        .hand("Andy", vec![card(Red, Value(1)), card(Blue, Value(2))])
+        .hand("Bob", vec![card(Green, Value(3))])
+        .hand("Carl", vec![card(Yellow, Value(4))])
+        .top_card(card(Red, Value(9)))
+        .build()
+        .unwrap();
+    game.apply(Command::Forfeit {
+        player: "Bob".into(),
+    })
+    .unwrap();
+
+    // Carl holds fewer cards than Andy, Bob gave up
+    assert_eq!(
+        game.apply(Command::ForceFinish).unwrap(),
+        vec![
+            Event::PlayerFinished { who: "Carl".into() },
+            Event::PlayerFinished { who: "Andy".into() },
+            Event::PlayerFinished { who: "Bob".into() },
+            Event::GameFinished,
+        ]
+    );
+    assert_eq!(game.status(), GameStatus::Finished);
+    assert!(game.was_force_finished());
+    assert!(game.wins().is_empty());
+    assert!(matches!(
+        game.apply(Command::ForceFinish),
+        Err(CommandError::ForceFinish(ForceFinishError::GameNotRunning(
+            _
+        )))
+    ));
+
+    game.start().unwrap();
+    assert!(!game.was_force_finished());
+}
+
+#[test]
+fn test_rematch() {
+    let mut game = Game::new("Andy".into());
+    game.add_player("Bob".into()).unwrap();
+    game.add_player("Carl".into()).unwrap();
+    game.start().unwrap();
+
+    let propose = Command::ProposeRematch {
+        author: "Andy".into(),
+    };
+    assert!(matches!(
+        game.apply(propose.clone()),
+        Err(CommandError::Rematch(RematchError::GameNotFinished(_)))
+    ));
+
+    // Bob and Carl give up, Andy wins
+    game.apply(Command::Forfeit {
+        player: "Bob".into(),
+    })
+    .unwrap();
+    game.apply(Command::Forfeit {
+        player: "Carl".into(),
+    })
+    .unwrap();
+    assert_eq!(game.status(), GameStatus::Finished);
+    assert_eq!(game.wins().get("Andy"), Some(&1));
+
+    let confirm = |player: &str| Command::ConfirmRematch {
+        player: player.into(),
+    };
+    assert!(matches!(
+        game.apply(confirm("Bob")),
+        Err(CommandError::Rematch(RematchError::NoReadyCheck))
+    ));
+    assert!(matches!(
+        game.apply(Command::ProposeRematch {
+            author: "Bob".into()
+        }),
+        Err(CommandError::Rematch(RematchError::NotAuthor(_)))
+    ));
+
+    assert_eq!(
+        game.apply(propose).unwrap(),
+        vec![Event::RematchProposed { by: "Andy".into() }]
+    );
+    assert_eq!(
+        game.apply(confirm("Bob")).unwrap(),
+        vec![Event::RematchConfirmed { who: "Bob".into() }]
+    );
+    assert_eq!(
+        game.rematch_confirmed(),
+        Some(&vec!["Andy".to_string(), "Bob".to_string()])
+    );
+
+    // Carl never agreed
+    assert_eq!(
+        game.apply(Command::ReturnToLobby {
+            author: "Andy".into()
+        })
+        .unwrap(),
+        vec![
+            Event::PlayerLeft {
+                name: "Carl".into(),
+                kicked: false
+            },
+            Event::ReturnedToLobby
+        ]
+    );
+    assert_eq!(game.status(), GameStatus::Lobby);
+    assert_eq!(game.players().len(), 2);
+    assert!(game.players().iter().all(|p| !p.is_finished()));
+    assert_eq!(game.rematch_confirmed(), None);
+    assert!(game.add_player("Carl".into()).is_ok());
+    assert_eq!(game.wins().get("Andy"), Some(&1));
+}